@@ -0,0 +1,457 @@
+//! # 📚 Emoji Dictionaries: Semantics Beyond the Core 21
+//!
+//! The built-in mappings cover the core combinators and muses. A dictionary
+//! is a named, versioned list of [`EmojiSemantic`] entries kept in a TOML or
+//! JSON file, validated before anything touches the engine and merged with
+//! conflict detection against both `semantics` and `reverse_semantics`.
+//!
+//! ```toml
+//! name = "ocean-pack"
+//! version = "0.2.0"
+//!
+//! [[entries]]
+//! emoji = "🐙"
+//! expression = "Octopus"
+//! poetic_meaning = "Eight arms of parallel reduction"
+//! resonance_score = 0.9
+//! rarity_tier = "Rare"
+//! combinator_type = "Substitution"
+//! ```
+//!
+//! Every merged dictionary is recorded in the engine's dictionary version,
+//! which `generate_nft_metadata` stamps into each token so it can later be
+//! re-interpreted against the same vocabulary.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{EmojiSemantic, EmojiSemantics};
+
+/// Name of the built-in dictionary
+pub const CORE_DICTIONARY_NAME: &str = "core";
+
+/// Version of the built-in dictionary; bump it whenever the core mappings change
+pub const CORE_DICTIONARY_VERSION: &str = "1.0.0";
+
+/// 📚 A named, versioned set of emoji semantics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmojiDictionary {
+    /// Dictionary name, unique among the dictionaries merged into an engine
+    pub name: String,
+    /// Version of this dictionary's contents
+    pub version: String,
+    /// The semantics it defines
+    #[serde(default)]
+    pub entries: Vec<EmojiSemantic>,
+}
+
+/// 🏷️ Which dictionary, at which version, contributed to an engine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DictionaryStamp {
+    pub name: String,
+    pub version: String,
+}
+
+impl fmt::Display for DictionaryStamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.name, self.version)
+    }
+}
+
+/// ⚖️ What `merge_dictionary` does when an entry clashes with the engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Refuse the whole dictionary
+    #[default]
+    Reject,
+    /// Keep the engine's existing entry and skip the dictionary's
+    KeepExisting,
+    /// Replace the engine's entry with the dictionary's
+    Replace,
+}
+
+/// ⚔️ One entry that disagrees with what the engine already knows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum DictionaryConflict {
+    /// The emoji is already mapped to a different semantic
+    Emoji { emoji: String, existing_expression: String, new_expression: String },
+    /// The expression is already the reverse mapping of another emoji
+    Expression { expression: String, existing_emoji: String, new_emoji: String },
+}
+
+impl fmt::Display for DictionaryConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DictionaryConflict::Emoji { emoji, existing_expression, new_expression } => write!(
+                f,
+                "{} already means `{}`, dictionary says `{}`",
+                emoji, existing_expression, new_expression
+            ),
+            DictionaryConflict::Expression { expression, existing_emoji, new_emoji } => write!(
+                f,
+                "`{}` is already written {}, dictionary writes it {}",
+                expression, existing_emoji, new_emoji
+            ),
+        }
+    }
+}
+
+/// 📋 What a merge changed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MergeReport {
+    /// Emojis that were new to the engine
+    pub added: Vec<String>,
+    /// Emojis whose existing semantic was replaced
+    pub replaced: Vec<String>,
+    /// Emojis left as they were: identical entries, or conflicts kept under `KeepExisting`
+    pub unchanged: Vec<String>,
+    /// Conflicts found, whichever way they were resolved
+    pub conflicts: Vec<DictionaryConflict>,
+}
+
+/// ❌ Why a dictionary could not be loaded or merged
+#[derive(Debug, Error)]
+pub enum DictionaryError {
+    #[error("failed to read dictionary {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("unsupported dictionary format `{0}` (expected .toml or .json)")]
+    UnsupportedFormat(String),
+    #[error("invalid TOML dictionary: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("invalid JSON dictionary: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("dictionary entry {index} ({emoji}): {reason}")]
+    InvalidEntry { index: usize, emoji: String, reason: String },
+    #[error("dictionary {0}")]
+    InvalidHeader(String),
+    #[error("{emoji} is defined more than once in the dictionary")]
+    DuplicateEmoji { emoji: String },
+    #[error("dictionary `{0}` has already been merged")]
+    AlreadyMerged(String),
+    #[error("{} conflict(s) with existing semantics, first: {}", .0.len(), .0[0])]
+    Conflicts(Vec<DictionaryConflict>),
+}
+
+impl EmojiDictionary {
+    /// Create an empty dictionary
+    pub fn new(name: &str, version: &str) -> Self {
+        Self { name: name.to_string(), version: version.to_string(), entries: Vec::new() }
+    }
+
+    /// 📂 Load a dictionary, choosing the format from the file extension
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, DictionaryError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|source| DictionaryError::Io { path: path.to_path_buf(), source })?;
+
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        let dictionary = match extension.to_ascii_lowercase().as_str() {
+            "toml" => Self::from_toml_str(&contents)?,
+            "json" => Self::from_json_str(&contents)?,
+            other => return Err(DictionaryError::UnsupportedFormat(other.to_string())),
+        };
+        info!("📚 Loaded dictionary {} from {}", dictionary.stamp(), path.display());
+        Ok(dictionary)
+    }
+
+    /// Parse and validate a TOML dictionary
+    pub fn from_toml_str(contents: &str) -> Result<Self, DictionaryError> {
+        let dictionary: Self = toml::from_str(contents)?;
+        dictionary.validate()?;
+        Ok(dictionary)
+    }
+
+    /// Parse and validate a JSON dictionary
+    pub fn from_json_str(contents: &str) -> Result<Self, DictionaryError> {
+        let dictionary: Self = serde_json::from_str(contents)?;
+        dictionary.validate()?;
+        Ok(dictionary)
+    }
+
+    /// The stamp this dictionary leaves on an engine
+    pub fn stamp(&self) -> DictionaryStamp {
+        DictionaryStamp { name: self.name.clone(), version: self.version.clone() }
+    }
+
+    /// ✅ Check the header and every entry, independent of any engine
+    pub fn validate(&self) -> Result<(), DictionaryError> {
+        let is_label = |text: &str| !text.is_empty() && !text.contains(['@', '+']) && !text.contains(char::is_whitespace);
+        if !is_label(&self.name) {
+            return Err(DictionaryError::InvalidHeader(format!("name `{}` must be non-empty without spaces, `@` or `+`", self.name)));
+        }
+        if !is_label(&self.version) {
+            return Err(DictionaryError::InvalidHeader(format!("version `{}` must be non-empty without spaces, `@` or `+`", self.version)));
+        }
+
+        let mut seen = HashSet::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            let invalid = |reason: &str| DictionaryError::InvalidEntry {
+                index,
+                emoji: entry.emoji.clone(),
+                reason: reason.to_string(),
+            };
+
+            if entry.emoji.trim().is_empty() {
+                return Err(invalid("emoji is empty"));
+            }
+            if entry.emoji.contains(char::is_whitespace) {
+                return Err(invalid("emoji contains whitespace"));
+            }
+            if crate::grammar::is_structural(&entry.emoji) {
+                return Err(invalid("emoji is reserved by the poem grammar"));
+            }
+            if entry.expression.trim().is_empty() {
+                return Err(invalid("expression is empty"));
+            }
+            if !(0.0..=1.0).contains(&entry.resonance_score) {
+                return Err(invalid("resonance_score must be between 0.0 and 1.0"));
+            }
+            if !seen.insert(entry.emoji.as_str()) {
+                return Err(DictionaryError::DuplicateEmoji { emoji: entry.emoji.clone() });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Two entries mean the same thing if they agree on everything that drives interpretation
+fn same_meaning(a: &EmojiSemantic, b: &EmojiSemantic) -> bool {
+    a.expression == b.expression && a.resonance_score == b.resonance_score && a.combinator_type == b.combinator_type
+}
+
+impl EmojiSemantics {
+    /// 📂 Create an engine with the core semantics plus the dictionary at `path`
+    ///
+    /// Conflicts with the core mappings are rejected.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, DictionaryError> {
+        let mut engine = Self::new();
+        engine.merge_dictionary(EmojiDictionary::from_file(path)?, ConflictPolicy::Reject)?;
+        Ok(engine)
+    }
+
+    /// 📚 Merge a dictionary into this engine
+    ///
+    /// The dictionary is validated and checked against `semantics` and
+    /// `reverse_semantics` before anything changes, so a rejected merge
+    /// leaves the engine untouched. Entries identical to existing ones are
+    /// not conflicts. On success the dictionary's stamp is appended to the
+    /// engine's dictionary version.
+    pub fn merge_dictionary(&mut self, dictionary: EmojiDictionary, policy: ConflictPolicy) -> Result<MergeReport, DictionaryError> {
+        dictionary.validate()?;
+        if self.dictionaries.iter().any(|stamp| stamp.name == dictionary.name) {
+            return Err(DictionaryError::AlreadyMerged(dictionary.name));
+        }
+
+        let mut report = MergeReport::default();
+        let mut accepted = Vec::new();
+        for entry in dictionary.entries.iter() {
+            let mut conflicted = false;
+            match self.semantics.get(&entry.emoji) {
+                Some(existing) if same_meaning(existing, entry) => {
+                    report.unchanged.push(entry.emoji.clone());
+                    continue;
+                }
+                Some(existing) => {
+                    conflicted = true;
+                    report.conflicts.push(DictionaryConflict::Emoji {
+                        emoji: entry.emoji.clone(),
+                        existing_expression: existing.expression.clone(),
+                        new_expression: entry.expression.clone(),
+                    });
+                }
+                None => {}
+            }
+            if let Some(existing_emoji) = self.reverse_semantics.get(&entry.expression) {
+                if *existing_emoji != entry.emoji {
+                    conflicted = true;
+                    report.conflicts.push(DictionaryConflict::Expression {
+                        expression: entry.expression.clone(),
+                        existing_emoji: existing_emoji.clone(),
+                        new_emoji: entry.emoji.clone(),
+                    });
+                }
+            }
+
+            match (conflicted, policy) {
+                (true, ConflictPolicy::Reject) => {}
+                (true, ConflictPolicy::KeepExisting) => report.unchanged.push(entry.emoji.clone()),
+                _ => accepted.push(entry),
+            }
+        }
+
+        if policy == ConflictPolicy::Reject && !report.conflicts.is_empty() {
+            return Err(DictionaryError::Conflicts(report.conflicts));
+        }
+
+        for entry in accepted {
+            let mut semantic = entry.clone();
+            if semantic.lambda_expr.is_none() {
+                semantic.lambda_expr = Some(self.generate_lambda_expression(&semantic.expression, &semantic.combinator_type));
+            }
+
+            match self.semantics.insert(semantic.emoji.clone(), semantic.clone()) {
+                Some(previous) => {
+                    if self.reverse_semantics.get(&previous.expression) == Some(&previous.emoji) {
+                        self.reverse_semantics.remove(&previous.expression);
+                    }
+                    report.replaced.push(semantic.emoji.clone());
+                }
+                None => report.added.push(semantic.emoji.clone()),
+            }
+            self.reverse_semantics.insert(semantic.expression, semantic.emoji);
+        }
+
+        info!(
+            "📚 Merged dictionary {}: {} added, {} replaced, {} unchanged",
+            dictionary.stamp(),
+            report.added.len(),
+            report.replaced.len(),
+            report.unchanged.len()
+        );
+        self.dictionaries.push(dictionary.stamp());
+        Ok(report)
+    }
+
+    /// The dictionaries this engine was built from, core first
+    pub fn dictionaries(&self) -> &[DictionaryStamp] {
+        &self.dictionaries
+    }
+
+    /// 🏷️ Version string stamped into NFT metadata, e.g. `core@1.0.0+ocean-pack@0.2.0`
+    pub fn dictionary_version(&self) -> String {
+        self.dictionaries.iter().map(ToString::to_string).collect::<Vec<_>>().join("+")
+    }
+
+    /// True when every dictionary named in `version` is loaded here at that
+    /// version, so tokens stamped with it interpret exactly as they did
+    pub fn supports_dictionary_version(&self, version: &str) -> bool {
+        version.split('+').all(|stamp| self.dictionaries.iter().any(|loaded| loaded.to_string() == stamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CombinatorType, RarityTier};
+    use lambda_calculus_core::Expr;
+
+    const OCEAN: &str = r#"
+name = "ocean-pack"
+version = "0.2.0"
+
+[[entries]]
+emoji = "🐙"
+expression = "Octopus"
+poetic_meaning = "Eight arms of parallel reduction"
+resonance_score = 0.9
+rarity_tier = "Rare"
+combinator_type = "Substitution"
+
+[[entries]]
+emoji = "🐚"
+expression = "Shell"
+poetic_meaning = "A spiral that keeps what it holds"
+resonance_score = 0.86
+rarity_tier = "Uncommon"
+combinator_type = "Constant"
+"#;
+
+    fn entry(emoji: &str, expression: &str) -> EmojiSemantic {
+        EmojiSemantic {
+            emoji: emoji.to_string(),
+            expression: expression.to_string(),
+            poetic_meaning: "test entry".to_string(),
+            resonance_score: 0.9,
+            lambda_expr: None,
+            rarity_tier: RarityTier::Rare,
+            combinator_type: CombinatorType::Identity,
+        }
+    }
+
+    #[test]
+    fn test_toml_and_json_dictionaries_merge() {
+        let dictionary = EmojiDictionary::from_toml_str(OCEAN).unwrap();
+        let json = serde_json::to_string(&dictionary).unwrap();
+        assert_eq!(EmojiDictionary::from_json_str(&json).unwrap(), dictionary);
+
+        let mut semantics = EmojiSemantics::new();
+        let report = semantics.merge_dictionary(dictionary, ConflictPolicy::Reject).unwrap();
+        assert_eq!(report.added, vec!["🐙", "🐚"]);
+        assert_eq!(semantics.reverse_semantics.get("Octopus").map(String::as_str), Some("🐙"));
+        assert_eq!(semantics.semantics["🐚"].lambda_expr.as_deref(), Some("λx.λy.x"));
+        assert_eq!(semantics.dictionary_version(), "core@1.0.0+ocean-pack@0.2.0");
+        assert!(semantics.supports_dictionary_version("core@1.0.0"));
+        assert!(!semantics.supports_dictionary_version("core@1.0.0+ocean-pack@0.1.0"));
+
+        let (expr, _) = semantics.interpret_emoji_poem("🐙🐚").unwrap();
+        assert_eq!(expr, Expr::app(Expr::S, Expr::K));
+    }
+
+    #[test]
+    fn test_metadata_is_stamped_with_dictionary_version() {
+        let mut semantics = EmojiSemantics::new();
+        semantics.merge_dictionary(EmojiDictionary::from_toml_str(OCEAN).unwrap(), ConflictPolicy::Reject).unwrap();
+        let metadata = semantics.generate_nft_metadata("🌀🐚", 3).unwrap();
+        assert_eq!(metadata.dictionary_version, "core@1.0.0+ocean-pack@0.2.0");
+
+        // Metadata from before versioning was made with the first core dictionary
+        let mut legacy = serde_json::to_value(&metadata).unwrap();
+        legacy.as_object_mut().unwrap().remove("dictionary_version");
+        let legacy: crate::NFTMetadata = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy.dictionary_version, "core@1.0.0");
+        assert!(EmojiSemantics::new().supports_dictionary_version(&legacy.dictionary_version));
+    }
+
+    #[test]
+    fn test_invalid_dictionaries_are_rejected() {
+        let mut bad = EmojiDictionary::new("bad", "1");
+        bad.entries.push(entry("🐙", "Octopus"));
+        bad.entries.push(entry("🐙", "Squid"));
+        assert!(matches!(bad.validate(), Err(DictionaryError::DuplicateEmoji { .. })));
+
+        bad.entries[1] = EmojiSemantic { resonance_score: 1.5, ..entry("🦑", "Squid") };
+        assert!(matches!(bad.validate(), Err(DictionaryError::InvalidEntry { index: 1, .. })));
+
+        assert!(matches!(EmojiDictionary::new("", "1").validate(), Err(DictionaryError::InvalidHeader(_))));
+        assert!(EmojiDictionary::from_toml_str("name = \"x\"\nversion = 3").is_err());
+    }
+
+    #[test]
+    fn test_conflicts_follow_policy() {
+        let mut clash = EmojiDictionary::new("clash", "1");
+        clash.entries.push(entry("🌀", "Swirl")); // emoji already means S
+        clash.entries.push(entry("🐙", "Muse")); // expression already written 🎭
+        clash.entries.push(entry("🦑", "Squid"));
+
+        let mut semantics = EmojiSemantics::new();
+        match semantics.merge_dictionary(clash.clone(), ConflictPolicy::Reject) {
+            Err(DictionaryError::Conflicts(conflicts)) => assert_eq!(conflicts.len(), 2),
+            other => panic!("expected conflicts, got {:?}", other),
+        }
+        assert!(!semantics.semantics.contains_key("🦑"));
+        assert_eq!(semantics.dictionary_version(), "core@1.0.0");
+
+        let report = semantics.merge_dictionary(clash.clone(), ConflictPolicy::KeepExisting).unwrap();
+        assert_eq!(report.added, vec!["🦑"]);
+        assert_eq!(semantics.semantics["🌀"].expression, "S");
+        assert!(matches!(
+            semantics.merge_dictionary(clash.clone(), ConflictPolicy::Replace),
+            Err(DictionaryError::AlreadyMerged(_))
+        ));
+
+        let mut semantics = EmojiSemantics::new();
+        let report = semantics.merge_dictionary(clash, ConflictPolicy::Replace).unwrap();
+        assert_eq!(report.replaced, vec!["🌀"]);
+        assert_eq!(semantics.reverse_semantics.get("Muse").map(String::as_str), Some("🐙"));
+        assert!(!semantics.reverse_semantics.contains_key("S"));
+    }
+}
@@ -0,0 +1,388 @@
+//! # 📐 Emoji Grammar: Grouping and Abstraction in Poems
+//!
+//! A plain poem is still read left to right as one application, so `🌀🔮💫`
+//! means `((S K) I)`. Three structural emojis add the rest of the lambda
+//! calculus:
+//!
+//! - `🌛 … 🌜` group a sub-poem, so `🌀🌛🔮💫🌜` is `S (K I)`
+//! - `🐑` binds the emoji after it as a variable; the body runs to the end of
+//!   the enclosing group, so `🌛🐑🐱🐱🐱🌜` is `λx.(x x)`
+//! - any emoji without a semantic can be bound; unbound ones stay symbols
+//!
+//! Application binds tighter than `🐑`, and grouping overrides both. Groups
+//! and binders nest at most [`MAX_NESTING`] deep. The
+//! parser never stops at the first problem: it reports a diagnostic pointing
+//! at the offending emoji, repairs what it can and keeps going, so one pass
+//! lists every mistake in the poem.
+
+use std::fmt;
+use std::ops::Range;
+
+use lambda_calculus_core::Expr;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{EmojiSemantics, EmojiToken};
+
+/// Opens a group
+pub const GROUP_OPEN: &str = "🌛";
+/// Closes a group
+pub const GROUP_CLOSE: &str = "🌜";
+/// Introduces a lambda binding the next emoji
+pub const BINDER: &str = "🐑";
+
+/// How deeply groups and binders may nest before the parser gives up on
+/// the nested part, so hostile poems cannot exhaust the stack
+pub const MAX_NESTING: usize = 256;
+
+/// True for the emojis the grammar reserves for structure
+pub fn is_structural(emoji: &str) -> bool {
+    [GROUP_OPEN, GROUP_CLOSE, BINDER].contains(&emoji)
+}
+
+/// 🩺 A problem in a poem, pointing at the emoji that caused it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoemDiagnostic {
+    /// Index of the emoji in the poem's tokens, starting at 0
+    pub position: usize,
+    /// The offending emoji
+    pub emoji: String,
+    /// Byte range of the emoji in the poem
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for PoemDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "emoji {} ({}): {}", self.position + 1, self.emoji, self.message)
+    }
+}
+
+/// ❌ A poem that only parsed with repairs
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{} problem(s) in emoji poem: {}", .diagnostics.len(), .diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+pub struct PoemError {
+    pub diagnostics: Vec<PoemDiagnostic>,
+    /// The expression after error recovery
+    pub recovered: Expr,
+}
+
+/// 📐 The result of parsing a poem: always an expression, plus any diagnostics
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoemParse {
+    /// The parsed expression, repaired where the poem had errors
+    pub expr: Expr,
+    /// Average resonance of the emojis that became terms
+    pub resonance: f64,
+    /// Number of emojis that became terms (structural emojis and binder variables excluded)
+    pub term_count: usize,
+    pub diagnostics: Vec<PoemDiagnostic>,
+}
+
+impl PoemParse {
+    /// True when the poem parsed without repairs
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// The expression and resonance, or every diagnostic if any repair was needed
+    pub fn into_result(self) -> Result<(Expr, f64), PoemError> {
+        if self.is_ok() {
+            Ok((self.expr, self.resonance))
+        } else {
+            Err(PoemError { diagnostics: self.diagnostics, recovered: self.expr })
+        }
+    }
+}
+
+/// Recursive-descent parser over the poem's tokens
+struct PoemParser<'a> {
+    semantics: &'a EmojiSemantics,
+    tokens: Vec<EmojiToken>,
+    pos: usize,
+    /// Bound emojis and their variable names, innermost last
+    scope: Vec<(String, String)>,
+    /// Groups and binders currently open around `pos`
+    nesting: usize,
+    binders_seen: usize,
+    total_resonance: f64,
+    term_count: usize,
+    diagnostics: Vec<PoemDiagnostic>,
+}
+
+impl PoemParser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|token| token.text.as_str())
+    }
+
+    fn report(&mut self, position: usize, message: impl Into<String>) {
+        let token = &self.tokens[position];
+        self.diagnostics.push(PoemDiagnostic {
+            position,
+            emoji: token.text.clone(),
+            span: token.start..token.end,
+            message: message.into(),
+        });
+    }
+
+    /// A run of terms applied left to right, ending at a closing bracket
+    /// (inside a group) or at the end of the poem
+    fn sequence(&mut self, in_group: bool) -> Option<Expr> {
+        let mut terms = Vec::new();
+        while let Some(text) = self.peek() {
+            match text {
+                GROUP_CLOSE if in_group => break,
+                GROUP_CLOSE => {
+                    self.report(self.pos, "closing bracket without a matching 🌛");
+                    self.pos += 1;
+                }
+                BINDER => {
+                    // The body takes everything up to the end of the group
+                    if let Some(lambda) = self.lambda(in_group) {
+                        terms.push(lambda);
+                    }
+                    break;
+                }
+                GROUP_OPEN => {
+                    if let Some(group) = self.group() {
+                        terms.push(group);
+                    }
+                }
+                _ => {
+                    let leaf = self.leaf();
+                    terms.push(leaf);
+                }
+            }
+        }
+        terms.into_iter().reduce(Expr::app)
+    }
+
+    /// Report a group or binder nested too deeply and skip what it covers
+    fn too_deep(&mut self, position: usize, in_group: bool) {
+        self.report(position, format!("groups and binders nest deeper than {}", MAX_NESTING));
+        let mut open = 0;
+        while let Some(text) = self.peek() {
+            match text {
+                GROUP_OPEN => open += 1,
+                GROUP_CLOSE if open > 0 => open -= 1,
+                GROUP_CLOSE if in_group => break,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn group(&mut self) -> Option<Expr> {
+        let open = self.pos;
+        self.pos += 1;
+        if self.nesting == MAX_NESTING {
+            self.too_deep(open, true);
+            if self.peek() == Some(GROUP_CLOSE) {
+                self.pos += 1;
+            }
+            return None;
+        }
+        let reported = self.diagnostics.len();
+        self.nesting += 1;
+        let inner = self.sequence(true);
+        self.nesting -= 1;
+        if self.peek() == Some(GROUP_CLOSE) {
+            self.pos += 1;
+        } else {
+            self.report(open, "bracket is never closed with 🌜");
+        }
+        // A group emptied by an earlier error is already reported
+        if inner.is_none() && self.diagnostics.len() == reported {
+            self.report(open, "empty group");
+        }
+        inner
+    }
+
+    fn lambda(&mut self, in_group: bool) -> Option<Expr> {
+        let binder = self.pos;
+        self.pos += 1;
+        if self.nesting == MAX_NESTING {
+            self.too_deep(binder, in_group);
+            return None;
+        }
+
+        let variable = match self.peek() {
+            None => {
+                self.report(binder, "binder has no variable");
+                None
+            }
+            Some(text) if is_structural(text) => {
+                self.report(binder, format!("binder needs a variable emoji, found {}", text));
+                None
+            }
+            Some(_) => {
+                let position = self.pos;
+                self.pos += 1;
+                let token = &self.tokens[position];
+                match token.semantic_key.as_ref().and_then(|key| self.semantics.semantics.get(key)) {
+                    Some(semantic) => {
+                        let message = format!("{} already means {} and cannot be bound", token.text, semantic.expression);
+                        self.report(position, message);
+                        None
+                    }
+                    None => Some(token.text.clone()),
+                }
+            }
+        };
+
+        let name = variable.as_ref().map(|_| self.variable_name());
+        if let (Some(emoji), Some(name)) = (&variable, &name) {
+            self.scope.push((emoji.clone(), name.clone()));
+        }
+        let reported = self.diagnostics.len();
+        self.nesting += 1;
+        let body = self.sequence(in_group);
+        self.nesting -= 1;
+        if variable.is_some() {
+            self.scope.pop();
+        }
+
+        match (name, body) {
+            (Some(name), Some(body)) => Some(Expr::lambda(&name, body)),
+            (Some(_), None) => {
+                // A body that vanished to an earlier error is already reported
+                if self.diagnostics.len() == reported {
+                    self.report(binder, "binder has no body");
+                }
+                None
+            }
+            // Recover by dropping the broken binder and keeping its body
+            (None, body) => body,
+        }
+    }
+
+    /// Identifier for the next binder: x, y, z, w, x1, y1, …
+    fn variable_name(&mut self) -> String {
+        const NAMES: [&str; 4] = ["x", "y", "z", "w"];
+        let n = self.binders_seen;
+        self.binders_seen += 1;
+        match n / NAMES.len() {
+            0 => NAMES[n % NAMES.len()].to_string(),
+            round => format!("{}{}", NAMES[n % NAMES.len()], round),
+        }
+    }
+
+    fn leaf(&mut self) -> Expr {
+        let position = self.pos;
+        self.pos += 1;
+        self.term_count += 1;
+        let token = &self.tokens[position];
+
+        if let Some((_, name)) = self.scope.iter().rev().find(|(emoji, _)| *emoji == token.text) {
+            return Expr::var(name);
+        }
+        match token.semantic_key.as_ref().and_then(|key| self.semantics.semantics.get(key)) {
+            Some(semantic) => {
+                self.total_resonance += semantic.resonance_score;
+                self.semantics.create_expression_from_semantic(semantic)
+            }
+            // Unknown emoji - treat as symbol
+            None => Expr::sym(&token.text),
+        }
+    }
+}
+
+impl EmojiSemantics {
+    /// 📐 Parse a poem with the emoji grammar, recovering from errors
+    ///
+    /// An empty poem is the identity. Use `interpret_emoji_poem` to treat any
+    /// diagnostic as an error.
+    pub fn parse_poem(&self, poem: &str) -> PoemParse {
+        let mut parser = PoemParser {
+            semantics: self,
+            tokens: self.tokenize(poem),
+            pos: 0,
+            scope: Vec::new(),
+            nesting: 0,
+            binders_seen: 0,
+            total_resonance: 0.0,
+            term_count: 0,
+            diagnostics: Vec::new(),
+        };
+        let expr = parser.sequence(false).unwrap_or(Expr::I);
+
+        let resonance = if parser.term_count > 0 { parser.total_resonance / parser.term_count as f64 } else { 0.0 };
+        PoemParse { expr, resonance, term_count: parser.term_count, diagnostics: parser.diagnostics }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(poem: &str) -> PoemParse {
+        EmojiSemantics::new().parse_poem(poem)
+    }
+
+    #[test]
+    fn test_plain_poems_still_fold_left() {
+        let parsed = parse("🌀🔮💫");
+        assert!(parsed.is_ok());
+        assert_eq!(parsed.expr, Expr::app(Expr::app(Expr::S, Expr::K), Expr::I));
+        assert_eq!(parsed.term_count, 3);
+    }
+
+    #[test]
+    fn test_groups_and_binders() {
+        let parsed = parse("🌀🌛🔮💫🌜");
+        assert_eq!(parsed.expr, Expr::app(Expr::S, Expr::app(Expr::K, Expr::I)));
+
+        // (λx.x x) applied to 🐱 - the bound 🐱 is a variable, the free one a symbol
+        let parsed = parse("🌛🐑🐱🐱🐱🌜🐱");
+        assert!(parsed.is_ok());
+        let omega = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("x")));
+        assert_eq!(parsed.expr, Expr::app(omega, Expr::sym("🐱")));
+
+        // Nested binders shadow, and the body of the outer one runs to the end
+        let parsed = parse("🐑🐱🐑🐶🐱🐶");
+        let expected = Expr::lambda("x", Expr::lambda("y", Expr::app(Expr::var("x"), Expr::var("y"))));
+        assert_eq!(parsed.expr, expected);
+        assert_eq!(Expr::parse(&parsed.expr.to_string()), Ok(expected));
+    }
+
+    #[test]
+    fn test_diagnostics_point_at_the_offending_emoji() {
+        let parsed = parse("🌀🌜🌛🔮");
+        let positions: Vec<usize> = parsed.diagnostics.iter().map(|d| d.position).collect();
+        assert_eq!(positions, vec![1, 2]);
+        assert_eq!(parsed.diagnostics[0].span, "🌀".len().."🌀🌜".len());
+        assert_eq!(parsed.expr, Expr::app(Expr::S, Expr::K));
+
+        let parsed = parse("🐑🌀🌀🌛🌜🐑🐱");
+        let messages: Vec<String> = parsed.diagnostics.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "emoji 2 (🌀): 🌀 already means S and cannot be bound",
+                "emoji 4 (🌛): empty group",
+                "emoji 6 (🐑): binder has no body",
+            ]
+        );
+        assert_eq!(parsed.expr, Expr::S);
+        assert!(parsed.into_result().is_err());
+    }
+
+    #[test]
+    fn test_deep_nesting_is_a_diagnostic() {
+        let poem = format!("{}🔮{}", "🌛".repeat(5000), "🌜".repeat(5000));
+        let parsed = parse(&poem);
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(parsed.diagnostics[0].position, MAX_NESTING);
+        assert_eq!(parsed.diagnostics[0].message, format!("groups and binders nest deeper than {}", MAX_NESTING));
+
+        let parsed = parse(&"🐑🐱".repeat(5000));
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(parsed.diagnostics[0].position, 2 * MAX_NESTING);
+
+        // Nesting right up to the limit still parses
+        let poem = format!("{}🔮{}", "🌛".repeat(MAX_NESTING), "🌜".repeat(MAX_NESTING));
+        let parsed = parse(&poem);
+        assert!(parsed.is_ok());
+        assert_eq!(parsed.expr, Expr::K);
+    }
+}
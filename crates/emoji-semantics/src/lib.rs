@@ -4,16 +4,36 @@
 //! expressions, enabling the encoding of complex poetic and computational concepts
 //! through visual symbols that resonate with human consciousness.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use serde::{Serialize, Deserialize};
-use anyhow::Result;
 use log::{debug, info};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
-use lambda_calculus_core::{Expr, LambdaEngine};
+use lambda_calculus_core::{EvalError, Expr, LambdaEngine, ParseError, ReductionMetrics, Type};
+use thiserror::Error;
+
+pub mod dictionary;
+pub mod grammar;
+pub mod tokenizer;
+
+pub use dictionary::{ConflictPolicy, DictionaryError, DictionaryStamp, EmojiDictionary, MergeReport};
+pub use grammar::{PoemDiagnostic, PoemError, PoemParse};
+pub use tokenizer::EmojiToken;
+
+/// ❌ Why an emoji poem could not be evaluated
+#[derive(Debug, Error)]
+pub enum SemanticsError {
+    /// The poem does not parse cleanly
+    #[error(transparent)]
+    Poem(#[from] PoemError),
+    /// Reducing the poem's expression crossed a budget limit
+    #[error(transparent)]
+    Eval(#[from] EvalError),
+}
 
 /// 🌟 Semantic meaning of an emoji in our poetic system
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct EmojiSemantic {
     /// The emoji symbol itself
     pub emoji: String,
@@ -42,7 +62,7 @@ pub enum RarityTier {
 }
 
 /// 🔄 Types of combinators for functional composition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum CombinatorType {
     Identity,      // I combinator
     Constant,      // K combinator  
@@ -62,6 +82,10 @@ pub struct EmojiSemantics {
     pub reverse_semantics: HashMap<String, String>,
     /// Lambda calculus engine for evaluation
     pub lambda_engine: LambdaEngine,
+    /// Dictionaries merged into `semantics`, core first
+    dictionaries: Vec<DictionaryStamp>,
+    /// Source of randomness for poems and descriptions
+    rng: StdRng,
 }
 
 impl Default for EmojiSemantics {
@@ -73,10 +97,24 @@ impl Default for EmojiSemantics {
 impl EmojiSemantics {
     /// Create a new emoji semantics engine with built-in mappings
     pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    
+    /// 🎲 Create an engine whose random poems and descriptions are reproducible
+    pub fn new_with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+    
+    fn with_rng(mut rng: StdRng) -> Self {
         let mut engine = Self {
             semantics: HashMap::new(),
             reverse_semantics: HashMap::new(),
-            lambda_engine: LambdaEngine::new(),
+            lambda_engine: LambdaEngine::new().with_seed(rng.gen()),
+            dictionaries: vec![DictionaryStamp {
+                name: dictionary::CORE_DICTIONARY_NAME.to_string(),
+                version: dictionary::CORE_DICTIONARY_VERSION.to_string(),
+            }],
+            rng,
         };
         
         engine.initialize_core_semantics();
@@ -84,6 +122,21 @@ impl EmojiSemantics {
         engine
     }
     
+    /// Restart every random stream of this engine from `seed`
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.lambda_engine.reseed(self.rng.gen());
+    }
+    
+    /// 🎲 Run `operation` on a random stream started from `seed`, then give
+    /// the engine its own stream back exactly where it was
+    pub fn with_seeded_rng<T>(&mut self, seed: u64, operation: impl FnOnce(&mut Self) -> T) -> T {
+        let own = std::mem::replace(&mut self.rng, StdRng::seed_from_u64(seed));
+        let result = operation(self);
+        self.rng = own;
+        result
+    }
+    
     /// 🌟 Initialize the core emoji semantic mappings
     fn initialize_core_semantics(&mut self) {
         let core_mappings = vec![
@@ -180,68 +233,38 @@ impl EmojiSemantics {
     }
     
     /// 🎭 Interpret an emoji sequence as a lambda calculus expression
-    pub fn interpret_emoji_poem(&mut self, emoji_sequence: &str) -> Result<(Expr, f64)> {
+    ///
+    /// Poems are read with the emoji grammar (see [`grammar`]); any
+    /// diagnostic makes the poem an error.
+    pub fn interpret_emoji_poem(&mut self, emoji_sequence: &str) -> Result<(Expr, f64), PoemError> {
         debug!("🎭 Interpreting emoji poem: {}", emoji_sequence);
         
-        let emojis: Vec<char> = emoji_sequence.chars().collect();
-        if emojis.is_empty() {
-            return Ok((Expr::I, 0.0));
-        }
-        
-        let mut current_expr = Expr::I;
-        let mut total_resonance = 0.0;
-        let mut emoji_count = 0;
-        
-        for emoji_char in emojis {
-            let emoji_str = emoji_char.to_string();
-            
-            if let Some(semantic) = self.semantics.get(&emoji_str) {
-                let expr = self.create_expression_from_semantic(semantic)?;
-                current_expr = if emoji_count == 0 {
-                    expr
-                } else {
-                    Expr::app(current_expr, expr)
-                };
-                
-                total_resonance += semantic.resonance_score;
-                emoji_count += 1;
-            } else {
-                // Unknown emoji - treat as symbol
-                let unknown_expr = Expr::sym(&emoji_str);
-                current_expr = if emoji_count == 0 {
-                    unknown_expr
-                } else {
-                    Expr::app(current_expr, unknown_expr)
-                };
-                emoji_count += 1;
-            }
-        }
-        
-        let average_resonance = if emoji_count > 0 {
-            total_resonance / emoji_count as f64
-        } else {
-            0.0
-        };
+        let parsed = self.parse_poem(emoji_sequence);
+        let term_count = parsed.term_count;
+        let (expr, average_resonance) = parsed.into_result()?;
         
-        info!("✨ Interpreted {} emojis with average resonance {:.3}", emoji_count, average_resonance);
-        Ok((current_expr, average_resonance))
+        info!("✨ Interpreted {} emojis with average resonance {:.3}", term_count, average_resonance);
+        Ok((expr, average_resonance))
     }
     
     /// Create lambda expression from semantic definition
-    fn create_expression_from_semantic(&self, semantic: &EmojiSemantic) -> Result<Expr> {
+    fn create_expression_from_semantic(&self, semantic: &EmojiSemantic) -> Expr {
         match semantic.combinator_type {
-            CombinatorType::Identity => Ok(Expr::I),
-            CombinatorType::Constant => Ok(Expr::K),
-            CombinatorType::Substitution => Ok(Expr::S),
-            CombinatorType::Composition => Ok(Expr::app(Expr::S, Expr::app(Expr::K, Expr::S))),
-            CombinatorType::Recursion => Ok(Expr::y_combinator()),
-            CombinatorType::Muse => Ok(Expr::muse(&semantic.expression, semantic.resonance_score)),
-            CombinatorType::Quine => Ok(Expr::quine(Expr::sym(&semantic.emoji))),
-            CombinatorType::MetaMeme => Ok(Expr::metameme_combinator()),
+            CombinatorType::Identity => Expr::I,
+            CombinatorType::Constant => Expr::K,
+            CombinatorType::Substitution => Expr::S,
+            CombinatorType::Composition => Expr::app(Expr::S, Expr::app(Expr::K, Expr::S)),
+            CombinatorType::Recursion => Expr::y_combinator(),
+            CombinatorType::Muse => Expr::muse(&semantic.expression, semantic.resonance_score),
+            CombinatorType::Quine => Expr::quine(Expr::sym(&semantic.emoji)),
+            CombinatorType::MetaMeme => Expr::metameme_combinator(),
         }
     }
     
     /// 🌀 Convert lambda expression back to emoji sequence
+    ///
+    /// This is a poetic, lossy rendering; use `Expr::to_emoji_code` when the
+    /// term must be recovered exactly.
     pub fn expr_to_emoji(&self, expr: &Expr) -> String {
         match expr {
             Expr::S => "🌀".to_string(),
@@ -267,23 +290,58 @@ impl EmojiSemantics {
         }
     }
     
+    /// 🔮 Compile `expr` to combinators and write it as a `🌀🔮💫` poem
+    ///
+    /// Arguments that are themselves applications are grouped with
+    /// `🌛 … 🌜`, so the poem reads back as the same combinator term. Returns
+    /// `None` when the term mentions symbols, muses or free variables, which
+    /// have no combinator form.
+    pub fn expr_to_ski_poem(&self, expr: &Expr) -> Option<String> {
+        fn write(expr: &Expr, poem: &mut String) {
+            match expr {
+                Expr::App(left, right) => {
+                    write(left, poem);
+                    if let Expr::App(_, _) = right.as_ref() {
+                        poem.push_str(grammar::GROUP_OPEN);
+                        write(right, poem);
+                        poem.push_str(grammar::GROUP_CLOSE);
+                    } else {
+                        write(right, poem);
+                    }
+                }
+                Expr::S => poem.push('🌀'),
+                Expr::K => poem.push('🔮'),
+                _ => poem.push('💫'),
+            }
+        }
+        
+        let ski = expr.to_ski();
+        if !ski.is_pure_ski() {
+            return None;
+        }
+        let mut poem = String::new();
+        write(&ski, &mut poem);
+        Some(poem)
+    }
+    
     /// 🎨 Generate a random emoji poem with specified parameters
-    pub fn generate_random_poem(&self, length: usize, min_resonance: f64) -> String {
-        let mut rng = rand::thread_rng();
+    pub fn generate_random_poem(&mut self, length: usize, min_resonance: f64) -> String {
         let mut poem = String::new();
         
-        let high_resonance_emojis: Vec<&String> = self.semantics
+        // Sorted so that a seeded engine picks the same emojis on every run
+        let mut high_resonance_emojis: Vec<&String> = self.semantics
             .iter()
             .filter(|(_, semantic)| semantic.resonance_score >= min_resonance)
             .map(|(emoji, _)| emoji)
             .collect();
+        high_resonance_emojis.sort();
         
         if high_resonance_emojis.is_empty() {
             return "🌀🎭🧬".to_string(); // Fallback
         }
         
         for _ in 0..length {
-            let emoji = high_resonance_emojis[rng.gen_range(0..high_resonance_emojis.len())];
+            let emoji = high_resonance_emojis[self.rng.gen_range(0..high_resonance_emojis.len())];
             poem.push_str(emoji);
         }
         
@@ -291,9 +349,10 @@ impl EmojiSemantics {
     }
     
     /// 🏆 Generate NFT metadata for an emoji sequence
-    pub fn generate_nft_metadata(&mut self, emoji_sequence: &str, token_id: u32) -> Result<NFTMetadata> {
+    pub fn generate_nft_metadata(&mut self, emoji_sequence: &str, token_id: u32) -> Result<NFTMetadata, SemanticsError> {
         let (expr, resonance) = self.interpret_emoji_poem(emoji_sequence)?;
         let trace = self.lambda_engine.normalize(expr.clone())?;
+        let principal_type = self.lambda_engine.infer_type(&expr).ok();
         
         let rarity = self.calculate_rarity(resonance);
         let lambda_expr = format!("{}", expr);
@@ -309,12 +368,13 @@ impl EmojiSemantics {
             resonance_score: resonance,
             rarity_tier: rarity.clone(),
             reduction_steps: trace.step_count,
-            attributes: self.generate_attributes(emoji_sequence, &rarity, resonance),
+            attributes: self.generate_attributes(emoji_sequence, &rarity, resonance, principal_type.as_ref(), &trace.metrics),
+            dictionary_version: self.dictionary_version(),
         })
     }
     
     /// Generate poetic description for NFT
-    fn generate_poetic_description(&self, emoji_sequence: &str, resonance: f64) -> String {
+    fn generate_poetic_description(&mut self, emoji_sequence: &str, resonance: f64) -> String {
         let base_poems = vec![
             "In the metaprotocol's dance, where lambda meets the light,",
             "Through recursive dreams and combinatorial flight,",
@@ -324,8 +384,7 @@ impl EmojiSemantics {
             "This digital verse carries wisdom's load.",
         ];
         
-        let mut rng = rand::thread_rng();
-        let base = base_poems[rng.gen_range(0..base_poems.len())];
+        let base = base_poems[self.rng.gen_range(0..base_poems.len())];
         
         format!(
             "{}\n\nEmoji Sequence: {}\nResonance: {:.3}\n\nThis unique MetaVerse Muse embodies the eternal dance between human creativity and computational beauty, encoded in the sacred language of emojis and lambda calculus.",
@@ -334,7 +393,14 @@ impl EmojiSemantics {
     }
     
     /// Generate NFT attributes
-    fn generate_attributes(&self, emoji_sequence: &str, rarity: &RarityTier, resonance: f64) -> Vec<NFTAttribute> {
+    fn generate_attributes(
+        &self,
+        emoji_sequence: &str,
+        rarity: &RarityTier,
+        resonance: f64,
+        principal_type: Option<&Type>,
+        metrics: &ReductionMetrics,
+    ) -> Vec<NFTAttribute> {
         let mut attributes = vec![
             NFTAttribute {
                 trait_type: "Rarity".to_string(),
@@ -346,16 +412,28 @@ impl EmojiSemantics {
             },
             NFTAttribute {
                 trait_type: "Emoji Count".to_string(),
-                value: emoji_sequence.chars().count().to_string(),
+                value: self.emoji_count(emoji_sequence).to_string(),
             },
         ];
         
+        // Largest the term grew while reducing, unless the backend could not
+        // see the terms in between, and how much work it started with
+        if let Some(peak_size) = metrics.known_peak_size() {
+            attributes.push(NFTAttribute {
+                trait_type: "Peak Size".to_string(),
+                value: peak_size.to_string(),
+            });
+        }
+        attributes.push(NFTAttribute {
+            trait_type: "Redex Count".to_string(),
+            value: metrics.initial_redexes().to_string(),
+        });
+        
         // Add combinator type attributes
-        let emoji_chars: Vec<char> = emoji_sequence.chars().collect();
-        let mut combinator_types = std::collections::HashSet::new();
+        let mut combinator_types = BTreeSet::new();
         
-        for emoji_char in emoji_chars {
-            if let Some(semantic) = self.semantics.get(&emoji_char.to_string()) {
+        for token in self.tokenize(emoji_sequence) {
+            if let Some(semantic) = token.semantic_key.and_then(|key| self.semantics.get(&key)) {
                 combinator_types.insert(format!("{:?}", semantic.combinator_type));
             }
         }
@@ -367,6 +445,18 @@ impl EmojiSemantics {
             });
         }
         
+        // Typeable poems normalize under every strategy
+        attributes.push(NFTAttribute {
+            trait_type: "Typeable".to_string(),
+            value: if principal_type.is_some() { "Yes" } else { "No" }.to_string(),
+        });
+        if let Some(principal_type) = principal_type {
+            attributes.push(NFTAttribute {
+                trait_type: "Principal Type".to_string(),
+                value: principal_type.to_string(),
+            });
+        }
+        
         attributes
     }
 }
@@ -384,6 +474,26 @@ pub struct NFTMetadata {
     pub rarity_tier: RarityTier,
     pub reduction_steps: usize,
     pub attributes: Vec<NFTAttribute>,
+    /// Dictionaries the emoji sequence was interpreted with; metadata written
+    /// before dictionaries were versioned reads back as the first core release
+    #[serde(default = "legacy_dictionary_version")]
+    pub dictionary_version: String,
+}
+
+fn legacy_dictionary_version() -> String {
+    format!("{}@{}", dictionary::CORE_DICTIONARY_NAME, dictionary::CORE_DICTIONARY_VERSION)
+}
+
+impl NFTMetadata {
+    /// Reload the stored lambda expression so it can be re-reduced
+    pub fn parse_lambda_expression(&self) -> std::result::Result<Expr, ParseError> {
+        Expr::parse(&self.lambda_expression)
+    }
+    
+    /// Reload the stored reduced expression
+    pub fn parse_reduced_expression(&self) -> std::result::Result<Expr, ParseError> {
+        Expr::parse(&self.reduced_expression)
+    }
 }
 
 /// 🏷️ NFT Attribute
@@ -417,6 +527,17 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_multi_codepoint_emojis_interpret_and_count() {
+        let mut semantics = EmojiSemantics::new();
+        let (expr, _) = semantics.interpret_emoji_poem("♾️⚛️").unwrap();
+        assert_eq!(expr, Expr::app(Expr::y_combinator(), Expr::K));
+        
+        let metadata = semantics.generate_nft_metadata("⚛️⚛️👩‍🚀", 7).unwrap();
+        let count = metadata.attributes.iter().find(|attr| attr.trait_type == "Emoji Count");
+        assert_eq!(count.map(|attr| attr.value.as_str()), Some("3"));
+    }
+    
     #[test]
     fn test_expr_to_emoji() {
         let semantics = EmojiSemantics::new();
@@ -424,6 +545,19 @@ mod tests {
         assert_eq!(emoji, "🌀");
     }
     
+    #[test]
+    fn test_lambda_terms_become_ski_poems() {
+        let mut semantics = EmojiSemantics::new();
+        let poem = semantics.expr_to_ski_poem(&Expr::y_combinator()).unwrap();
+        assert!(poem.chars().all(|c| "🌀🔮💫🌛🌜".contains(c)), "{}", poem);
+        
+        let (expr, _) = semantics.interpret_emoji_poem(&poem).unwrap();
+        assert_eq!(expr, Expr::y_combinator().to_ski());
+        
+        assert_eq!(semantics.expr_to_ski_poem(&Expr::lambda("x", Expr::var("x"))).as_deref(), Some("💫"));
+        assert_eq!(semantics.expr_to_ski_poem(&Expr::sym("🐱")), None);
+    }
+    
     #[test]
     fn test_rarity_calculation() {
         let semantics = EmojiSemantics::new();
@@ -445,12 +579,124 @@ mod tests {
         assert!(!metadata.attributes.is_empty());
     }
     
+    #[test]
+    fn test_nft_metadata_reports_typeability() {
+        let mut semantics = EmojiSemantics::new();
+        let attribute = |metadata: &NFTMetadata, trait_type: &str| {
+            metadata.attributes.iter().find(|attr| attr.trait_type == trait_type).map(|attr| attr.value.clone())
+        };
+        
+        // S K K is the identity
+        let typed = semantics.generate_nft_metadata("🌀⚛️⚛️", 1).unwrap();
+        assert_eq!(attribute(&typed, "Typeable").as_deref(), Some("Yes"));
+        assert_eq!(attribute(&typed, "Principal Type").as_deref(), Some("a → a"));
+        
+        // S cannot take a muse where it needs a function
+        let untyped = semantics.generate_nft_metadata("🌀🎭", 2).unwrap();
+        assert_eq!(attribute(&untyped, "Typeable").as_deref(), Some("No"));
+        assert_eq!(attribute(&untyped, "Principal Type"), None);
+    }
+    
+    #[test]
+    fn test_nft_metadata_reports_reduction_metrics() {
+        let mut semantics = EmojiSemantics::new();
+        let attribute = |metadata: &NFTMetadata, trait_type: &str| {
+            metadata.attributes.iter().find(|attr| attr.trait_type == trait_type).map(|attr| attr.value.clone())
+        };
+        
+        // S K K (S K) → K (S K) (K (S K)) → S K: one S redex, and the S step grows the term
+        let metadata = semantics.generate_nft_metadata("🌀⚛️⚛️🌛🌀⚛️🌜", 1).unwrap();
+        assert_eq!(attribute(&metadata, "Redex Count").as_deref(), Some("1"));
+        assert_eq!(attribute(&metadata, "Peak Size").as_deref(), Some("11"));
+        
+        // The machine never sees the larger middle term, so it claims no peak
+        semantics.lambda_engine.backend = lambda_calculus_core::Backend::Machine;
+        let metadata = semantics.generate_nft_metadata("🌀⚛️⚛️🌛🌀⚛️🌜", 2).unwrap();
+        assert_eq!(attribute(&metadata, "Redex Count").as_deref(), Some("1"));
+        assert_eq!(attribute(&metadata, "Peak Size"), None);
+    }
+    
+    #[test]
+    fn test_nft_metadata_expressions_reload() {
+        let mut semantics = EmojiSemantics::new();
+        let metadata = semantics.generate_nft_metadata("🌀🎭🧬", 1).unwrap();
+        
+        let (expr, _) = semantics.interpret_emoji_poem("🌀🎭🧬").unwrap();
+        assert_eq!(metadata.parse_lambda_expression().unwrap(), expr);
+        
+        let trace = semantics.lambda_engine.normalize(expr).unwrap();
+        assert_eq!(metadata.parse_reduced_expression().unwrap(), trace.final_form);
+    }
+    
     #[test]
     fn test_random_poem_generation() {
-        let semantics = EmojiSemantics::new();
+        let mut semantics = EmojiSemantics::new();
         let poem = semantics.generate_random_poem(5, 0.90);
         
         assert_eq!(poem.chars().count(), 5);
         assert!(!poem.is_empty());
     }
+    
+    #[test]
+    fn test_seeded_generation_is_reproducible() {
+        let collection = |seed| {
+            let mut semantics = EmojiSemantics::new_with_seed(seed);
+            (1..=5)
+                .map(|token_id| {
+                    let poem = semantics.generate_random_poem(4, 0.95);
+                    serde_json::to_string(&semantics.generate_nft_metadata(&poem, token_id).unwrap()).unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        
+        assert_eq!(collection(42), collection(42));
+        assert_ne!(collection(42), collection(43));
+    }
+    
+    #[test]
+    fn test_machine_backend_agrees_with_substitution() {
+        use lambda_calculus_core::{Backend, EvalBudget, Termination};
+        
+        let mut semantics = EmojiSemantics::new();
+        let mut emojis: Vec<String> = semantics.semantics.keys().cloned().collect();
+        emojis.sort();
+        
+        // Every poem of one or two emojis, plus random longer ones
+        let mut corpus: Vec<String> = emojis.clone();
+        for first in &emojis {
+            for second in &emojis {
+                corpus.push(format!("{}{}", first, second));
+            }
+        }
+        let mut rng = StdRng::seed_from_u64(0x4b21);
+        for _ in 0..200 {
+            let length = rng.gen_range(3..=6);
+            corpus.push((0..length).map(|_| emojis[rng.gen_range(0..emojis.len())].as_str()).collect());
+        }
+        
+        // S duplicates its argument, so keep runaway poems small
+        let budget = EvalBudget::unlimited().with_max_term_size(2_000);
+        let mut agreed = 0;
+        for poem in &corpus {
+            let (expr, _) = semantics.interpret_emoji_poem(poem).unwrap();
+            let substitution = match LambdaEngine::new().with_max_steps(200).with_budget(budget).try_normalize(expr.clone()) {
+                Ok(trace) if trace.termination == Termination::NormalForm => trace,
+                _ => continue,
+            };
+            let machine = LambdaEngine::new()
+                .with_max_steps(400)
+                .with_backend(Backend::Machine)
+                .normalize(expr)
+                .unwrap();
+            
+            assert_eq!(machine.termination, Termination::NormalForm, "machine diverged on {}", poem);
+            assert!(
+                machine.final_form.alpha_eq(&substitution.final_form),
+                "{}: machine gave {} but substitution gave {}",
+                poem, machine.final_form, substitution.final_form
+            );
+            agreed += 1;
+        }
+        assert!(agreed > emojis.len());
+    }
 }
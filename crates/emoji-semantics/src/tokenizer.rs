@@ -0,0 +1,134 @@
+//! # ✂️ Emoji Tokenizer: Splitting Poems Where Readers Would
+//!
+//! Many emojis are more than one codepoint: `♾️` is a base plus the U+FE0F
+//! variation selector, `👍🏽` carries a skin-tone modifier, `👩‍🚀` joins two
+//! emojis with a zero-width joiner and `🇯🇵` is a pair of regional indicators.
+//! The tokenizer walks extended grapheme clusters instead of `char`s and
+//! matches the longest run of clusters that names an entry in
+//! `EmojiSemantics::semantics`, so none of these are shredded into loose
+//! codepoints.
+
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::EmojiSemantics;
+
+/// Text-presentation (U+FE0E) and emoji-presentation (U+FE0F) selectors
+const VARIATION_SELECTORS: [char; 2] = ['\u{FE0E}', '\u{FE0F}'];
+
+/// ✂️ One emoji of a poem and where it sits in the input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmojiToken {
+    /// The input text covered by this token
+    pub text: String,
+    /// Byte offset of the token in the input
+    pub start: usize,
+    /// Byte offset just past the token
+    pub end: usize,
+    /// The `semantics` entry the token matched, if any
+    pub semantic_key: Option<String>,
+}
+
+impl EmojiToken {
+    /// True when the token matched a known emoji semantic
+    pub fn is_known(&self) -> bool {
+        self.semantic_key.is_some()
+    }
+}
+
+impl EmojiSemantics {
+    /// ✂️ Split `input` into emoji tokens
+    ///
+    /// At each position the longest run of grapheme clusters that names a
+    /// semantic wins; variation selectors are ignored when matching, so `♾`
+    /// and `♾️` both find the Infinity entry. Anything unmatched becomes a
+    /// single-cluster token with no `semantic_key`.
+    pub fn tokenize(&self, input: &str) -> Vec<EmojiToken> {
+        let clusters: Vec<(usize, &str)> = input.grapheme_indices(true).collect();
+        let longest_key = self
+            .semantics
+            .keys()
+            .map(|key| key.graphemes(true).count())
+            .max()
+            .unwrap_or(1)
+            .max(1);
+
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < clusters.len() {
+            let start = clusters[i].0;
+            let end_of = |j: usize| clusters.get(j).map_or(input.len(), |(offset, _)| *offset);
+
+            let longest = (i + 1..=(i + longest_key).min(clusters.len()))
+                .rev()
+                .find_map(|j| self.lookup_key(&input[start..end_of(j)]).map(|key| (j, key)));
+
+            let (next, semantic_key) = match longest {
+                Some((j, key)) => (j, Some(key)),
+                None => (i + 1, None),
+            };
+            let end = end_of(next);
+            tokens.push(EmojiToken { text: input[start..end].to_string(), start, end, semantic_key });
+            i = next;
+        }
+        tokens
+    }
+
+    /// Number of emoji tokens in `input`, as counted by `tokenize`
+    pub fn emoji_count(&self, input: &str) -> usize {
+        self.tokenize(input).len()
+    }
+
+    /// The `semantics` key that `text` names, ignoring variation selectors
+    fn lookup_key(&self, text: &str) -> Option<String> {
+        if self.semantics.contains_key(text) {
+            return Some(text.to_string());
+        }
+        let bare: String = text.chars().filter(|c| !VARIATION_SELECTORS.contains(c)).collect();
+        let presented = format!("{}\u{FE0F}", bare);
+        [bare, presented].into_iter().find(|key| self.semantics.contains_key(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(tokens: &[EmojiToken]) -> Vec<&str> {
+        tokens.iter().map(|token| token.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_variation_selector_entries_match() {
+        let semantics = EmojiSemantics::new();
+        let tokens = semantics.tokenize("🌀♾️⚛️");
+
+        assert_eq!(texts(&tokens), vec!["🌀", "♾️", "⚛️"]);
+        assert!(tokens.iter().all(EmojiToken::is_known));
+        assert_eq!(tokens[1].start, "🌀".len());
+        assert_eq!(tokens[2].end, "🌀♾️⚛️".len());
+
+        // Without the selector the base character still finds the entry
+        assert_eq!(semantics.tokenize("♾")[0].semantic_key.as_deref(), Some("♾️"));
+    }
+
+    #[test]
+    fn test_clusters_are_not_shredded() {
+        let semantics = EmojiSemantics::new();
+        let tokens = semantics.tokenize("👩‍🚀👍🏽🇯🇵🎭");
+
+        assert_eq!(texts(&tokens), vec!["👩‍🚀", "👍🏽", "🇯🇵", "🎭"]);
+        assert!(!tokens[0].is_known());
+        assert_eq!(tokens[3].semantic_key.as_deref(), Some("🎭"));
+    }
+
+    #[test]
+    fn test_longest_match_spans_several_clusters() {
+        let mut semantics = EmojiSemantics::new();
+        semantics.add_semantic("🌀🎭", "Swirl", "A spiral muse", 0.9, crate::CombinatorType::Muse);
+
+        let tokens = semantics.tokenize("🌀🎭🌀");
+        assert_eq!(texts(&tokens), vec!["🌀🎭", "🌀"]);
+        assert_eq!(semantics.emoji_count("🌀🎭🌀"), 2);
+    }
+}
@@ -0,0 +1,52 @@
+//! 🏛️ `normalize` on the shared term arena versus the Krivine machine
+//!
+//! Run with `cargo bench -p lambda-calculus-core`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use lambda_calculus_core::{Backend, Expr, LambdaEngine};
+
+/// Church numeral `λf.λx.f (f (... x))`
+fn church(n: usize) -> Expr {
+    let mut body = Expr::var("x");
+    for _ in 0..n {
+        body = Expr::app(Expr::var("f"), body);
+    }
+    Expr::lambda("f", Expr::lambda("x", body))
+}
+
+/// `Y f` unfolds one more `f` per step - a long trace over a growing term
+fn long_traces(c: &mut Criterion) {
+    let expr = Expr::app(Expr::y_combinator(), Expr::sym("f"));
+    let mut group = c.benchmark_group("y_unfolding");
+    for steps in [250, 500, 1_000] {
+        let engine = LambdaEngine::new().with_max_steps(steps);
+        group.bench_with_input(BenchmarkId::new("arena", steps), &expr, |b, expr| {
+            b.iter(|| engine.clone().normalize(black_box(expr.clone())).unwrap())
+        });
+        let machine = engine.clone().with_backend(Backend::Machine);
+        group.bench_with_input(BenchmarkId::new("machine", steps), &expr, |b, expr| {
+            b.iter(|| machine.clone().normalize(black_box(expr.clone())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// `n n` on Church numerals: exponential output built from heavily shared parts
+fn church_powers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("church_self_power");
+    for n in [2, 3] {
+        let expr = Expr::app(church(n), church(n));
+        let engine = LambdaEngine::new().with_max_steps(100_000);
+        group.bench_with_input(BenchmarkId::new("arena", n), &expr, |b, expr| {
+            b.iter(|| engine.clone().normalize(black_box(expr.clone())).unwrap())
+        });
+        let machine = engine.clone().with_backend(Backend::Machine);
+        group.bench_with_input(BenchmarkId::new("machine", n), &expr, |b, expr| {
+            b.iter(|| machine.clone().normalize(black_box(expr.clone())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, long_traces, church_powers);
+criterion_main!(benches);
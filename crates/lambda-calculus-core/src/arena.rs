@@ -0,0 +1,698 @@
+//! # 🏛️ Term Arena: Hash-Consed, Shared Expressions
+//!
+//! [`LambdaEngine::normalize`] does its work here rather than on owned
+//! [`Expr`] trees. A [`TermArena`] stores each distinct subterm exactly once
+//! and refers to it by [`TermId`]: a reduction step only allocates the nodes
+//! on the path from the root to the redex that are genuinely new, and a trace
+//! is just a list of ids, so long reductions stay in bounded memory.
+//!
+//! Bound variables are stored as de Bruijn indices and binder names are only
+//! hints for printing, so substitution never has to rename anything. Every
+//! stored term also gets an alpha class, shared by exactly the terms that are
+//! alpha-equivalent to it, which is what cycle detection compares.
+//!
+//! Size, depth and free variables are computed once, when a term is
+//! interned, so budget checks cost nothing per step. `Expr` stays the public
+//! view via [`TermArena::from_expr`] and [`TermArena::to_expr`].
+
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+
+use crate::metrics::TermMetrics;
+use crate::strategy::Walk;
+use crate::trace::{Path, PathStep, Redex};
+use crate::{Expr, LambdaEngine, Rule};
+
+/// 🏷️ Handle to a term stored in a `TermArena`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TermId(u32);
+
+/// 🧱 One node of a shared term; children are ids into the same arena
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Term {
+    /// A variable bound by an enclosing lambda, as its de Bruijn index
+    Bound(u32),
+    /// A free variable, by name
+    Var(String),
+    /// A lambda; its name is only a hint for printing
+    Lambda(String, TermId),
+    App(TermId, TermId),
+    Sym(String),
+    S,
+    K,
+    I,
+    Muse(String, u32),
+    Quine(TermId),
+    DNA(Vec<u8>),
+}
+
+/// A stored term with facts computed once, when it is interned
+#[derive(Debug, Clone)]
+struct Node {
+    term: Term,
+    /// Size of the expanded tree, saturating
+    size: usize,
+    depth: usize,
+    /// Names of the free variables
+    free_vars: Rc<BTreeSet<String>>,
+    /// One more than the largest index that escapes this term, 0 when none does
+    loose: u32,
+    /// The first stored term alpha-equivalent to this one
+    class: TermId,
+}
+
+/// 🏛️ Hash-consing store: structurally equal terms share one `TermId`
+#[derive(Debug, Clone, Default)]
+pub struct TermArena {
+    nodes: Vec<Node>,
+    index: HashMap<Term, TermId>,
+    /// Terms with binder names blanked and children replaced by their classes
+    classes: HashMap<Term, TermId>,
+    no_free_vars: Rc<BTreeSet<String>>,
+}
+
+impl TermArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of distinct terms stored
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn term(&self, id: TermId) -> &Term {
+        &self.node(id).term
+    }
+
+    /// Number of nodes `id` would have as an `Expr` tree
+    pub fn size(&self, id: TermId) -> usize {
+        self.node(id).size
+    }
+
+    pub fn depth(&self, id: TermId) -> usize {
+        self.node(id).depth
+    }
+
+    pub fn is_free_in(&self, var: &str, id: TermId) -> bool {
+        self.node(id).free_vars.contains(var)
+    }
+
+    /// 🪞 The alpha class of `id`: two terms share one exactly when they are
+    /// alpha-equivalent
+    pub fn alpha_class(&self, id: TermId) -> TermId {
+        self.node(id).class
+    }
+
+    fn node(&self, id: TermId) -> &Node {
+        &self.nodes[id.0 as usize]
+    }
+
+    /// 🔗 Store `term`, or return the id it already has
+    pub fn intern(&mut self, term: Term) -> TermId {
+        if let Some(&id) = self.index.get(&term) {
+            return id;
+        }
+
+        let (size, depth, free_vars, loose) = match &term {
+            Term::Bound(index) => (1, 1, Rc::clone(&self.no_free_vars), index + 1),
+            Term::Var(name) => (1, 1, Rc::new(BTreeSet::from([name.clone()])), 0),
+            Term::Lambda(_, body) => {
+                let body = self.node(*body);
+                (body.size.saturating_add(1), body.depth + 1, Rc::clone(&body.free_vars), body.loose.saturating_sub(1))
+            }
+            Term::App(left, right) => {
+                let (left, right) = (self.node(*left), self.node(*right));
+                let free_vars = if right.free_vars.is_subset(&left.free_vars) {
+                    Rc::clone(&left.free_vars)
+                } else if left.free_vars.is_subset(&right.free_vars) {
+                    Rc::clone(&right.free_vars)
+                } else {
+                    Rc::new(left.free_vars.union(&right.free_vars).cloned().collect())
+                };
+                let size = left.size.saturating_add(right.size).saturating_add(1);
+                (size, left.depth.max(right.depth) + 1, free_vars, left.loose.max(right.loose))
+            }
+            Term::Quine(inner) => {
+                let inner = self.node(*inner);
+                (inner.size.saturating_add(1), inner.depth + 1, Rc::clone(&inner.free_vars), inner.loose)
+            }
+            _ => (1, 1, Rc::clone(&self.no_free_vars), 0),
+        };
+
+        let id = TermId(self.nodes.len() as u32);
+        let shape = match &term {
+            Term::Lambda(_, body) => Term::Lambda(String::new(), self.alpha_class(*body)),
+            Term::App(left, right) => Term::App(self.alpha_class(*left), self.alpha_class(*right)),
+            Term::Quine(inner) => Term::Quine(self.alpha_class(*inner)),
+            leaf => leaf.clone(),
+        };
+        let class = *self.classes.entry(shape).or_insert(id);
+        self.nodes.push(Node { term: term.clone(), size, depth, free_vars, loose, class });
+        self.index.insert(term, id);
+        id
+    }
+
+    pub(crate) fn app(&mut self, left: TermId, right: TermId) -> TermId {
+        self.intern(Term::App(left, right))
+    }
+
+    /// 📥 Store an `Expr`, sharing every repeated subterm
+    pub fn from_expr(&mut self, expr: &Expr) -> TermId {
+        self.intern_under(expr, &mut Vec::new())
+    }
+
+    /// Store `expr` as it sits under `binders`, innermost last
+    ///
+    /// Walks with an explicit stack, so a deeply nested `Expr` cannot
+    /// overflow the call stack.
+    pub(crate) fn intern_under<'e>(&mut self, expr: &'e Expr, binders: &mut Vec<&'e str>) -> TermId {
+        enum Visit<'e> {
+            Enter(&'e Expr),
+            Lambda(&'e str),
+            App,
+            Quine,
+        }
+
+        let mut pending = vec![Visit::Enter(expr)];
+        let mut built = Vec::new();
+        while let Some(visit) = pending.pop() {
+            let term = match visit {
+                Visit::Enter(expr) => match expr {
+                    Expr::Var(name) => match binders.iter().rev().position(|binder| binder == name) {
+                        Some(index) => Term::Bound(index as u32),
+                        None => Term::Var(name.clone()),
+                    },
+                    Expr::Lambda(param, body) => {
+                        binders.push(param);
+                        pending.extend([Visit::Lambda(param), Visit::Enter(body)]);
+                        continue;
+                    }
+                    Expr::App(left, right) => {
+                        pending.extend([Visit::App, Visit::Enter(right), Visit::Enter(left)]);
+                        continue;
+                    }
+                    Expr::Quine(inner) => {
+                        pending.extend([Visit::Quine, Visit::Enter(inner)]);
+                        continue;
+                    }
+                    Expr::Sym(symbol) => Term::Sym(symbol.clone()),
+                    Expr::S => Term::S,
+                    Expr::K => Term::K,
+                    Expr::I => Term::I,
+                    Expr::Muse(name, resonance) => Term::Muse(name.clone(), *resonance),
+                    Expr::DNA(data) => Term::DNA(data.clone()),
+                },
+                Visit::Lambda(param) => {
+                    binders.pop();
+                    let body = built.pop().expect("body interned");
+                    Term::Lambda(param.to_string(), body)
+                }
+                Visit::App => {
+                    let right = built.pop().expect("argument interned");
+                    let left = built.pop().expect("function interned");
+                    Term::App(left, right)
+                }
+                Visit::Quine => Term::Quine(built.pop().expect("inner term interned")),
+            };
+            built.push(self.intern(term));
+        }
+        built.pop().expect("root interned")
+    }
+
+    /// 📤 Expand a stored term back into an owned `Expr` tree
+    ///
+    /// Binders keep their hinted names unless that would capture a free
+    /// variable or an outer binder the body refers to; then they are primed,
+    /// so `λy` under a free `y` prints as `λy'`.
+    pub fn to_expr(&self, id: TermId) -> Expr {
+        self.to_expr_in(id, &mut Vec::new())
+    }
+
+    /// Expand `id` as it sits under binders named `binders`, innermost last
+    pub(crate) fn to_expr_in(&self, id: TermId, binders: &mut Vec<String>) -> Expr {
+        enum Visit {
+            Enter(TermId),
+            Lambda,
+            App,
+            Quine,
+        }
+
+        let mut pending = vec![Visit::Enter(id)];
+        let mut built = Vec::new();
+        while let Some(visit) = pending.pop() {
+            let expr = match visit {
+                Visit::Enter(id) => match self.term(id) {
+                    Term::Bound(index) => match binders.len().checked_sub(*index as usize + 1) {
+                        Some(position) => Expr::var(&binders[position]),
+                        None => Expr::var(&format!("#{}", index)),
+                    },
+                    Term::Var(name) => Expr::var(name),
+                    Term::Lambda(hint, body) => {
+                        let mut name = hint.clone();
+                        while self.captures(*body, &name, binders) {
+                            name.push('\'');
+                        }
+                        binders.push(name);
+                        pending.extend([Visit::Lambda, Visit::Enter(*body)]);
+                        continue;
+                    }
+                    Term::App(left, right) => {
+                        pending.extend([Visit::App, Visit::Enter(*right), Visit::Enter(*left)]);
+                        continue;
+                    }
+                    Term::Quine(inner) => {
+                        pending.extend([Visit::Quine, Visit::Enter(*inner)]);
+                        continue;
+                    }
+                    Term::Sym(symbol) => Expr::sym(symbol),
+                    Term::S => Expr::S,
+                    Term::K => Expr::K,
+                    Term::I => Expr::I,
+                    Term::Muse(name, resonance) => Expr::Muse(name.clone(), *resonance),
+                    Term::DNA(data) => Expr::dna(data.clone()),
+                },
+                Visit::Lambda => {
+                    let body = built.pop().expect("body expanded");
+                    let name = binders.pop().expect("pushed on entry");
+                    Expr::Lambda(name, Box::new(body))
+                }
+                Visit::App => {
+                    let right = built.pop().expect("argument expanded");
+                    let left = built.pop().expect("function expanded");
+                    Expr::app(left, right)
+                }
+                Visit::Quine => Expr::quine(built.pop().expect("inner term expanded")),
+            };
+            built.push(expr);
+        }
+        built.pop().expect("root expanded")
+    }
+
+    /// True when naming the binder of `body` `name` would capture a free
+    /// variable, or an outer binder `body` may refer to
+    fn captures(&self, body: TermId, name: &str, binders: &[String]) -> bool {
+        let outer = (self.node(body).loose as usize).saturating_sub(1).min(binders.len());
+        self.is_free_in(name, body) || binders[binders.len() - outer..].iter().any(|binder| binder == name)
+    }
+
+    /// 🔄 β for a stored lambda body: index 0 becomes `argument`, and the
+    /// indices of the outer binders drop by one
+    fn instantiate(&mut self, body: TermId, argument: TermId) -> TermId {
+        let mut shifted = HashMap::new();
+        self.rebuild(body, 0, &mut HashMap::new(), &mut |arena, index, depth| {
+            if index == depth {
+                arena.shift(argument, depth, 0, shifted.entry(depth).or_default())
+            } else {
+                arena.intern(Term::Bound(index - 1))
+            }
+        })
+    }
+
+    /// Move `id` under one more binder
+    pub(crate) fn lift(&mut self, id: TermId) -> TermId {
+        self.shift(id, 1, 0, &mut HashMap::new())
+    }
+
+    /// Move `id` under `by` more binders: indices from `cutoff` up escape it
+    /// and grow by `by`
+    fn shift(&mut self, id: TermId, by: u32, cutoff: u32, memo: &mut HashMap<(TermId, u32), TermId>) -> TermId {
+        if by == 0 {
+            return id;
+        }
+        self.rebuild(id, cutoff, memo, &mut |arena, index, _| arena.intern(Term::Bound(index + by)))
+    }
+
+    /// Rewrite every index of `root` that escapes it when it sits under
+    /// `level` binders, with `bound(arena, index, binders)`
+    ///
+    /// Results depend only on the subterm and the binders above it, so each
+    /// shared node is rewritten once; the walk keeps its own stack, so deep
+    /// terms cannot overflow the call stack.
+    fn rebuild(
+        &mut self,
+        root: TermId,
+        level: u32,
+        memo: &mut HashMap<(TermId, u32), TermId>,
+        bound: &mut dyn FnMut(&mut Self, u32, u32) -> TermId,
+    ) -> TermId {
+        let done = |arena: &Self, memo: &HashMap<(TermId, u32), TermId>, id: TermId, level: u32| {
+            if arena.node(id).loose <= level { Some(id) } else { memo.get(&(id, level)).copied() }
+        };
+
+        let mut pending = vec![(root, level, false)];
+        while let Some((id, level, children_done)) = pending.pop() {
+            if done(self, memo, id, level).is_some() {
+                continue;
+            }
+            let term = self.term(id).clone();
+            if !children_done {
+                pending.push((id, level, true));
+                match term {
+                    Term::Lambda(_, body) => pending.push((body, level + 1, false)),
+                    Term::App(left, right) => pending.extend([(right, level, false), (left, level, false)]),
+                    Term::Quine(inner) => pending.push((inner, level, false)),
+                    _ => {}
+                }
+                continue;
+            }
+            let child = |memo: &HashMap<(TermId, u32), TermId>, id, level| {
+                done(self, memo, id, level).expect("children are rewritten first")
+            };
+            let result = match term {
+                Term::Bound(index) => bound(self, index, level),
+                Term::Lambda(hint, body) => {
+                    let body = child(memo, body, level + 1);
+                    self.intern(Term::Lambda(hint, body))
+                }
+                Term::App(left, right) => {
+                    let (left, right) = (child(memo, left, level), child(memo, right, level));
+                    self.app(left, right)
+                }
+                Term::Quine(inner) => {
+                    let inner = child(memo, inner, level);
+                    self.intern(Term::Quine(inner))
+                }
+                _ => id,
+            };
+            memo.insert((id, level), result);
+        }
+        done(self, memo, root, level).expect("root rewritten")
+    }
+
+    /// Occurrences of the variable with de Bruijn `index`, counted as in the
+    /// expanded tree
+    pub(crate) fn occurrences(&self, id: TermId, index: u32) -> usize {
+        let mut memo: HashMap<(TermId, u32), usize> = HashMap::new();
+        let count = |memo: &HashMap<(TermId, u32), usize>, id: TermId, index: u32| {
+            if self.node(id).loose <= index { Some(0) } else { memo.get(&(id, index)).copied() }
+        };
+
+        let mut pending = vec![(id, index, false)];
+        while let Some((id, index, children_done)) = pending.pop() {
+            if count(&memo, id, index).is_some() {
+                continue;
+            }
+            let children = match *self.term(id) {
+                Term::Lambda(_, body) => vec![(body, index + 1)],
+                Term::Quine(inner) => vec![(inner, index)],
+                Term::App(left, right) => vec![(left, index), (right, index)],
+                _ => Vec::new(),
+            };
+            if !children_done {
+                pending.push((id, index, true));
+                pending.extend(children.into_iter().map(|(child, index)| (child, index, false)));
+                continue;
+            }
+            let here = match *self.term(id) {
+                Term::Bound(bound) => usize::from(bound == index),
+                _ => children
+                    .into_iter()
+                    .map(|(child, index)| count(&memo, child, index).expect("children are counted first"))
+                    .fold(0, usize::saturating_add),
+            };
+            memo.insert((id, index), here);
+        }
+        count(&memo, id, index).expect("root counted")
+    }
+}
+
+/// ⚙️ The engine's rules applied to terms in a `TermArena`
+///
+/// Strategies (see `strategy.rs`) walk the term and ask the reducer to
+/// contract the redex they pick. The reducer tracks the names of the lambdas
+/// a walk is under, for the muse rules, which render their argument.
+pub(crate) struct Reducer {
+    pub(crate) arena: TermArena,
+    environment: HashMap<String, TermId>,
+    /// Hinted names of the lambdas around the subterm being reduced, innermost last
+    pub(crate) scope: Vec<String>,
+    redex_counts: HashMap<TermId, usize>,
+    /// What each strategy walk found below a term, so a step only walks the
+    /// nodes the previous step rebuilt
+    pub(crate) walks: HashMap<(TermId, Walk), Option<(TermId, Redex)>>,
+}
+
+impl Reducer {
+    pub(crate) fn new(engine: &LambdaEngine) -> Self {
+        let mut arena = TermArena::new();
+        let environment = engine
+            .environment
+            .iter()
+            .map(|(name, value)| (name.clone(), arena.from_expr(value)))
+            .collect();
+        Self { arena, environment, scope: Vec::new(), redex_counts: HashMap::new(), walks: HashMap::new() }
+    }
+
+    /// The rule that would contract `id` at its root, without building the result
+    pub(crate) fn redex_rule(&self, id: TermId) -> Option<Rule> {
+        let term = |id| self.arena.term(id);
+        match term(id) {
+            Term::Var(name) => self.environment.contains_key(name).then_some(Rule::EnvLookup),
+            Term::App(left, _) => match term(*left) {
+                Term::Lambda(_, _) => Some(Rule::Beta),
+                Term::I => Some(Rule::I),
+                Term::Muse(_, _) => Some(Rule::MuseApply),
+                Term::Quine(_) => Some(Rule::QuineApply),
+                Term::App(inner, _) => match term(*inner) {
+                    Term::K => Some(Rule::K),
+                    Term::App(s, _) if *term(*s) == Term::S => Some(Rule::S),
+                    _ => None,
+                },
+                _ => None,
+            },
+            Term::Muse(_, resonance) => (*resonance < 1000).then_some(Rule::MuseEvolve),
+            Term::Quine(_) => Some(Rule::QuineUnwrap),
+            _ => None,
+        }
+    }
+
+    /// ⚡ Contract the redex at the root of `id`, if there is one
+    ///
+    /// Combinators only fire once saturated: `S` needs three arguments, `K` two
+    /// and `I` one. A partially applied combinator such as `S f` or `K x` is a
+    /// normal form, and it is up to the strategy whether its arguments reduce.
+    pub(crate) fn contract(&mut self, id: TermId) -> Option<(TermId, Redex)> {
+        let rule = self.redex_rule(id)?;
+        let mut duplicates = false;
+        let after = match (rule, self.arena.term(id).clone()) {
+            (Rule::EnvLookup, Term::Var(name)) => self.environment[&name],
+            (Rule::Beta, Term::App(function, argument)) => {
+                let Term::Lambda(_, body) = *self.arena.term(function) else { unreachable!("β needs a lambda") };
+                duplicates = self.arena.occurrences(body, 0) > 1;
+                self.arena.instantiate(body, argument)
+            }
+            (Rule::I, Term::App(_, argument)) => argument,
+            (Rule::K, Term::App(function, _)) => {
+                let Term::App(_, kept) = *self.arena.term(function) else { unreachable!("K needs two arguments") };
+                kept
+            }
+            (Rule::S, Term::App(function, x)) => {
+                let Term::App(sf, g) = *self.arena.term(function) else { unreachable!("S needs three arguments") };
+                let Term::App(_, f) = *self.arena.term(sf) else { unreachable!("S needs three arguments") };
+                // The argument is shared between both copies, not cloned
+                duplicates = true;
+                let fx = self.arena.app(f, x);
+                let gx = self.arena.app(g, x);
+                self.arena.app(fx, gx)
+            }
+            (Rule::QuineApply, Term::App(quine, argument)) => {
+                let Term::Quine(inner) = *self.arena.term(quine) else { unreachable!("quine-apply needs a quine") };
+                let applied = self.arena.app(inner, argument);
+                self.arena.intern(Term::Quine(applied))
+            }
+            (Rule::QuineUnwrap, Term::Quine(inner)) => inner,
+            // Muse rules render their argument into a name, so they go through
+            // `Expr` to stay byte-for-byte identical with the machine backend
+            (Rule::MuseEvolve | Rule::MuseApply, _) => {
+                let expr = self.arena.to_expr_in(id, &mut self.scope.clone());
+                let (_, contracted) = crate::contract_muse(&expr)?;
+                let mut binders = self.scope.iter().map(String::as_str).collect();
+                self.arena.intern_under(&contracted, &mut binders)
+            }
+            _ => unreachable!("redex_rule and contract disagree on {:?}", rule),
+        };
+        Some((after, Redex::at_root(rule, duplicates)))
+    }
+
+    /// 🎯 Contract the redex at `path` below `id`, rebuilding the nodes above it
+    pub(crate) fn contract_at(&mut self, id: TermId, path: &[PathStep]) -> Option<(TermId, Redex)> {
+        let outer = self.scope.len();
+        let mut spine = Vec::with_capacity(path.len());
+        let mut current = id;
+        for &step in path {
+            let child = match (step, self.arena.term(current)) {
+                (PathStep::Left, Term::App(left, _)) => *left,
+                (PathStep::Right, Term::App(_, right)) => *right,
+                (PathStep::Body, Term::Lambda(hint, body)) => {
+                    self.scope.push(hint.clone());
+                    *body
+                }
+                (PathStep::Inner, Term::Quine(inner)) => *inner,
+                _ => break,
+            };
+            spine.push((current, step));
+            current = child;
+        }
+        let contracted = if spine.len() == path.len() { self.contract(current) } else { None };
+        self.scope.truncate(outer);
+
+        let (mut rebuilt, mut redex) = contracted?;
+        for (parent, step) in spine.into_iter().rev() {
+            rebuilt = match (step, self.arena.term(parent).clone()) {
+                (PathStep::Left, Term::App(_, right)) => self.arena.app(rebuilt, right),
+                (PathStep::Right, Term::App(left, _)) => self.arena.app(left, rebuilt),
+                (PathStep::Body, Term::Lambda(hint, _)) => self.arena.intern(Term::Lambda(hint, rebuilt)),
+                (PathStep::Inner, Term::Quine(_)) => self.arena.intern(Term::Quine(rebuilt)),
+                _ => unreachable!("the spine was walked along `path`"),
+            };
+        }
+        redex.path = Path::from(path);
+        Some((rebuilt, redex))
+    }
+
+    /// 🎯 Every redex in `id` and the path to it, leftmost-outermost first
+    pub(crate) fn redexes(&self, id: TermId) -> Vec<(Vec<PathStep>, Rule)> {
+        let mut found = Vec::new();
+        let mut pending = vec![(id, Vec::new())];
+        while let Some((id, path)) = pending.pop() {
+            if let Some(rule) = self.redex_rule(id) {
+                found.push((path.clone(), rule));
+            }
+            let children = match *self.arena.term(id) {
+                Term::App(left, right) => vec![(PathStep::Left, left), (PathStep::Right, right)],
+                Term::Lambda(_, body) => vec![(PathStep::Body, body)],
+                Term::Quine(inner) => vec![(PathStep::Inner, inner)],
+                _ => Vec::new(),
+            };
+            for (step, child) in children.into_iter().rev() {
+                let mut path = path.clone();
+                path.push(step);
+                pending.push((child, path));
+            }
+        }
+        found
+    }
+
+    /// 📏 Size, depth and redex count of `id`
+    pub(crate) fn measure(&mut self, id: TermId) -> TermMetrics {
+        TermMetrics { size: self.arena.size(id), depth: self.arena.depth(id), redexes: self.count_redexes(id) }
+    }
+
+    /// Redexes anywhere in `id`, remembered so each shared node is counted once
+    fn count_redexes(&mut self, id: TermId) -> usize {
+        let mut pending = vec![(id, false)];
+        while let Some((id, children_done)) = pending.pop() {
+            if self.redex_counts.contains_key(&id) {
+                continue;
+            }
+            let children = match *self.arena.term(id) {
+                Term::Lambda(_, body) | Term::Quine(body) => vec![body],
+                Term::App(left, right) => vec![left, right],
+                _ => Vec::new(),
+            };
+            if !children_done {
+                pending.push((id, true));
+                pending.extend(children.into_iter().map(|child| (child, false)));
+                continue;
+            }
+            let here = usize::from(self.redex_rule(id).is_some());
+            let count = children
+                .into_iter()
+                .map(|child| self.redex_counts[&child])
+                .fold(here, usize::saturating_add);
+            self.redex_counts.insert(id, count);
+        }
+        self.redex_counts[&id]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BudgetKind, EvalBudget, EvalError, Termination};
+
+    fn church(n: usize) -> Expr {
+        let mut body = Expr::var("x");
+        for _ in 0..n {
+            body = Expr::app(Expr::var("f"), body);
+        }
+        Expr::lambda("f", Expr::lambda("x", body))
+    }
+
+    #[test]
+    fn test_hash_consing_shares_equal_subterms() {
+        let mut arena = TermArena::new();
+        let twice = Expr::app(Expr::solfunmeme(), Expr::solfunmeme());
+        let id = arena.from_expr(&twice);
+
+        assert_eq!(arena.from_expr(&Expr::solfunmeme()), match arena.term(id) {
+            Term::App(left, _) => *left,
+            other => panic!("expected an application, got {:?}", other),
+        });
+        assert_eq!(arena.size(id), twice.size());
+        assert_eq!(arena.depth(id), twice.depth());
+        assert_eq!(arena.to_expr(id), twice);
+        assert!(arena.len() < twice.size());
+    }
+
+    #[test]
+    fn test_alpha_classes_ignore_binder_names() {
+        let mut arena = TermArena::new();
+        let a = arena.from_expr(&Expr::lambda("a", Expr::app(Expr::var("a"), Expr::var("free"))));
+        let b = arena.from_expr(&Expr::lambda("b", Expr::app(Expr::var("b"), Expr::var("free"))));
+        let c = arena.from_expr(&Expr::lambda("c", Expr::app(Expr::var("free"), Expr::var("c"))));
+
+        assert_ne!(a, b);
+        assert_eq!(arena.alpha_class(a), arena.alpha_class(b));
+        assert_ne!(arena.alpha_class(a), arena.alpha_class(c));
+        assert_eq!(arena.to_expr(b), Expr::lambda("b", Expr::app(Expr::var("b"), Expr::var("free"))));
+    }
+
+    #[test]
+    fn test_read_back_primes_binders_that_would_capture() {
+        let mut engine = LambdaEngine::new();
+        // (λx.λy.x) y: the free `y` ends up under the binder hinted `y`
+        let konst = Expr::lambda("x", Expr::lambda("y", Expr::var("x")));
+        let trace = engine.normalize(Expr::app(konst, Expr::var("y"))).unwrap();
+        assert_eq!(trace.final_form, Expr::lambda("y'", Expr::var("y")));
+
+        // Shadowing that the body does not see keeps its name
+        let shadow = Expr::lambda("x", Expr::lambda("x", Expr::var("x")));
+        let mut arena = TermArena::new();
+        let id = arena.from_expr(&shadow);
+        assert_eq!(arena.to_expr(id), shadow);
+    }
+
+    #[test]
+    fn test_long_trace_stays_small() {
+        // Y f unfolds forever; every step adds one `f` around a shared core
+        let steps = 100_000;
+        let mut engine = LambdaEngine::new().with_max_steps(steps);
+        let trace = engine.normalize(Expr::app(Expr::y_combinator(), Expr::sym("f"))).unwrap();
+
+        assert_eq!(trace.step_count, steps);
+        assert!(matches!(trace.termination, Termination::Growth { .. }));
+        assert!(trace.arena().len() < 4 * steps, "{} terms for {} steps", trace.arena().len(), steps);
+    }
+
+    #[test]
+    fn test_church_arithmetic_on_shared_terms() {
+        // 2 3 = 3² = 9, built from heavily shared copies of 3
+        let mut engine = LambdaEngine::new().with_max_steps(10_000);
+        let trace = engine.normalize(Expr::app(church(2), church(3))).unwrap();
+        assert!(trace.final_form.alpha_eq(&church(9)));
+    }
+
+    #[test]
+    fn test_budget_uses_cached_sizes() {
+        let triple = Expr::lambda("x", Expr::app(Expr::app(Expr::var("x"), Expr::var("x")), Expr::var("x")));
+        let mut engine = LambdaEngine::new().with_budget(EvalBudget::unlimited().with_max_term_size(60));
+        let err = engine.normalize(Expr::app(triple.clone(), triple)).unwrap_err();
+        assert!(matches!(err, EvalError::BudgetExceeded { kind: BudgetKind::TermSize, .. }));
+    }
+}
@@ -0,0 +1,183 @@
+//! # ⏳ Evaluation Budgets: Keeping Runaway Poems in Check
+//!
+//! A single S step duplicates its argument, so a few dozen steps can grow a
+//! term exponentially. An [`EvalBudget`] bounds steps, term size, nesting depth
+//! and wall-clock time; when any limit is crossed reduction stops with
+//! [`EvalError::BudgetExceeded`], carrying the last term that was reached.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Expr;
+
+/// ⏳ Hard limits on a single normalization; `None` means unbounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct EvalBudget {
+    /// Reduction steps before giving up with an error. Unlike
+    /// `LambdaEngine::max_steps`, running out is a failure, not a result
+    pub max_steps: Option<usize>,
+    /// Largest term, in nodes, that may appear during reduction
+    pub max_term_size: Option<usize>,
+    /// Deepest nesting that may appear during reduction
+    pub max_depth: Option<usize>,
+    /// Wall-clock time allowed for the whole normalization
+    pub deadline: Option<Duration>,
+}
+
+impl EvalBudget {
+    /// A budget with no limits at all
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    pub fn with_max_term_size(mut self, max_term_size: usize) -> Self {
+        self.max_term_size = Some(max_term_size);
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// The first limit `expr` breaks, if any
+    pub(crate) fn check_term(&self, expr: &Expr) -> Option<BudgetKind> {
+        if self.max_term_size.is_some_and(|limit| expr.size() > limit) {
+            Some(BudgetKind::TermSize)
+        } else {
+            self.check_shape(0, expr.depth())
+        }
+    }
+
+    /// The first limit a term of this size and depth breaks, if any
+    pub(crate) fn check_shape(&self, size: usize, depth: usize) -> Option<BudgetKind> {
+        if self.max_term_size.is_some_and(|limit| size > limit) {
+            Some(BudgetKind::TermSize)
+        } else if self.max_depth.is_some_and(|limit| depth > limit) {
+            Some(BudgetKind::Depth)
+        } else {
+            None
+        }
+    }
+}
+
+/// 📏 Which part of an `EvalBudget` ran out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetKind {
+    Steps,
+    TermSize,
+    Depth,
+    Deadline,
+}
+
+impl fmt::Display for BudgetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BudgetKind::Steps => "step",
+            BudgetKind::TermSize => "term size",
+            BudgetKind::Depth => "depth",
+            BudgetKind::Deadline => "deadline",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// ❌ Why a normalization could not finish
+#[derive(Debug, Error)]
+pub enum EvalError {
+    /// A budget limit was crossed; `partial` is the last term reached
+    #[error("{kind} budget exceeded after {steps} steps")]
+    BudgetExceeded {
+        kind: BudgetKind,
+        partial: Expr,
+        steps: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LambdaEngine;
+
+    /// `S I I (S I I)` doubles its work without ever shrinking
+    fn sii_sii() -> Expr {
+        let sii = Expr::app(Expr::app(Expr::S, Expr::I), Expr::I);
+        Expr::app(sii.clone(), sii)
+    }
+
+    #[test]
+    fn test_term_size_budget_returns_partial_term() {
+        // (λx.x x x) applied to itself grows by a copy each step
+        let triple = Expr::lambda("x", Expr::app(Expr::app(Expr::var("x"), Expr::var("x")), Expr::var("x")));
+        let mut engine = LambdaEngine::new().with_budget(EvalBudget::unlimited().with_max_term_size(60));
+
+        match engine.try_normalize(Expr::app(triple.clone(), triple)) {
+            Err(EvalError::BudgetExceeded { kind, partial, steps }) => {
+                assert_eq!(kind, BudgetKind::TermSize);
+                assert!(partial.size() > 60);
+                assert!(steps > 0);
+            }
+            other => panic!("expected a term size overrun, got {:?}", other.map(|t| t.final_form)),
+        }
+    }
+
+    #[test]
+    fn test_step_budget_is_an_error() {
+        let mut engine = LambdaEngine::new()
+            .with_budget(EvalBudget::unlimited().with_max_steps(3));
+        let err = engine.try_normalize(Expr::app(Expr::y_combinator(), Expr::sym("f"))).unwrap_err();
+        assert!(matches!(err, EvalError::BudgetExceeded { kind: BudgetKind::Steps, steps: 3, .. }));
+
+        // `normalize` is the same entry point and holds to the same budget
+        let err = engine.normalize(Expr::app(Expr::y_combinator(), Expr::sym("f"))).unwrap_err();
+        assert!(matches!(err, EvalError::BudgetExceeded { kind: BudgetKind::Steps, steps: 3, .. }));
+    }
+
+    #[test]
+    fn test_depth_budget_checks_initial_term() {
+        let mut engine = LambdaEngine::new().with_budget(EvalBudget::unlimited().with_max_depth(2));
+        let err = engine.try_normalize(sii_sii()).unwrap_err();
+        assert!(matches!(err, EvalError::BudgetExceeded { kind: BudgetKind::Depth, steps: 0, .. }));
+    }
+
+    #[test]
+    fn test_depth_budget_refuses_very_deep_terms_without_walking_them() {
+        let deep = (0..20_000).fold(Expr::sym("🐱"), |body, _| Expr::app(Expr::sym("🐱"), body));
+        let mut engine = LambdaEngine::new().with_budget(EvalBudget::unlimited().with_max_depth(5_000));
+        let err = engine.try_normalize(deep).unwrap_err();
+        assert!(matches!(err, EvalError::BudgetExceeded { kind: BudgetKind::Depth, steps: 0, .. }));
+    }
+
+    #[test]
+    fn test_zero_deadline_expires() {
+        let mut engine = LambdaEngine::new().with_budget(EvalBudget::unlimited().with_deadline(Duration::ZERO));
+        let err = engine.try_normalize(sii_sii()).unwrap_err();
+        assert!(matches!(err, EvalError::BudgetExceeded { kind: BudgetKind::Deadline, .. }));
+    }
+
+    #[test]
+    fn test_budget_does_not_affect_terminating_terms() {
+        let budget = EvalBudget::unlimited()
+            .with_max_steps(10)
+            .with_max_term_size(10)
+            .with_max_depth(5)
+            .with_deadline(Duration::from_secs(5));
+        let mut engine = LambdaEngine::new().with_budget(budget);
+        let trace = engine.try_normalize(Expr::app(Expr::I, Expr::sym("🌀"))).unwrap();
+        assert_eq!(trace.final_form, Expr::sym("🌀"));
+    }
+}
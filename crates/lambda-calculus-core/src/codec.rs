@@ -0,0 +1,322 @@
+//! # 🔐 Canonical Emoji Codec: Every Term, Exactly
+//!
+//! `EmojiSemantics::expr_to_emoji` is a poetic rendering: it drops binder
+//! names, variables, quine bodies and DNA bytes. This codec is its lossless
+//! counterpart. A term is written in prefix notation, one emoji per
+//! constructor, so no brackets are needed:
+//!
+//! | Term            | Code                                   |
+//! |-----------------|----------------------------------------|
+//! | `S` / `K` / `I` | 🌀 / 🔮 / 💫                           |
+//! | `f x`           | 🔗 *f* *x*                             |
+//! | `λx.body`       | 🐑 *name* *body*                       |
+//! | bound variable  | 👆 *n* — the n-th enclosing binder      |
+//! | free variable   | 🆓 *name*                              |
+//! | `Sym`           | 🔣 *name*                              |
+//! | `Muse`          | 🎭 *name* *n*                          |
+//! | `Quine`         | 🪞 *body*                              |
+//! | `DNA`           | 🧬 *n* *byte*…                         |
+//!
+//! Numbers are keycap digits closed by 🔚 (`1️⃣2️⃣🔚` is 12) and a *name* is
+//! its length in characters followed by the characters themselves. Bound
+//! variables are de Bruijn indices pointing at the nearest binder with their
+//! name, so the binder names kept on 🐑 make `decode(encode(e)) == e` exact.
+//! Decoding accepts only canonical codes, which makes the codec a bijection
+//! between terms and valid codes.
+
+use thiserror::Error;
+
+use crate::Expr;
+
+const APP: &str = "🔗";
+const LAMBDA: &str = "🐑";
+const BOUND: &str = "👆";
+const FREE: &str = "🆓";
+const SYM: &str = "🔣";
+const S: &str = "🌀";
+const K: &str = "🔮";
+const I: &str = "💫";
+const MUSE: &str = "🎭";
+const QUINE: &str = "🪞";
+const DNA: &str = "🧬";
+const END: &str = "🔚";
+/// Combining enclosing keycap, written after a digit and U+FE0F
+const KEYCAP: &str = "\u{FE0F}\u{20E3}";
+
+/// ❌ Why a string is not the canonical code of any term
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CodecError {
+    #[error("code ends in the middle of a term")]
+    UnexpectedEnd,
+    #[error("unexpected input at byte {offset}")]
+    UnknownToken { offset: usize },
+    #[error("malformed number at byte {offset}")]
+    BadNumber { offset: usize },
+    #[error("non-canonical code at byte {offset}: {reason}")]
+    NonCanonical { offset: usize, reason: String },
+    #[error("trailing input after the term at byte {offset}")]
+    TrailingInput { offset: usize },
+}
+
+impl Expr {
+    /// 🔐 Encode as a canonical emoji code that `from_emoji_code` reverses exactly
+    pub fn to_emoji_code(&self) -> String {
+        let mut out = String::new();
+        encode(self, &mut Vec::new(), &mut out);
+        out
+    }
+
+    /// 🔓 Decode a canonical emoji code produced by `to_emoji_code`
+    pub fn from_emoji_code(code: &str) -> Result<Expr, CodecError> {
+        let mut decoder = Decoder { input: code, pos: 0, binders: Vec::new() };
+        let expr = decoder.term()?;
+        if decoder.pos < code.len() {
+            return Err(CodecError::TrailingInput { offset: decoder.pos });
+        }
+        Ok(expr)
+    }
+}
+
+fn encode<'a>(expr: &'a Expr, binders: &mut Vec<&'a str>, out: &mut String) {
+    match expr {
+        Expr::Var(name) => match binders.iter().rev().position(|binder| binder == name) {
+            Some(index) => {
+                out.push_str(BOUND);
+                encode_number(index, out);
+            }
+            None => {
+                out.push_str(FREE);
+                encode_name(name, out);
+            }
+        },
+        Expr::Lambda(param, body) => {
+            out.push_str(LAMBDA);
+            encode_name(param, out);
+            binders.push(param);
+            encode(body, binders, out);
+            binders.pop();
+        }
+        Expr::App(left, right) => {
+            out.push_str(APP);
+            encode(left, binders, out);
+            encode(right, binders, out);
+        }
+        Expr::Sym(symbol) => {
+            out.push_str(SYM);
+            encode_name(symbol, out);
+        }
+        Expr::S => out.push_str(S),
+        Expr::K => out.push_str(K),
+        Expr::I => out.push_str(I),
+        Expr::Muse(name, resonance) => {
+            out.push_str(MUSE);
+            encode_name(name, out);
+            encode_number(*resonance as usize, out);
+        }
+        Expr::Quine(inner) => {
+            out.push_str(QUINE);
+            encode(inner, binders, out);
+        }
+        Expr::DNA(data) => {
+            out.push_str(DNA);
+            encode_number(data.len(), out);
+            for byte in data {
+                encode_number(*byte as usize, out);
+            }
+        }
+    }
+}
+
+fn encode_number(n: usize, out: &mut String) {
+    for digit in n.to_string().chars() {
+        out.push(digit);
+        out.push_str(KEYCAP);
+    }
+    out.push_str(END);
+}
+
+fn encode_name(name: &str, out: &mut String) {
+    encode_number(name.chars().count(), out);
+    out.push_str(name);
+}
+
+struct Decoder<'a> {
+    input: &'a str,
+    pos: usize,
+    /// Binder names of the lambdas enclosing the current position, innermost last
+    binders: Vec<String>,
+}
+
+impl Decoder<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, CodecError> {
+        let start = self.pos;
+        if self.rest().is_empty() {
+            return Err(CodecError::UnexpectedEnd);
+        }
+
+        if self.eat(S) {
+            Ok(Expr::S)
+        } else if self.eat(K) {
+            Ok(Expr::K)
+        } else if self.eat(I) {
+            Ok(Expr::I)
+        } else if self.eat(APP) {
+            let left = self.term()?;
+            let right = self.term()?;
+            Ok(Expr::app(left, right))
+        } else if self.eat(LAMBDA) {
+            let param = self.name()?;
+            self.binders.push(param.clone());
+            let body = self.term();
+            self.binders.pop();
+            Ok(Expr::lambda(&param, body?))
+        } else if self.eat(BOUND) {
+            let index = self.number()?;
+            let depth = self.binders.len();
+            if index >= depth {
+                return Err(self.non_canonical(start, format!("index {} with only {} enclosing binders", index, depth)));
+            }
+            let name = self.binders[depth - 1 - index].clone();
+            if self.binders[depth - index..].contains(&name) {
+                return Err(self.non_canonical(start, format!("`{}` is shadowed by a nearer binder", name)));
+            }
+            Ok(Expr::var(&name))
+        } else if self.eat(FREE) {
+            let name = self.name()?;
+            if self.binders.contains(&name) {
+                return Err(self.non_canonical(start, format!("free `{}` is bound here", name)));
+            }
+            Ok(Expr::var(&name))
+        } else if self.eat(SYM) {
+            Ok(Expr::Sym(self.name()?))
+        } else if self.eat(MUSE) {
+            let name = self.name()?;
+            let offset = self.pos;
+            let resonance = u32::try_from(self.number()?).map_err(|_| CodecError::BadNumber { offset })?;
+            Ok(Expr::Muse(name, resonance))
+        } else if self.eat(QUINE) {
+            Ok(Expr::quine(self.term()?))
+        } else if self.eat(DNA) {
+            let len = self.number()?;
+            let mut data = Vec::new();
+            for _ in 0..len {
+                let offset = self.pos;
+                data.push(u8::try_from(self.number()?).map_err(|_| CodecError::BadNumber { offset })?);
+            }
+            Ok(Expr::DNA(data))
+        } else {
+            Err(CodecError::UnknownToken { offset: start })
+        }
+    }
+
+    /// Keycap digits closed by 🔚, without leading zeros
+    fn number(&mut self) -> Result<usize, CodecError> {
+        let start = self.pos;
+        let mut digits = String::new();
+        while !self.eat(END) {
+            let mut chars = self.rest().chars();
+            match chars.next() {
+                None => return Err(CodecError::UnexpectedEnd),
+                Some(digit) if digit.is_ascii_digit() && chars.as_str().starts_with(KEYCAP) => {
+                    digits.push(digit);
+                    self.pos += 1 + KEYCAP.len();
+                }
+                Some(_) => return Err(CodecError::BadNumber { offset: start }),
+            }
+        }
+        if digits.is_empty() || (digits.len() > 1 && digits.starts_with('0')) {
+            return Err(CodecError::BadNumber { offset: start });
+        }
+        digits.parse().map_err(|_| CodecError::BadNumber { offset: start })
+    }
+
+    /// A character count followed by that many characters
+    fn name(&mut self) -> Result<String, CodecError> {
+        let len = self.number()?;
+        let rest = self.rest();
+        let end = match rest.char_indices().nth(len) {
+            Some((end, _)) => end,
+            None if rest.chars().count() == len => rest.len(),
+            None => return Err(CodecError::UnexpectedEnd),
+        };
+        let name = rest[..end].to_string();
+        self.pos += end;
+        Ok(name)
+    }
+
+    fn non_canonical(&self, offset: usize, reason: String) -> CodecError {
+        CodecError::NonCanonical { offset, reason }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_expr(rng: &mut StdRng, depth: usize) -> Expr {
+        const NAMES: [&str; 4] = ["x", "y", "🌀", "x'"];
+        let leaf = depth == 0 || rng.gen_bool(0.3);
+        match rng.gen_range(0..if leaf { 7 } else { 10 }) {
+            0 => Expr::var(NAMES[rng.gen_range(0..NAMES.len())]),
+            1 => Expr::sym(NAMES[rng.gen_range(0..NAMES.len())]),
+            2 => Expr::S,
+            3 => Expr::K,
+            4 => Expr::I,
+            5 => Expr::Muse("Sol".to_string(), rng.gen_range(0..2000)),
+            6 => Expr::DNA((0..rng.gen_range(0..4)).map(|_| rng.gen()).collect()),
+            7 => Expr::lambda(NAMES[rng.gen_range(0..NAMES.len())], random_expr(rng, depth - 1)),
+            8 => Expr::quine(random_expr(rng, depth - 1)),
+            _ => Expr::app(random_expr(rng, depth - 1), random_expr(rng, depth - 1)),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_keeps_everything_expr_to_emoji_drops() {
+        // λx.λx.x y, with a shadowed binder and a free variable
+        let shadowed = Expr::lambda("x", Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("y"))));
+        let code = shadowed.to_emoji_code();
+        assert_eq!(code, "🐑1️⃣🔚x🐑1️⃣🔚x🔗👆0️⃣🔚🆓1️⃣🔚y");
+        assert_eq!(Expr::from_emoji_code(&code), Ok(shadowed));
+
+        let rich = Expr::quine(Expr::app(Expr::muse("Muse", 0.99), Expr::DNA(vec![0, 7, 255])));
+        assert_eq!(Expr::from_emoji_code(&rich.to_emoji_code()), Ok(rich));
+    }
+
+    #[test]
+    fn test_random_terms_round_trip() {
+        let mut rng = StdRng::seed_from_u64(14);
+        for _ in 0..500 {
+            let expr = random_expr(&mut rng, 6);
+            let code = expr.to_emoji_code();
+            assert_eq!(Expr::from_emoji_code(&code).as_ref(), Ok(&expr), "code {}", code);
+        }
+    }
+
+    #[test]
+    fn test_non_canonical_codes_are_rejected() {
+        // λx.λx.(index 1) names the outer x, which the inner x shadows
+        assert!(matches!(Expr::from_emoji_code("🐑1️⃣🔚x🐑1️⃣🔚x👆1️⃣🔚"), Err(CodecError::NonCanonical { .. })));
+        // a "free" x under a binder for x
+        assert!(matches!(Expr::from_emoji_code("🐑1️⃣🔚x🆓1️⃣🔚x"), Err(CodecError::NonCanonical { .. })));
+        assert!(matches!(Expr::from_emoji_code("👆0️⃣🔚"), Err(CodecError::NonCanonical { .. })));
+        assert!(matches!(Expr::from_emoji_code("🧬1️⃣🔚0️⃣7️⃣🔚"), Err(CodecError::BadNumber { .. })));
+        assert_eq!(Expr::from_emoji_code("🔗🌀"), Err(CodecError::UnexpectedEnd));
+        assert_eq!(Expr::from_emoji_code("🌀🔮"), Err(CodecError::TrailingInput { offset: "🌀".len() }));
+        assert_eq!(Expr::from_emoji_code("🦄"), Err(CodecError::UnknownToken { offset: 0 }));
+    }
+}
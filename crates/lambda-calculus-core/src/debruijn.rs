@@ -0,0 +1,182 @@
+//! # 🔢 De Bruijn Terms: Expressions Without Names
+//!
+//! A nameless view of [`Expr`] where every bound variable is replaced by the
+//! number of binders between it and its lambda. Two expressions that differ
+//! only in the names of their bound variables map to the same [`DeBruijn`]
+//! term, which makes alpha-equivalence a plain structural comparison.
+
+use std::collections::HashSet;
+
+use crate::Expr;
+
+/// 🔢 An expression with bound variables replaced by de Bruijn indices
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DeBruijn {
+    /// Bound variable, counted outward from the innermost enclosing lambda (0-based)
+    Bound(usize),
+    /// Free variable, kept by name
+    Free(String),
+    /// Lambda abstraction - the binder name is gone
+    Lambda(Box<DeBruijn>),
+    /// Function application
+    App(Box<DeBruijn>, Box<DeBruijn>),
+    Sym(String),
+    S,
+    K,
+    I,
+    Muse(String, u32),
+    Quine(Box<DeBruijn>),
+    DNA(Vec<u8>),
+}
+
+impl Expr {
+    /// 🔢 Convert to the nameless de Bruijn representation
+    pub fn to_debruijn(&self) -> DeBruijn {
+        fn convert(expr: &Expr, binders: &mut Vec<String>) -> DeBruijn {
+            match expr {
+                Expr::Var(name) => match binders.iter().rev().position(|b| b == name) {
+                    Some(index) => DeBruijn::Bound(index),
+                    None => DeBruijn::Free(name.clone()),
+                },
+                Expr::Lambda(param, body) => {
+                    binders.push(param.clone());
+                    let body = convert(body, binders);
+                    binders.pop();
+                    DeBruijn::Lambda(Box::new(body))
+                }
+                Expr::App(left, right) => DeBruijn::App(
+                    Box::new(convert(left, binders)),
+                    Box::new(convert(right, binders)),
+                ),
+                Expr::Sym(symbol) => DeBruijn::Sym(symbol.clone()),
+                Expr::S => DeBruijn::S,
+                Expr::K => DeBruijn::K,
+                Expr::I => DeBruijn::I,
+                Expr::Muse(name, resonance) => DeBruijn::Muse(name.clone(), *resonance),
+                Expr::Quine(inner) => DeBruijn::Quine(Box::new(convert(inner, binders))),
+                Expr::DNA(data) => DeBruijn::DNA(data.clone()),
+            }
+        }
+
+        convert(self, &mut Vec::new())
+    }
+
+    /// 🪞 Alpha-equivalence: equal up to renaming of bound variables
+    pub fn alpha_eq(&self, other: &Expr) -> bool {
+        self.to_debruijn() == other.to_debruijn()
+    }
+}
+
+impl DeBruijn {
+    /// Names of the free variables in this term
+    pub fn free_vars(&self) -> HashSet<String> {
+        fn collect(term: &DeBruijn, out: &mut HashSet<String>) {
+            match term {
+                DeBruijn::Free(name) => {
+                    out.insert(name.clone());
+                }
+                DeBruijn::Lambda(body) | DeBruijn::Quine(body) => collect(body, out),
+                DeBruijn::App(left, right) => {
+                    collect(left, out);
+                    collect(right, out);
+                }
+                _ => {}
+            }
+        }
+
+        let mut vars = HashSet::new();
+        collect(self, &mut vars);
+        vars
+    }
+
+    /// 🏷️ Read back into a named `Expr`, inventing binder names that
+    /// cannot capture any free variable
+    pub fn to_expr(&self) -> Expr {
+        fn convert(term: &DeBruijn, binders: &mut Vec<String>, free: &HashSet<String>) -> Expr {
+            match term {
+                DeBruijn::Bound(index) => match binders.iter().rev().nth(*index) {
+                    Some(name) => Expr::var(name),
+                    // Dangling index: keep it visible rather than silently rebinding it
+                    None => Expr::var(&format!("#{}", index)),
+                },
+                DeBruijn::Free(name) => Expr::var(name),
+                DeBruijn::Lambda(body) => {
+                    let name = binder_name(binders.len(), free);
+                    binders.push(name.clone());
+                    let body = convert(body, binders, free);
+                    binders.pop();
+                    Expr::lambda(&name, body)
+                }
+                DeBruijn::App(left, right) => Expr::app(
+                    convert(left, binders, free),
+                    convert(right, binders, free),
+                ),
+                DeBruijn::Sym(symbol) => Expr::sym(symbol),
+                DeBruijn::S => Expr::S,
+                DeBruijn::K => Expr::K,
+                DeBruijn::I => Expr::I,
+                DeBruijn::Muse(name, resonance) => Expr::Muse(name.clone(), *resonance),
+                DeBruijn::Quine(inner) => Expr::quine(convert(inner, binders, free)),
+                DeBruijn::DNA(data) => Expr::dna(data.clone()),
+            }
+        }
+
+        convert(self, &mut Vec::new(), &self.free_vars())
+    }
+}
+
+/// Binder name for nesting depth `depth`: `x`, `y`, `z`, `x1`, `y1`, ...
+/// primed until it no longer clashes with a free variable of the term
+fn binder_name(depth: usize, free: &HashSet<String>) -> String {
+    const BASES: [&str; 3] = ["x", "y", "z"];
+    let base = BASES[depth % BASES.len()];
+    let round = depth / BASES.len();
+    let mut name = if round == 0 { base.to_string() } else { format!("{}{}", base, round) };
+    while free.contains(&name) {
+        name.push('\'');
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alpha_equivalence() {
+        let id_x = Expr::lambda("x", Expr::var("x"));
+        let id_y = Expr::lambda("y", Expr::var("y"));
+        assert!(id_x.alpha_eq(&id_y));
+
+        let k_first = Expr::lambda("x", Expr::lambda("y", Expr::var("x")));
+        let k_second = Expr::lambda("x", Expr::lambda("y", Expr::var("y")));
+        assert!(!k_first.alpha_eq(&k_second));
+
+        // Free variables are compared by name
+        assert!(!Expr::var("a").alpha_eq(&Expr::var("b")));
+    }
+
+    #[test]
+    fn test_shadowing_refers_to_innermost_binder() {
+        let shadowed = Expr::lambda("x", Expr::lambda("x", Expr::var("x")));
+        assert_eq!(
+            shadowed.to_debruijn(),
+            DeBruijn::Lambda(Box::new(DeBruijn::Lambda(Box::new(DeBruijn::Bound(0)))))
+        );
+    }
+
+    #[test]
+    fn test_read_back_avoids_free_names() {
+        // λa.(x a) has `x` free, so the binder must not be read back as `x`
+        let expr = Expr::lambda("a", Expr::app(Expr::var("x"), Expr::var("a")));
+        let named = expr.to_debruijn().to_expr();
+        assert!(named.alpha_eq(&expr));
+        assert_eq!(named.free_vars(), expr.free_vars());
+    }
+
+    #[test]
+    fn test_round_trip_y_combinator() {
+        let y = Expr::y_combinator();
+        assert!(y.to_debruijn().to_expr().alpha_eq(&y));
+    }
+}
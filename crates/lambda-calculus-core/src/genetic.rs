@@ -0,0 +1,546 @@
+//! # 🧬 Genetic Programming: Breeding Better Poems
+//!
+//! `LambdaEngine::evolve` mutates a single term. This module evolves whole
+//! populations of them:
+//!
+//! - **Fitness** — a pluggable [`Fitness`] scores each term, higher is
+//!   fitter; [`Resonance`], [`TermSize`], [`StepsToNormalForm`] and
+//!   [`Quineness`] are built in and [`Weighted`] blends several
+//! - **Selection** — parents are drawn by tournament or roulette wheel
+//! - **Crossover** — two parents swap randomly chosen subterms
+//! - **Mutation** — offspring go through `evolve`'s point mutation
+//! - **Elitism** — the fittest terms survive into the next generation as-is
+//!
+//! Every random choice comes from the engine's seeded stream, so a seeded
+//! engine breeds the same populations every run.
+
+use std::collections::HashSet;
+
+use log::{debug, info};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{EvalError, Expr, LambdaEngine, PathStep};
+
+/// ❌ Why a genetic run could not proceed
+#[derive(Debug, Error)]
+pub enum GeneticError {
+    #[error("cannot breed from an empty population")]
+    EmptyPopulation,
+    #[error(transparent)]
+    Eval(#[from] EvalError),
+}
+
+/// ⚖️ Scores a term for selection; higher is fitter
+pub trait Fitness {
+    /// Short name for logs and reports
+    fn name(&self) -> &str;
+
+    /// Score `expr`, evaluating it under `engine`'s environment and strategy if needed
+    fn score(&self, expr: &Expr, engine: &LambdaEngine) -> f64;
+}
+
+/// 🎭 Mean resonance of the term's muses, in `0.0..=1.0`; terms without muses score 0
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Resonance;
+
+/// 📏 Prefers small terms: `1 / size`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TermSize;
+
+/// 🏁 Prefers terms that normalize quickly: `1 / (1 + steps)`, or 0 when no
+/// normal form is reached within `max_steps`
+#[derive(Debug, Clone, Copy)]
+pub struct StepsToNormalForm {
+    pub max_steps: usize,
+}
+
+/// 🪞 Prefers quines: 1 for an exact quine, `1 / period` for a cyclic one, 0 otherwise
+#[derive(Debug, Clone, Copy)]
+pub struct Quineness {
+    /// Reduction steps allowed when verifying
+    pub max_steps: usize,
+}
+
+/// ➕ A weighted sum of other fitness functions
+#[derive(Default)]
+pub struct Weighted {
+    parts: Vec<(f64, Box<dyn Fitness>)>,
+}
+
+impl Default for StepsToNormalForm {
+    fn default() -> Self {
+        Self { max_steps: 100 }
+    }
+}
+
+impl Default for Quineness {
+    fn default() -> Self {
+        Self { max_steps: 16 }
+    }
+}
+
+impl Weighted {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `fitness` to the sum with the given weight
+    pub fn with(mut self, weight: f64, fitness: impl Fitness + 'static) -> Self {
+        self.parts.push((weight, Box::new(fitness)));
+        self
+    }
+}
+
+/// An engine that gives up once `expr` has grown well beyond its size, so
+/// exploding offspring are cheap to score
+fn scoring_engine(engine: &LambdaEngine, expr: &Expr, max_steps: usize) -> LambdaEngine {
+    engine.clone().with_max_steps(max_steps).with_budget(engine.budget.with_max_term_size(8 * expr.size() + 64))
+}
+
+fn muse_resonances(expr: &Expr, resonances: &mut Vec<u32>) {
+    match expr {
+        Expr::Muse(_, resonance) => resonances.push(*resonance),
+        Expr::App(left, right) => {
+            muse_resonances(left, resonances);
+            muse_resonances(right, resonances);
+        }
+        Expr::Lambda(_, body) | Expr::Quine(body) => muse_resonances(body, resonances),
+        _ => {}
+    }
+}
+
+impl Fitness for Resonance {
+    fn name(&self) -> &str {
+        "resonance"
+    }
+
+    fn score(&self, expr: &Expr, _engine: &LambdaEngine) -> f64 {
+        let mut resonances = Vec::new();
+        muse_resonances(expr, &mut resonances);
+        if resonances.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = resonances.iter().map(|&resonance| resonance as f64 / 1000.0).sum();
+        (total / resonances.len() as f64).min(1.0)
+    }
+}
+
+impl Fitness for TermSize {
+    fn name(&self) -> &str {
+        "term size"
+    }
+
+    fn score(&self, expr: &Expr, _engine: &LambdaEngine) -> f64 {
+        1.0 / expr.size() as f64
+    }
+}
+
+impl Fitness for StepsToNormalForm {
+    fn name(&self) -> &str {
+        "steps to normal form"
+    }
+
+    fn score(&self, expr: &Expr, engine: &LambdaEngine) -> f64 {
+        match scoring_engine(engine, expr, self.max_steps).try_normalize(expr.clone()) {
+            Ok(trace) if trace.is_normal_form => 1.0 / (1.0 + trace.step_count as f64),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Fitness for Quineness {
+    fn name(&self) -> &str {
+        "quineness"
+    }
+
+    fn score(&self, expr: &Expr, engine: &LambdaEngine) -> f64 {
+        scoring_engine(engine, expr, self.max_steps)
+            .verify_quine(expr)
+            .ok()
+            .and_then(|report| report.verdict.period())
+            .map_or(0.0, |period| 1.0 / period as f64)
+    }
+}
+
+impl Fitness for Weighted {
+    fn name(&self) -> &str {
+        "weighted"
+    }
+
+    fn score(&self, expr: &Expr, engine: &LambdaEngine) -> f64 {
+        self.parts.iter().map(|(weight, fitness)| weight * fitness.score(expr, engine)).sum()
+    }
+}
+
+/// 🎯 How parents are drawn from a scored population
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Selection {
+    /// The fittest of `size` uniformly drawn candidates
+    Tournament { size: usize },
+    /// Probability proportional to fitness, shifted so the least fit has weight 0
+    Roulette,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Selection::Tournament { size: 3 }
+    }
+}
+
+/// ⚙️ Parameters of a genetic run
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GeneticConfig {
+    pub population_size: usize,
+    pub generations: usize,
+    /// Chance that an offspring comes from crossover rather than a copy of one parent
+    pub crossover_rate: f64,
+    /// Mutation rate passed to `evolve` for every offspring
+    pub mutation_rate: f64,
+    /// Fittest terms copied unchanged into the next generation
+    pub elitism: usize,
+    pub selection: Selection,
+    /// Offspring larger than this are replaced by their first parent
+    pub max_term_size: usize,
+}
+
+impl Default for GeneticConfig {
+    fn default() -> Self {
+        Self {
+            population_size: 20,
+            generations: 10,
+            crossover_rate: 0.7,
+            mutation_rate: 0.3,
+            elitism: 2,
+            selection: Selection::default(),
+            max_term_size: 200,
+        }
+    }
+}
+
+impl GeneticConfig {
+    pub fn with_population_size(mut self, population_size: usize) -> Self {
+        self.population_size = population_size;
+        self
+    }
+
+    pub fn with_generations(mut self, generations: usize) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    pub fn with_crossover_rate(mut self, crossover_rate: f64) -> Self {
+        self.crossover_rate = crossover_rate;
+        self
+    }
+
+    pub fn with_mutation_rate(mut self, mutation_rate: f64) -> Self {
+        self.mutation_rate = mutation_rate;
+        self
+    }
+
+    pub fn with_elitism(mut self, elitism: usize) -> Self {
+        self.elitism = elitism;
+        self
+    }
+
+    pub fn with_selection(mut self, selection: Selection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    pub fn with_max_term_size(mut self, max_term_size: usize) -> Self {
+        self.max_term_size = max_term_size;
+        self
+    }
+}
+
+/// 🏷️ A term and its fitness
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scored {
+    pub expr: Expr,
+    pub fitness: f64,
+}
+
+/// 🐣 A bred term and the indices of the parents it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Offspring {
+    pub expr: Expr,
+    pub parent: usize,
+    /// The crossover partner, if the offspring came from crossover
+    pub partner: Option<usize>,
+}
+
+/// 📊 Summary of one generation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+    pub worst_fitness: f64,
+    /// The fittest term, rendered
+    pub best_expression: String,
+    pub mean_size: f64,
+    /// Terms that are distinct up to alpha-equivalence
+    pub distinct_terms: usize,
+}
+
+impl GenerationStats {
+    /// Summarize a scored population; `None` when it is empty
+    pub fn from_population(generation: usize, population: &[Scored]) -> Option<Self> {
+        let best = population.iter().max_by(|a, b| a.fitness.total_cmp(&b.fitness))?;
+        let count = population.len() as f64;
+        let distinct: HashSet<_> = population.iter().map(|scored| scored.expr.to_debruijn()).collect();
+        Some(Self {
+            generation,
+            best_fitness: best.fitness,
+            mean_fitness: population.iter().map(|scored| scored.fitness).sum::<f64>() / count,
+            worst_fitness: population.iter().map(|scored| scored.fitness).fold(f64::INFINITY, f64::min),
+            best_expression: best.expr.to_string(),
+            mean_size: population.iter().map(|scored| scored.expr.size()).sum::<usize>() as f64 / count,
+            distinct_terms: distinct.len(),
+        })
+    }
+}
+
+/// 🏆 Outcome of `evolve_population`
+#[derive(Debug, Clone)]
+pub struct GeneticRun {
+    /// The fittest term seen in any generation
+    pub best: Scored,
+    /// One entry per generation, starting with the initial population
+    pub history: Vec<GenerationStats>,
+    /// The last generation, fittest first
+    pub population: Vec<Scored>,
+}
+
+impl Expr {
+    /// 🧭 Paths to every subterm, the root (empty path) first
+    pub fn subterm_paths(&self) -> Vec<Vec<PathStep>> {
+        fn walk(expr: &Expr, path: &mut Vec<PathStep>, paths: &mut Vec<Vec<PathStep>>) {
+            paths.push(path.clone());
+            let children: Vec<(PathStep, &Expr)> = match expr {
+                Expr::App(left, right) => vec![(PathStep::Left, left), (PathStep::Right, right)],
+                Expr::Lambda(_, body) => vec![(PathStep::Body, body)],
+                Expr::Quine(inner) => vec![(PathStep::Inner, inner)],
+                _ => Vec::new(),
+            };
+            for (step, child) in children {
+                path.push(step);
+                walk(child, path, paths);
+                path.pop();
+            }
+        }
+        let mut paths = Vec::new();
+        walk(self, &mut Vec::new(), &mut paths);
+        paths
+    }
+
+    /// 🧭 A copy with the subterm at `path` replaced, or `None` if `path` leads nowhere
+    pub fn replace_subterm(&self, path: &[PathStep], replacement: Expr) -> Option<Expr> {
+        let Some((step, rest)) = path.split_first() else {
+            return Some(replacement);
+        };
+        match (step, self) {
+            (PathStep::Left, Expr::App(left, right)) => Some(Expr::app(left.replace_subterm(rest, replacement)?, (**right).clone())),
+            (PathStep::Right, Expr::App(left, right)) => Some(Expr::app((**left).clone(), right.replace_subterm(rest, replacement)?)),
+            (PathStep::Body, Expr::Lambda(param, body)) => Some(Expr::lambda(param, body.replace_subterm(rest, replacement)?)),
+            (PathStep::Inner, Expr::Quine(inner)) => Some(Expr::quine(inner.replace_subterm(rest, replacement)?)),
+            _ => None,
+        }
+    }
+}
+
+impl LambdaEngine {
+    /// ✂️ Subtree crossover: swap a random subterm of `a` with a random subterm of `b`
+    ///
+    /// Swapped subterms keep their variable names, so a variable can become
+    /// free or be captured in its new position - as in any tree-based GP.
+    pub fn crossover(&mut self, a: &Expr, b: &Expr) -> (Expr, Expr) {
+        let paths_a = a.subterm_paths();
+        let paths_b = b.subterm_paths();
+        let path_a = &paths_a[self.rng.gen_range(0..paths_a.len())];
+        let path_b = &paths_b[self.rng.gen_range(0..paths_b.len())];
+
+        let (Some(sub_a), Some(sub_b)) = (a.subterm_at(path_a), b.subterm_at(path_b)) else {
+            unreachable!("subterm_paths only returns paths into the term")
+        };
+        let child_a = a.replace_subterm(path_a, sub_b.clone()).expect("path from subterm_paths");
+        let child_b = b.replace_subterm(path_b, sub_a.clone()).expect("path from subterm_paths");
+        (child_a, child_b)
+    }
+
+    /// 🎯 Draw the index of one parent from a non-empty scored population
+    pub fn select(&mut self, population: &[Scored], selection: Selection) -> usize {
+        match selection {
+            Selection::Tournament { size } => (0..size.max(1))
+                .map(|_| self.rng.gen_range(0..population.len()))
+                .reduce(|best, candidate| if population[candidate].fitness > population[best].fitness { candidate } else { best })
+                .expect("at least one contestant"),
+            Selection::Roulette => {
+                let floor = population.iter().map(|scored| scored.fitness).fold(f64::INFINITY, f64::min);
+                let total: f64 = population.iter().map(|scored| scored.fitness - floor).sum();
+                if total <= 0.0 || !total.is_finite() {
+                    return self.rng.gen_range(0..population.len());
+                }
+                let mut spin = self.rng.gen_range(0.0..total);
+                for (index, scored) in population.iter().enumerate() {
+                    spin -= scored.fitness - floor;
+                    if spin < 0.0 {
+                        return index;
+                    }
+                }
+                population.len() - 1
+            }
+        }
+    }
+
+    /// 🐣 Breed `count` offspring from a scored population by selection,
+    /// crossover and mutation
+    pub fn breed(&mut self, population: &[Scored], count: usize, config: &GeneticConfig) -> Result<Vec<Offspring>, GeneticError> {
+        if population.is_empty() {
+            return Err(GeneticError::EmptyPopulation);
+        }
+        let mut offspring = Vec::with_capacity(count);
+        while offspring.len() < count {
+            let parent = self.select(population, config.selection);
+            let mut children = if self.rng.gen::<f64>() < config.crossover_rate {
+                let partner = self.select(population, config.selection);
+                let (a, b) = self.crossover(&population[parent].expr, &population[partner].expr);
+                vec![(a, parent, Some(partner)), (b, partner, Some(parent))]
+            } else {
+                vec![(population[parent].expr.clone(), parent, None)]
+            };
+            children.truncate(count - offspring.len());
+
+            for (child, parent, partner) in children {
+                let mutated = self.evolve(&child, config.mutation_rate)?;
+                let expr = if mutated.size() > config.max_term_size { population[parent].expr.clone() } else { mutated };
+                offspring.push(Offspring { expr, parent, partner });
+            }
+        }
+        Ok(offspring)
+    }
+
+    /// 🧬 Evolve a population for `config.generations` generations under `fitness`
+    ///
+    /// The initial terms are topped up to `population_size` by breeding.
+    /// Each generation keeps its `elitism` fittest terms and fills the rest
+    /// with offspring; fitness is evaluated under this engine's environment,
+    /// strategy and budget.
+    pub fn evolve_population(&mut self, initial: &[Expr], fitness: &dyn Fitness, config: &GeneticConfig) -> Result<GeneticRun, GeneticError> {
+        if initial.is_empty() {
+            return Err(GeneticError::EmptyPopulation);
+        }
+        info!("🧬 Evolving {} terms for {} generations by {}", initial.len(), config.generations, fitness.name());
+
+        let evaluator = self.clone();
+        let score = |exprs: Vec<Expr>| -> Vec<Scored> {
+            exprs.into_iter().map(|expr| Scored { fitness: fitness.score(&expr, &evaluator), expr }).collect()
+        };
+
+        let mut population = score(initial.to_vec());
+        let size = config.population_size.max(1);
+        if population.len() < size {
+            let topped_up = self.breed(&population, size - population.len(), config)?;
+            population.extend(score(topped_up.into_iter().map(|offspring| offspring.expr).collect()));
+        }
+
+        let mut history = Vec::with_capacity(config.generations + 1);
+        let mut best: Option<Scored> = None;
+        for generation in 0..=config.generations {
+            population.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+            if best.as_ref().is_none_or(|best| population[0].fitness > best.fitness) {
+                best = Some(population[0].clone());
+            }
+            let stats = GenerationStats::from_population(generation, &population).expect("population is never empty");
+            debug!("🧬 Generation {}: best {:.4}, mean {:.4}", generation, stats.best_fitness, stats.mean_fitness);
+            history.push(stats);
+
+            if generation == config.generations {
+                break;
+            }
+            let elites = config.elitism.min(size);
+            let offspring = self.breed(&population, size - elites, config)?;
+            population.truncate(elites);
+            population.extend(score(offspring.into_iter().map(|offspring| offspring.expr).collect()));
+        }
+
+        let best = best.expect("at least one generation");
+        info!("🏆 Fittest term scored {:.4}: {}", best.fitness, best.expr);
+        Ok(GeneticRun { best, history, population })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn muse(resonance: u32) -> Expr {
+        Expr::Muse("Calliope".to_string(), resonance)
+    }
+
+    #[test]
+    fn test_crossover_swaps_subterms() {
+        let a = Expr::app(Expr::S, Expr::K);
+        let b = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::I));
+        assert_eq!(a.subterm_paths().len(), 3);
+        assert_eq!(a.replace_subterm(&[PathStep::Right], Expr::I), Some(Expr::app(Expr::S, Expr::I)));
+        assert_eq!(a.replace_subterm(&[PathStep::Body], Expr::I), None);
+
+        let mut engine = LambdaEngine::new().with_seed(18);
+        for _ in 0..20 {
+            let (child_a, child_b) = engine.crossover(&a, &b);
+            // Material is exchanged, never created or lost
+            assert_eq!(child_a.size() + child_b.size(), a.size() + b.size());
+        }
+    }
+
+    #[test]
+    fn test_selection_prefers_fitter_terms() {
+        let population: Vec<Scored> = (0..4).map(|n| Scored { expr: muse(n * 250), fitness: n as f64 }).collect();
+        let mut engine = LambdaEngine::new().with_seed(18);
+
+        let tournament = Selection::Tournament { size: population.len() * 4 };
+        let picks: Vec<usize> = (0..50).map(|_| engine.select(&population, tournament)).collect();
+        assert!(picks.iter().filter(|&&pick| pick == 3).count() > 40);
+
+        // Roulette never draws the least fit, whose shifted weight is zero
+        assert!((0..100).all(|_| engine.select(&population, Selection::Roulette) != 0));
+    }
+
+    #[test]
+    fn test_evolution_climbs_and_keeps_elites() {
+        let initial = vec![Expr::app(muse(100), muse(200)), Expr::app(muse(300), Expr::I)];
+        let config = GeneticConfig::default().with_population_size(12).with_generations(8).with_mutation_rate(0.9);
+
+        let run = LambdaEngine::new().with_seed(18).evolve_population(&initial, &Resonance, &config).unwrap();
+        assert_eq!(run.history.len(), 9);
+        assert_eq!(run.population.len(), 12);
+        // Elitism: the best score never drops between generations
+        assert!(run.history.windows(2).all(|pair| pair[1].best_fitness >= pair[0].best_fitness));
+        assert!(run.best.fitness > run.history[0].best_fitness);
+
+        let again = LambdaEngine::new().with_seed(18).evolve_population(&initial, &Resonance, &config).unwrap();
+        assert_eq!(again.history, run.history);
+    }
+
+    #[test]
+    fn test_builtin_fitness_functions() {
+        let engine = LambdaEngine::new();
+        let omega_half = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("x")));
+        let omega = Expr::app(omega_half.clone(), omega_half);
+
+        assert_eq!(TermSize.score(&Expr::I, &engine), 1.0);
+        assert_eq!(StepsToNormalForm::default().score(&Expr::app(Expr::I, Expr::K), &engine), 0.5);
+        assert_eq!(StepsToNormalForm::default().score(&omega, &engine), 0.0);
+        assert_eq!(Quineness::default().score(&omega, &engine), 1.0);
+        assert_eq!(Quineness::default().score(&Expr::I, &engine), 0.0);
+        assert!((Resonance.score(&Expr::app(muse(400), muse(800)), &engine) - 0.6).abs() < 1e-9);
+
+        let blended = Weighted::new().with(2.0, TermSize).with(1.0, Quineness::default());
+        assert_eq!(blended.score(&Expr::I, &engine), 2.0);
+    }
+}
@@ -10,12 +10,68 @@
 //! - **Self-Replication**: Expressions that output themselves (quines)
 //! - **Emoji Encoding**: Lambda expressions encoded as emoji sequences
 //! - **Poetry Generation**: Lambda calculus that creates beautiful verse
+//! - **Round-Trip Parsing**: `Display` output parses back into an `Expr`
+//! - **Capture-Avoiding Substitution**: bound variables are renamed, never captured
+//! - **Structured Traces**: every step names its rule and the path to its redex
+//! - **Cycle Detection**: looping reductions stop early and report their period
+//! - **Evaluation Budgets**: step, size, depth and time limits on every reduction
+//! - **Shared Terms**: a hash-consed arena keeps very long reductions in bounded memory
+//! - **Krivine Machine**: an environment-machine backend with read-back to `Expr`
+//! - **Church Prelude**: numerals, booleans, pairs and lists with decoders
+//! - **Canonical Emoji Codec**: a lossless emoji encoding that round-trips every term
+//! - **Quine Verification**: fixed-point checks that classify exact and cyclic quines
+//! - **Quine Synthesis**: enumerative search for the smallest quine carrying a seed
+//! - **Genetic Programming**: crossover, selection, elitism and pluggable fitness
+//! - **Type Inference**: Hindley–Milner principal types; typed closed terms normalize
+//! - **Bracket Abstraction**: compile any lambda term to pure S, K and I and back
+//! - **Reduction Metrics**: term size, depth, redexes and duplication at every step
+//! - **Step Debugger**: step, rewind, breakpoints and hand-picked redexes
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::time::Instant;
 
-use anyhow::Result;
 use log::{debug, info, warn};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+pub mod arena;
+pub mod budget;
+pub mod codec;
+pub mod debruijn;
+pub mod genetic;
+pub mod machine;
+pub mod metrics;
+pub mod parser;
+pub mod prelude;
+pub mod quine;
+pub mod ski;
+pub mod stepper;
+pub mod strategy;
+pub mod synthesis;
+pub mod trace;
+pub mod types;
+
+pub use arena::{Term, TermArena, TermId};
+pub use budget::{BudgetKind, EvalBudget, EvalError};
+pub use codec::CodecError;
+pub use debruijn::DeBruijn;
+pub use genetic::{Fitness, GenerationStats, GeneticConfig, GeneticError, GeneticRun, Scored, Selection};
+pub use machine::Backend;
+pub use metrics::{ReductionMetrics, TermMetrics};
+pub use parser::ParseError;
+pub use prelude::ChurchValue;
+pub use quine::{QuineReport, QuineVerdict};
+pub use stepper::{Breakpoint, RedexChoice, RunOutcome, Stepper};
+pub use strategy::Strategy;
+pub use synthesis::{QuineSearch, SynthesisBudget};
+pub use trace::{PathStep, ReductionStep, Rule, Termination};
+pub use types::{Type, TypeError};
+
+use arena::Reducer;
+
+/// Results of reduction, which can only fail by crossing a budget limit
+type Result<T, E = EvalError> = std::result::Result<T, E>;
 
 /// 🌀 The fundamental expression type for our lambda calculus poetry engine
 /// 
@@ -57,6 +113,31 @@ pub enum Expr {
     DNA(Vec<u8>),
 }
 
+/// Nested terms are taken apart one node at a time, so dropping a very deep
+/// `Expr` (the final form of a long reduction) cannot overflow the stack
+impl Drop for Expr {
+    fn drop(&mut self) {
+        fn detach(expr: &mut Expr, pending: &mut Vec<Expr>) {
+            let children: [Option<&mut Box<Expr>>; 2] = match expr {
+                Expr::Lambda(_, body) | Expr::Quine(body) => [Some(body), None],
+                Expr::App(left, right) => [Some(left), Some(right)],
+                _ => [None, None],
+            };
+            for child in children.into_iter().flatten() {
+                if matches!(**child, Expr::Lambda(..) | Expr::App(..) | Expr::Quine(..)) {
+                    pending.push(std::mem::replace(&mut **child, Expr::S));
+                }
+            }
+        }
+
+        let mut pending = Vec::new();
+        detach(self, &mut pending);
+        while let Some(mut expr) = pending.pop() {
+            detach(&mut expr, &mut pending);
+        }
+    }
+}
+
 impl Expr {
     /// Create a function application `left right`
     pub fn app(left: Expr, right: Expr) -> Expr {
@@ -101,6 +182,67 @@ impl Expr {
         }
     }
     
+    /// Number of nodes in the expression tree
+    ///
+    /// Walks the tree with an explicit stack, like `depth`, so that budget
+    /// checks on very deep terms cannot overflow the thread's stack.
+    pub fn size(&self) -> usize {
+        let mut size = 0;
+        let mut pending = vec![self];
+        while let Some(expr) = pending.pop() {
+            size += 1;
+            expr.push_children(&mut pending);
+        }
+        size
+    }
+
+    /// Nesting depth of the expression tree (a leaf has depth 1)
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut pending = vec![(self, 1)];
+        let mut children = Vec::new();
+        while let Some((expr, level)) = pending.pop() {
+            depth = depth.max(level);
+            expr.push_children(&mut children);
+            pending.extend(children.drain(..).map(|child| (child, level + 1)));
+        }
+        depth
+    }
+
+    fn push_children<'a>(&'a self, out: &mut Vec<&'a Expr>) {
+        match self {
+            Expr::Lambda(_, body) | Expr::Quine(body) => out.push(body),
+            Expr::App(left, right) => out.extend([left.as_ref(), right.as_ref()]),
+            _ => {}
+        }
+    }
+    
+    /// Names of the variables that occur free (unbound) in this expression
+    pub fn free_vars(&self) -> HashSet<String> {
+        let mut vars = HashSet::new();
+        self.collect_free_vars(&mut Vec::new(), &mut vars);
+        vars
+    }
+    
+    fn collect_free_vars<'a>(&'a self, bound: &mut Vec<&'a str>, out: &mut HashSet<String>) {
+        match self {
+            Expr::Var(name) if !bound.contains(&name.as_str()) => {
+                out.insert(name.clone());
+            }
+            Expr::Lambda(param, body) => {
+                bound.push(param);
+                body.collect_free_vars(bound, out);
+                bound.pop();
+            }
+            Expr::App(left, right) => {
+                left.collect_free_vars(bound, out);
+                right.collect_free_vars(bound, out);
+            }
+            Expr::Quine(inner) => inner.collect_free_vars(bound, out),
+            _ => {}
+        }
+    }
+    
     /// 🌟 The legendary Y-combinator for recursion: `Y = λf.(λx.f (x x)) (λx.f (x x))`
     pub fn y_combinator() -> Expr {
         let inner = Expr::lambda("x", 
@@ -135,27 +277,66 @@ impl Expr {
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Expr::Var(name) => write!(f, "{}", name),
-            Expr::Lambda(var, body) => write!(f, "λ{}.{}", var, body),
+            Expr::Var(name) => parser::write_variable(f, name),
+            Expr::Lambda(var, body) => {
+                write!(f, "λ")?;
+                parser::write_variable(f, var)?;
+                write!(f, ".{}", body)
+            }
             Expr::App(left, right) => write!(f, "({} {})", left, right),
-            Expr::Sym(symbol) => write!(f, "{}", symbol),
+            Expr::Sym(symbol) => parser::write_symbol(f, symbol),
             Expr::S => write!(f, "S"),
             Expr::K => write!(f, "K"),
             Expr::I => write!(f, "I"),
-            Expr::Muse(name, resonance) => write!(f, "🎭{}[{:.2}]", name, resonance),
+            Expr::Muse(name, resonance) => parser::write_muse(f, name, *resonance),
             Expr::Quine(expr) => write!(f, "🌀Q({})", expr),
-            Expr::DNA(data) => write!(f, "🧬DNA[{}]", data.len()),
+            Expr::DNA(data) => parser::write_dna(f, data),
         }
     }
 }
 
 /// 🔄 Reduction trace for debugging and visualization
+///
+/// The intermediate terms live in a shared `TermArena`; `steps` and `terms`
+/// render them into owned values only when asked.
 #[derive(Debug, Clone)]
 pub struct ReductionTrace {
-    pub steps: Vec<String>, // Store as strings instead of Expr
     pub step_count: usize,
     pub final_form: Expr,
     pub is_normal_form: bool,
+    /// Why reduction stopped
+    pub termination: Termination,
+    /// Evaluation strategy that produced this trace
+    pub strategy: Strategy,
+    /// How the term grew and shrank along the way
+    pub metrics: ReductionMetrics,
+    arena: TermArena,
+    /// The term before the first step and after every step
+    terms: Vec<TermId>,
+    /// Rule and path of every contraction
+    redexes: Vec<(Rule, trace::Path)>,
+}
+
+impl ReductionTrace {
+    /// One entry per contraction, naming the rule and where the redex was
+    pub fn steps(&self) -> Vec<ReductionStep> {
+        let terms = self.terms();
+        self.redexes
+            .iter()
+            .zip(terms.windows(2))
+            .map(|((rule, path), pair)| ReductionStep::between(*rule, path.to_vec(), &pair[0], &pair[1]))
+            .collect()
+    }
+    
+    /// The term before the first step and after every step
+    pub fn terms(&self) -> Vec<Expr> {
+        self.terms.iter().map(|&id| self.arena.to_expr(id)).collect()
+    }
+    
+    /// The arena holding every term of the reduction
+    pub fn arena(&self) -> &TermArena {
+        &self.arena
+    }
 }
 
 /// 🧠 The Lambda Calculus Engine - where poetry becomes computation
@@ -165,8 +346,14 @@ pub struct LambdaEngine {
     pub max_steps: usize,
     /// Variable substitution environment
     pub environment: HashMap<String, Expr>,
-    /// Reduction trace for debugging
-    pub trace: Vec<Expr>,
+    /// Order in which redexes are chosen
+    pub strategy: Strategy,
+    /// Hard resource limits; crossing one aborts normalization with an error
+    pub budget: EvalBudget,
+    /// How reduction is carried out
+    pub backend: Backend,
+    /// Source of randomness for `evolve`
+    rng: StdRng,
 }
 
 impl Default for LambdaEngine {
@@ -178,212 +365,174 @@ impl Default for LambdaEngine {
 impl LambdaEngine {
     /// Create a new lambda calculus engine
     pub fn new() -> Self {
+        Self { rng: StdRng::from_entropy(), ..Self::seeded(0) }
+    }
+    
+    /// An engine with the defaults of `new` whose random stream starts from
+    /// `seed`, for internal reductions that must not depend on entropy
+    pub(crate) fn seeded(seed: u64) -> Self {
         Self {
             max_steps: 1000,
             environment: HashMap::new(),
-            trace: Vec::new(),
+            strategy: Strategy::default(),
+            budget: EvalBudget::unlimited(),
+            backend: Backend::default(),
+            rng: StdRng::seed_from_u64(seed),
         }
     }
     
+    /// Make `evolve` reproducible: the same seed yields the same mutations
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.reseed(seed);
+        self
+    }
+    
+    /// Restart the random stream used by `evolve` from `seed`
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+    
     /// Set maximum reduction steps
     pub fn with_max_steps(mut self, max_steps: usize) -> Self {
         self.max_steps = max_steps;
         self
     }
     
+    /// Set the evaluation strategy used by `normalize`
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+    
+    /// Set the resource budget enforced by `normalize`
+    pub fn with_budget(mut self, budget: EvalBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+    
     /// Add a variable binding to the environment
     pub fn bind(&mut self, name: &str, expr: Expr) {
         self.environment.insert(name.to_string(), expr);
     }
     
-    /// 🌟 Normalize a lambda expression with full beta reduction
+    /// 🌟 Normalize a lambda expression under the engine's strategy
+    ///
+    /// With the default `Strategy::NormalOrder` this is full beta reduction;
+    /// the weak and head strategies stop at their respective normal forms.
+    ///
+    /// Reduction runs on a hash-consed `TermArena`, so each step only builds
+    /// the nodes it changes. Every intermediate term is remembered by its
+    /// alpha class, so a reduction that revisits a term stops at once with
+    /// `Termination::Cycle` instead of spinning until `max_steps`.
+    ///
+    /// The same reduction as `try_normalize`, which holds the only
+    /// reduction loop; this name is kept for existing callers.
     pub fn normalize(&mut self, expr: Expr) -> Result<ReductionTrace> {
-        info!("🚀 Starting normalization of: {}", expr);
-        self.trace.clear();
-        self.trace.push(expr.clone());
-        
-        let mut current = expr;
-        let mut step_count = 0;
-        
-        while step_count < self.max_steps {
-            match self.beta_reduce(&current)? {
-                Some(reduced) => {
-                    debug!("Step {}: {} → {}", step_count + 1, current, reduced);
-                    current = reduced;
-                    self.trace.push(current.clone());
-                    step_count += 1;
-                }
-                None => {
-                    info!("✅ Reached normal form after {} steps", step_count);
-                    break;
-                }
-            }
+        self.try_normalize(expr)
+    }
+    
+    /// ⏳ Normalize within the engine's `EvalBudget`
+    ///
+    /// Hitting `max_steps` is still an ordinary outcome reported through
+    /// `Termination`; crossing any limit of the budget is an
+    /// `EvalError::BudgetExceeded` carrying the last term reached.
+    ///
+    /// With `Backend::Machine` and normal order the Krivine machine does the
+    /// work instead; its trace has a step count but no per-step entries.
+    pub fn try_normalize(&mut self, expr: Expr) -> Result<ReductionTrace> {
+        // Before anything walks the term recursively, so an oversized input
+        // is refused rather than overflowing the stack
+        let budget = self.budget;
+        if let Some(kind) = budget.check_term(&expr) {
+            return Err(EvalError::BudgetExceeded { kind, partial: expr, steps: 0 });
         }
         
-        if step_count >= self.max_steps {
-            warn!("⚠️ Maximum steps reached, may not be in normal form");
+        if self.backend == Backend::Machine && self.strategy == Strategy::NormalOrder {
+            return self.normalize_machine(&expr);
         }
         
-        // Convert trace to strings
-        let string_steps: Vec<String> = self.trace.iter().map(|expr| format!("{}", expr)).collect();
+        info!("🚀 Starting {} normalization of: {}", self.strategy, expr);
+        let started = Instant::now();
+        let mut reducer = Reducer::new(self);
+        let mut current = reducer.arena.from_expr(&expr);
         
-        Ok(ReductionTrace {
-            steps: string_steps,
-            step_count,
-            final_form: current.clone(),
-            is_normal_form: step_count < self.max_steps,
-        })
-    }
-    
-    /// 🔄 Perform one step of beta reduction
-    fn beta_reduce(&self, expr: &Expr) -> Result<Option<Expr>> {
-        match expr {
-            // Variable lookup in environment
-            Expr::Var(name) => {
-                if let Some(value) = self.environment.get(name) {
-                    Ok(Some(value.clone()))
-                } else {
-                    Ok(None)
-                }
+        let mut seen = HashMap::new();
+        seen.insert(reducer.arena.alpha_class(current), 0);
+        
+        let mut metrics = ReductionMetrics { per_step: true, ..Default::default() };
+        metrics.record(reducer.measure(current));
+        
+        let mut terms = vec![current];
+        let mut redexes = Vec::new();
+        let mut termination = None;
+        
+        while redexes.len() < self.max_steps {
+            let step_count = redexes.len();
+            if budget.deadline.is_some_and(|deadline| started.elapsed() >= deadline) {
+                let partial = reducer.arena.to_expr(current);
+                return Err(EvalError::BudgetExceeded { kind: BudgetKind::Deadline, partial, steps: step_count });
             }
             
-            // Lambda abstraction - no reduction needed
-            Expr::Lambda(_, _) => Ok(None),
-            
-            // Function application - the heart of computation
-            Expr::App(left, right) => {
-                match left.as_ref() {
-                    // Beta reduction: (λx.body) arg → body[x := arg]
-                    Expr::Lambda(param, body) => {
-                        let substituted = self.substitute(body, param, right)?;
-                        Ok(Some(substituted))
-                    }
-                    
-                    // S-combinator: S f g x → f x (g x)
-                    Expr::S => {
-                        // S f → partial application
-                        Ok(Some(Expr::app(
-                            Expr::app(Expr::S, (**right).clone()),
-                            Expr::I // Placeholder for next argument
-                        )))
-                    }
-                    
-                    // K-combinator: K x y → x
-                    Expr::K => {
-                        Ok(Some(Expr::app(Expr::K, (**right).clone())))
-                    }
-                    
-                    // I-combinator: I x → x
-                    Expr::I => {
-                        Ok(Some((**right).clone()))
-                    }
-                    
-                    // Nested application - reduce left side first
-                    Expr::App(inner_left, inner_right) => {
-                        match inner_left.as_ref() {
-                            // S f g x → f x (g x)
-                            Expr::App(s_expr, f) if matches!(s_expr.as_ref(), Expr::S) => {
-                                let g = inner_right;
-                                let x = right;
-                                Ok(Some(Expr::app(
-                                    Expr::app((**f).clone(), (**x).clone()),
-                                    Expr::app((**g).clone(), (**x).clone())
-                                )))
-                            }
-                            
-                            // K x y → x
-                            Expr::K => {
-                                Ok(Some((**inner_right).clone()))
-                            }
-                            
-                            _ => {
-                                // Try to reduce the left side
-                                if let Some(reduced_left) = self.beta_reduce(left)? {
-                                    Ok(Some(Expr::app(reduced_left, (**right).clone())))
-                                } else if let Some(reduced_right) = self.beta_reduce(right)? {
-                                    Ok(Some(Expr::app((**left).clone(), reduced_right)))
-                                } else {
-                                    Ok(None)
-                                }
-                            }
-                        }
-                    }
-                    
-                    // Muse application - poetic computation
-                    Expr::Muse(name, resonance) => {
-                        let new_resonance = ((*resonance as f64 / 1000.0) * 1.01 * 1000.0) as u32;
-                        Ok(Some(Expr::muse(&format!("{}+{}", name, right), new_resonance as f64 / 1000.0)))
-                    }
+            match reducer.step(current, self.strategy) {
+                Some(_) if budget.max_steps == Some(step_count) => {
+                    let partial = reducer.arena.to_expr(current);
+                    return Err(EvalError::BudgetExceeded { kind: BudgetKind::Steps, partial, steps: step_count });
+                }
+                Some((reduced, redex)) => {
+                    debug!("Step {}: {} at {:?}", step_count + 1, redex.rule, redex.path);
+                    metrics.record_redex(&redex);
+                    metrics.record(reducer.measure(reduced));
+                    redexes.push((redex.rule, redex.path));
+                    current = reduced;
+                    terms.push(current);
+                    let step_count = step_count + 1;
                     
-                    // Quine application - self-replication
-                    Expr::Quine(inner) => {
-                        Ok(Some(Expr::quine(Expr::app((**inner).clone(), (**right).clone()))))
+                    let (size, depth) = (reducer.arena.size(current), reducer.arena.depth(current));
+                    if let Some(kind) = budget.check_shape(size, depth) {
+                        warn!("⏳ {} budget exceeded at step {}", kind, step_count);
+                        let partial = reducer.arena.to_expr(current);
+                        return Err(EvalError::BudgetExceeded { kind, partial, steps: step_count });
                     }
                     
-                    _ => {
-                        // Try to reduce subexpressions
-                        if let Some(reduced_left) = self.beta_reduce(left)? {
-                            Ok(Some(Expr::app(reduced_left, (**right).clone())))
-                        } else if let Some(reduced_right) = self.beta_reduce(right)? {
-                            Ok(Some(Expr::app((**left).clone(), reduced_right)))
-                        } else {
-                            Ok(None)
-                        }
+                    let class = reducer.arena.alpha_class(current);
+                    if let Some(&first_seen_step) = seen.get(&class) {
+                        let period = step_count - first_seen_step;
+                        info!("🔁 Cycle of period {} detected at step {}", period, step_count);
+                        termination = Some(Termination::Cycle { period, first_seen_step });
+                        break;
                     }
+                    seen.insert(class, step_count);
                 }
-            }
-            
-            // Combinators and symbols - no reduction
-            Expr::S | Expr::K | Expr::I | Expr::Sym(_) | Expr::DNA(_) => Ok(None),
-            
-            // Muse - can evolve
-            Expr::Muse(name, resonance) => {
-                let resonance_f64 = *resonance as f64 / 1000.0;
-                if resonance_f64 < 1.0 {
-                    let new_resonance = ((resonance_f64 + 0.001) * 1000.0) as u32;
-                    Ok(Some(Expr::Muse(name.clone(), new_resonance)))
-                } else {
-                    Ok(None)
+                None => {
+                    info!("✅ Reached normal form after {} steps", step_count);
+                    termination = Some(Termination::NormalForm);
+                    break;
                 }
             }
-            
-            // Quine - self-replication
-            Expr::Quine(inner) => {
-                Ok(Some((**inner).clone()))
-            }
         }
-    }
-    
-    /// 🔄 Substitute variable with expression in body
-    fn substitute(&self, body: &Expr, var: &str, replacement: &Expr) -> Result<Expr> {
-        match body {
-            Expr::Var(name) => {
-                if name == var {
-                    Ok(replacement.clone())
-                } else {
-                    Ok(body.clone())
-                }
-            }
-            
-            Expr::Lambda(param, lambda_body) => {
-                if param == var {
-                    // Variable is shadowed, no substitution
-                    Ok(body.clone())
-                } else {
-                    let substituted_body = self.substitute(lambda_body, var, replacement)?;
-                    Ok(Expr::lambda(param, substituted_body))
-                }
-            }
-            
-            Expr::App(left, right) => {
-                let substituted_left = self.substitute(left, var, replacement)?;
-                let substituted_right = self.substitute(right, var, replacement)?;
-                Ok(Expr::app(substituted_left, substituted_right))
+        
+        let termination = match termination {
+            Some(termination) => termination,
+            // The last permitted step may itself have produced the normal form
+            None if reducer.step(current, self.strategy).is_none() => Termination::NormalForm,
+            None => {
+                warn!("⚠️ Maximum steps reached, may not be in normal form");
+                let sizes: Vec<usize> = terms.iter().map(|&id| reducer.arena.size(id)).collect();
+                Termination::from_sizes(&sizes)
             }
-            
-            // Other expressions remain unchanged
-            _ => Ok(body.clone()),
-        }
+        };
+        
+        Ok(ReductionTrace {
+            step_count: redexes.len(),
+            final_form: reducer.arena.to_expr(current),
+            is_normal_form: termination == Termination::NormalForm,
+            termination,
+            strategy: self.strategy,
+            metrics,
+            arena: reducer.arena,
+            terms,
+            redexes,
+        })
     }
     
     /// 🎭 Generate a poetic expression with given resonance
@@ -413,27 +562,24 @@ impl LambdaEngine {
     }
     
     /// 🧬 Evolve an expression through genetic operations
-    pub fn evolve(&self, expr: &Expr, mutation_rate: f64) -> Result<Expr> {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        
-        if rng.gen::<f64>() < mutation_rate {
+    pub fn evolve(&mut self, expr: &Expr, mutation_rate: f64) -> Result<Expr> {
+        if self.rng.gen::<f64>() < mutation_rate {
             match expr {
                 Expr::Muse(name, resonance) => {
                     let resonance_f64 = *resonance as f64 / 1000.0;
-                    let new_resonance_f64 = (resonance_f64 + rng.gen_range(-0.1..0.1)).clamp(0.0, 1.0);
+                    let new_resonance_f64 = (resonance_f64 + self.rng.gen_range(-0.1..0.1)).clamp(0.0, 1.0);
                     let new_resonance = (new_resonance_f64 * 1000.0) as u32;
                     Ok(Expr::Muse(name.clone(), new_resonance))
                 }
                 
                 Expr::Sym(symbol) => {
                     let mutations = ["🌀", "🎭", "🧬", "🌟", "💫", "🔮"];
-                    let new_symbol = mutations[rng.gen_range(0..mutations.len())];
+                    let new_symbol = mutations[self.rng.gen_range(0..mutations.len())];
                     Ok(Expr::sym(new_symbol))
                 }
                 
                 Expr::App(left, right) => {
-                    if rng.gen::<bool>() {
+                    if self.rng.gen::<bool>() {
                         let evolved_left = self.evolve(left, mutation_rate / 2.0)?;
                         Ok(Expr::app(evolved_left, (**right).clone()))
                     } else {
@@ -487,6 +633,26 @@ impl Default for ExprBuilder {
     }
 }
 
+/// 🎭 The muse rules, on an `Expr` whose root is a muse or a muse applied to
+/// an argument: the argument is rendered into the muse's new name
+pub(crate) fn contract_muse(expr: &Expr) -> Option<(Rule, Expr)> {
+    match expr {
+        // Muse application - poetic computation
+        Expr::App(left, right) => match left.as_ref() {
+            Expr::Muse(name, resonance) => {
+                let new_resonance = ((*resonance as f64 / 1000.0) * 1.01 * 1000.0) as u32;
+                Some((Rule::MuseApply, Expr::muse(&format!("{}+{}", name, right), new_resonance as f64 / 1000.0)))
+            }
+            _ => None,
+        },
+        // Muse - gains a thousandth of resonance until it reaches 1.0
+        Expr::Muse(name, resonance) if *resonance < 1000 => {
+            Some((Rule::MuseEvolve, Expr::Muse(name.clone(), resonance + 1)))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -507,23 +673,152 @@ mod tests {
     fn test_s_combinator() {
         let mut engine = LambdaEngine::new();
         
-        // Test S I I x → x (should behave like identity)
+        // S I I x → I x (I x) → x (I x) → x x (self-application, the ω combinator)
         let s_i_i = Expr::app(Expr::app(Expr::S, Expr::I), Expr::I);
         let application = Expr::app(s_i_i, Expr::sym("test"));
         
         let trace = engine.normalize(application).unwrap();
-        // S I I x → I x (I x) → x (I x) → x x
-        // This is more complex than simple identity
-        assert!(trace.step_count > 0);
+        assert_eq!(trace.final_form, Expr::app(Expr::sym("test"), Expr::sym("test")));
+        assert_eq!(trace.step_count, 3);
+        assert!(trace.is_normal_form);
+    }
+    
+    /// Apply `head` to each argument in turn: `apply(f, [a, b])` = `f a b`
+    fn apply(head: Expr, args: &[Expr]) -> Expr {
+        args.iter().cloned().fold(head, Expr::app)
+    }
+    
+    fn normal_form(expr: Expr) -> Expr {
+        let trace = LambdaEngine::new().normalize(expr).unwrap();
+        assert!(trace.is_normal_form);
+        trace.final_form
+    }
+    
+    fn skk() -> Expr {
+        apply(Expr::S, &[Expr::K, Expr::K])
+    }
+    
+    /// `B = S (K S) K`, composition: `B f g x = f (g x)`
+    fn b_combinator() -> Expr {
+        apply(Expr::S, &[Expr::app(Expr::K, Expr::S), Expr::K])
+    }
+    
+    /// `C = S (B B S) (K K)`, flip: `C f x y = f y x`
+    fn c_combinator() -> Expr {
+        let bbs = apply(b_combinator(), &[b_combinator(), Expr::S]);
+        apply(Expr::S, &[bbs, Expr::app(Expr::K, Expr::K)])
+    }
+    
+    /// `W = S S (K I)`, duplication: `W f x = f x x`
+    fn w_combinator() -> Expr {
+        apply(Expr::S, &[Expr::S, Expr::app(Expr::K, Expr::I)])
+    }
+    
+    #[test]
+    fn test_skk_is_identity() {
+        let x = Expr::sym("x");
+        assert_eq!(normal_form(Expr::app(skk(), x.clone())), x);
+        assert_eq!(normal_form(apply(skk(), &[Expr::S, x.clone()])), Expr::app(Expr::S, x));
+    }
+    
+    #[test]
+    fn test_unsaturated_combinators_are_normal_forms() {
+        let mut engine = LambdaEngine::new();
+        for expr in [
+            Expr::S,
+            Expr::app(Expr::S, Expr::sym("f")),
+            apply(Expr::S, &[Expr::sym("f"), Expr::sym("g")]),
+            Expr::app(Expr::K, Expr::sym("x")),
+            skk(),
+        ] {
+            let trace = engine.normalize(expr.clone()).unwrap();
+            assert_eq!(trace.step_count, 0, "{} should not reduce", expr);
+            assert_eq!(trace.final_form, expr);
+            assert!(trace.is_normal_form);
+        }
+    }
+    
+    #[test]
+    fn test_unsaturated_combinator_reduces_arguments() {
+        // S (I f) → S f: the head waits, its argument still normalizes
+        let expr = Expr::app(Expr::S, Expr::app(Expr::I, Expr::sym("f")));
+        assert_eq!(normal_form(expr), Expr::app(Expr::S, Expr::sym("f")));
+    }
+    
+    #[test]
+    fn test_k_combinator() {
+        let (x, y) = (Expr::sym("x"), Expr::sym("y"));
+        assert_eq!(normal_form(apply(Expr::K, &[x.clone(), y.clone()])), x);
+        // Extra arguments are re-applied to the result
+        assert_eq!(
+            normal_form(apply(Expr::K, &[Expr::I, x.clone(), y.clone()])),
+            y
+        );
+    }
+    
+    #[test]
+    fn test_derived_b_c_w_combinators() {
+        let (f, g, x, y) = (Expr::sym("f"), Expr::sym("g"), Expr::sym("x"), Expr::sym("y"));
+        
+        assert_eq!(
+            normal_form(apply(b_combinator(), &[f.clone(), g.clone(), x.clone()])),
+            Expr::app(f.clone(), Expr::app(g, x.clone()))
+        );
+        assert_eq!(b_combinator(), Expr::metameme_combinator());
+        
+        assert_eq!(
+            normal_form(apply(c_combinator(), &[f.clone(), x.clone(), y.clone()])),
+            apply(f.clone(), &[y, x.clone()])
+        );
+        
+        assert_eq!(
+            normal_form(apply(w_combinator(), &[f.clone(), x.clone()])),
+            apply(f, &[x.clone(), x])
+        );
+    }
+    
+    #[test]
+    fn test_substitution_avoids_capture() {
+        let mut engine = LambdaEngine::new();
+        
+        // (λx.λy.x) y → λy'.y, the free `y` must stay free
+        let konst = Expr::lambda("x", Expr::lambda("y", Expr::var("x")));
+        let trace = engine.normalize(Expr::app(konst, Expr::var("y"))).unwrap();
+        
+        assert!(trace.final_form.alpha_eq(&Expr::lambda("z", Expr::var("y"))));
+        assert_eq!(trace.final_form.free_vars(), HashSet::from(["y".to_string()]));
+    }
+    
+    #[test]
+    fn test_substitution_renames_past_existing_primes() {
+        let mut engine = LambdaEngine::new();
+        
+        // (λx.λy.(x y')) y → λy''.(y y'), neither free variable may be captured
+        let body = Expr::lambda("y", Expr::app(Expr::var("x"), Expr::var("y'")));
+        let trace = engine.normalize(Expr::app(Expr::lambda("x", body), Expr::var("y"))).unwrap();
+        
+        let expected = Expr::lambda("z", Expr::app(Expr::var("y"), Expr::var("y'")));
+        assert!(trace.final_form.alpha_eq(&expected));
+    }
+    
+    #[test]
+    fn test_free_vars() {
+        let expr = Expr::app(
+            Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("y"))),
+            Expr::quine(Expr::var("z")),
+        );
+        let expected: HashSet<String> = ["y", "z"].iter().map(|v| v.to_string()).collect();
+        assert_eq!(expr.free_vars(), expected);
+        assert!(Expr::y_combinator().free_vars().is_empty());
     }
     
     #[test]
     fn test_muse_creation() {
         let muse = Expr::muse("Poetry", 0.95);
-        match muse {
+        match &muse {
             Expr::Muse(name, resonance) => {
                 assert_eq!(name, "Poetry");
-                assert_eq!(resonance, 950);
+                assert_eq!(*resonance, 950);
             }
             _ => panic!("Expected Muse"),
         }
@@ -540,6 +835,18 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_seeded_evolution_is_reproducible() {
+        let expr = Expr::solfunmeme();
+        let evolve = |seed| {
+            let mut engine = LambdaEngine::new().with_seed(seed);
+            (0..20).map(|_| engine.evolve(&expr, 0.9).unwrap()).collect::<Vec<_>>()
+        };
+        
+        assert_eq!(evolve(7), evolve(7));
+        assert_ne!(evolve(7), evolve(8));
+    }
+    
     #[test]
     fn test_solfunmeme_expression() {
         let solfunmeme = Expr::solfunmeme();
@@ -0,0 +1,722 @@
+//! # 🎰 Krivine Machine: Normalization Without Substitution
+//!
+//! An alternative backend for [`LambdaEngine::normalize`]. Instead of
+//! rewriting the whole term at every step, a strong Krivine machine walks the
+//! original expression with an environment of closures: beta reduction is just
+//! pushing a binding, and arguments are shared rather than copied. Once the
+//! head is stuck the machine normalizes under binders and into arguments, and
+//! the result is read back into an ordinary [`Expr`].
+//!
+//! The machine implements `Strategy::NormalOrder` with the same rules as the
+//! substitution engine (S, K, I, muses, quines and environment lookups), so it
+//! reaches the same normal forms. It records no per-step trace or metrics.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::Instant;
+
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{BudgetKind, EvalError, Expr, LambdaEngine, ReductionMetrics, ReductionTrace, Strategy, Term, TermArena, TermId, Termination};
+
+/// 🎰 How `LambdaEngine::normalize` carries out reduction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    /// Rewrite the term one redex at a time, with a full step trace
+    #[default]
+    Substitution,
+    /// Strong Krivine machine with closures and read-back; normal order only
+    Machine,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Substitution => write!(f, "substitution"),
+            Backend::Machine => write!(f, "machine"),
+        }
+    }
+}
+
+/// ❌ A backend name that `Backend::from_str` does not recognize
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("unknown reduction backend `{0}` (expected substitution or machine)")]
+pub struct UnknownBackend(pub String);
+
+impl FromStr for Backend {
+    type Err = UnknownBackend;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "substitution" | "subst" => Ok(Backend::Substitution),
+            "machine" | "krivine" => Ok(Backend::Machine),
+            _ => Err(UnknownBackend(s.to_string())),
+        }
+    }
+}
+
+/// Persistent environment: innermost binding first
+type Env<'a> = Option<Rc<Binding<'a>>>;
+
+struct Binding<'a> {
+    name: &'a str,
+    value: Rc<Thunk<'a>>,
+    next: Env<'a>,
+}
+
+fn bind<'a>(env: &Env<'a>, name: &'a str, value: Rc<Thunk<'a>>) -> Env<'a> {
+    Some(Rc::new(Binding { name, value, next: env.clone() }))
+}
+
+fn lookup<'a>(env: &Env<'a>, name: &str) -> Option<Rc<Thunk<'a>>> {
+    let mut current = env.as_ref();
+    while let Some(binding) = current {
+        if binding.name == name {
+            return Some(Rc::clone(&binding.value));
+        }
+        current = binding.next.as_ref();
+    }
+    None
+}
+
+/// A suspended computation
+enum Thunk<'a> {
+    /// A subterm of the input (or of an environment value) under its bindings
+    Closure(&'a Expr, Env<'a>, usize),
+    /// Application built by the S rule, sharing `x` between both sides
+    App(Rc<Thunk<'a>>, Rc<Thunk<'a>>, usize),
+    /// A muse produced by a muse rule
+    Muse(String, u32),
+    /// A variable with no binding: free in the input, or the binder of a
+    /// lambda the machine is normalizing under
+    Neutral(String),
+}
+
+impl Thunk<'_> {
+    /// Nodes of the term this thunk stands for; closures are only measured
+    /// when the budget limits term size, and count 1 otherwise
+    fn size(&self) -> usize {
+        match self {
+            Thunk::Closure(_, _, size) | Thunk::App(_, _, size) => *size,
+            Thunk::Muse(..) | Thunk::Neutral(_) => 1,
+        }
+    }
+}
+
+fn app<'a>(function: Rc<Thunk<'a>>, argument: Rc<Thunk<'a>>) -> Rc<Thunk<'a>> {
+    let size = function.size().saturating_add(argument.size()).saturating_add(1);
+    Rc::new(Thunk::App(function, argument, size))
+}
+
+/// Size of a subterm of the input, and how often each of its free variables occurs
+struct Shape<'a> {
+    size: usize,
+    free: BTreeMap<&'a str, usize>,
+}
+
+/// Arguments waiting for the focus, first on top
+#[derive(Default)]
+struct Spine<'a> {
+    arguments: Vec<Rc<Thunk<'a>>>,
+    /// Nodes the arguments up to each one add to the focus, applications included
+    sizes: Vec<usize>,
+}
+
+impl<'a> Spine<'a> {
+    fn push(&mut self, argument: Rc<Thunk<'a>>) {
+        self.sizes.push(self.size().saturating_add(argument.size()).saturating_add(1));
+        self.arguments.push(argument);
+    }
+
+    fn pop(&mut self) -> Option<Rc<Thunk<'a>>> {
+        self.sizes.pop();
+        self.arguments.pop()
+    }
+
+    fn last(&self) -> Option<&Rc<Thunk<'a>>> {
+        self.arguments.last()
+    }
+
+    fn len(&self) -> usize {
+        self.arguments.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.arguments.is_empty()
+    }
+
+    /// Nodes every pending argument adds to the focus
+    fn size(&self) -> usize {
+        self.sizes.last().copied().unwrap_or(0)
+    }
+}
+
+/// A head the machine cannot reduce further
+enum Head<'a> {
+    Lambda(&'a str, &'a Expr, Env<'a>),
+    Atom(Expr),
+}
+
+enum StopReason {
+    StepLimit,
+    Budget(BudgetKind),
+}
+
+/// Why the machine stopped early, and the term it had reached
+struct Stop {
+    reason: StopReason,
+    partial: Expr,
+}
+
+/// What `Machine::normalize` builds around the term it is normalizing
+enum Frame<'a> {
+    /// The body of a lambda with this binder, sitting at this depth
+    Lambda(String, usize),
+    /// The remaining arguments of a stuck head, applied to those already
+    /// normalized; the application sits at this depth
+    Arguments(Expr, std::vec::IntoIter<Rc<Thunk<'a>>>, usize),
+}
+
+struct Machine<'a> {
+    engine: &'a LambdaEngine,
+    started: Instant,
+    steps: usize,
+    /// Names that may occur free in read-back terms; binders must avoid them
+    reserved: HashSet<String>,
+    /// Binder names currently in use by lambdas being normalized
+    scope: Vec<String>,
+    /// Nodes of the normal form committed so far, outside the term in focus
+    built: usize,
+    /// Nodes of the arguments still waiting in `normalize`'s frames
+    pending: usize,
+    /// Depth of the term in focus within the normal form
+    depth: usize,
+    /// Shapes of the subterms closures have been built from, by address
+    shapes: HashMap<*const Expr, Rc<Shape<'a>>>,
+}
+
+impl<'a> Machine<'a> {
+    /// Account for one contraction, or explain why it may not happen
+    fn charge(&mut self, focus: &Rc<Thunk<'a>>, stack: &Spine<'a>) -> Result<(), Stop> {
+        let budget = &self.engine.budget;
+        let reason = if self.steps >= self.engine.max_steps {
+            Some(StopReason::StepLimit)
+        } else if budget.deadline.is_some_and(|deadline| self.started.elapsed() >= deadline) {
+            Some(StopReason::Budget(BudgetKind::Deadline))
+        } else if budget.max_steps == Some(self.steps) {
+            Some(StopReason::Budget(BudgetKind::Steps))
+        } else {
+            // Every pending argument is one more application around the focus
+            let size = self.built.saturating_add(self.pending).saturating_add(stack.size()).saturating_add(focus.size());
+            budget.check_shape(size, self.depth + stack.len()).map(StopReason::Budget)
+        };
+
+        match reason {
+            Some(reason) => Err(Stop { reason, partial: self.read_back_state(focus, stack) }),
+            None => {
+                self.steps += 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Suspend `expr` under `env`, measured as the term it stands for when
+    /// the budget limits term size
+    fn closure(&mut self, expr: &'a Expr, env: Env<'a>) -> Rc<Thunk<'a>> {
+        let size = match self.engine.budget.max_term_size {
+            Some(_) => {
+                let shape = self.shape(expr);
+                shape.free.iter().fold(shape.size, |size, (name, &count)| match lookup(&env, name) {
+                    Some(value) => size.saturating_add(count.saturating_mul(value.size() - 1)),
+                    None => size,
+                })
+            }
+            None => 1,
+        };
+        Rc::new(Thunk::Closure(expr, env, size))
+    }
+
+    /// The shape of `expr`, worked out once per subterm
+    fn shape(&mut self, expr: &'a Expr) -> Rc<Shape<'a>> {
+        let mut pending = vec![(expr, false)];
+        while let Some((expr, children_done)) = pending.pop() {
+            let key: *const Expr = expr;
+            if self.shapes.contains_key(&key) {
+                continue;
+            }
+            let children: Vec<&'a Expr> = match expr {
+                Expr::Lambda(_, body) | Expr::Quine(body) => vec![body],
+                Expr::App(left, right) => vec![left, right],
+                _ => Vec::new(),
+            };
+            if !children_done && !children.is_empty() {
+                pending.push((expr, true));
+                pending.extend(children.into_iter().map(|child| (child, false)));
+                continue;
+            }
+
+            let mut shape = Shape { size: 1, free: BTreeMap::new() };
+            if let Expr::Var(name) = expr {
+                shape.free.insert(name.as_str(), 1);
+            }
+            for child in children {
+                let child = &self.shapes[&(child as *const Expr)];
+                shape.size = shape.size.saturating_add(child.size);
+                for (&name, &count) in &child.free {
+                    let occurrences = shape.free.entry(name).or_default();
+                    *occurrences = occurrences.saturating_add(count);
+                }
+            }
+            if let Expr::Lambda(param, _) = expr {
+                shape.free.remove(param.as_str());
+            }
+            self.shapes.insert(key, Rc::new(shape));
+        }
+        Rc::clone(&self.shapes[&(expr as *const Expr)])
+    }
+
+    /// Run to weak head normal form; the stack holds pending arguments, first on top
+    fn whnf(&mut self, mut focus: Rc<Thunk<'a>>) -> Result<(Head<'a>, Spine<'a>), Stop> {
+        let mut stack = Spine::default();
+        loop {
+            let next = match &*focus {
+                Thunk::App(function, argument, _) => {
+                    stack.push(Rc::clone(argument));
+                    Rc::clone(function)
+                }
+                // A lambda the machine is under shadows the environment
+                Thunk::Neutral(name) => match self.engine.environment.get(name).filter(|_| !self.scope.contains(name)) {
+                    Some(value) => {
+                        self.charge(&focus, &stack)?;
+                        self.closure(value, None)
+                    }
+                    None => return Ok((Head::Atom(Expr::var(name)), stack)),
+                },
+                Thunk::Muse(name, resonance) => {
+                    let muse = Expr::Muse(name.clone(), *resonance);
+                    let redex = match stack.last() {
+                        Some(argument) => Expr::app(muse.clone(), self.read_back(argument)),
+                        None => muse.clone(),
+                    };
+                    match &crate::contract_muse(&redex) {
+                        Some((_, Expr::Muse(name, resonance))) => {
+                            self.charge(&focus, &stack)?;
+                            if !matches!(redex, Expr::Muse(..)) {
+                                stack.pop();
+                            }
+                            Rc::new(Thunk::Muse(name.clone(), *resonance))
+                        }
+                        _ => return Ok((Head::Atom(muse), stack)),
+                    }
+                }
+                Thunk::Closure(expr, env, _) => match *expr {
+                    Expr::Var(name) => match lookup(env, name) {
+                        Some(value) => value,
+                        None => Rc::new(Thunk::Neutral(name.clone())),
+                    },
+                    Expr::Lambda(param, body) => {
+                        if stack.is_empty() {
+                            return Ok((Head::Lambda(param, body.as_ref(), env.clone()), stack));
+                        }
+                        self.charge(&focus, &stack)?;
+                        let argument = stack.pop().expect("stack checked non-empty");
+                        self.closure(body, bind(env, param, argument))
+                    }
+                    Expr::App(left, right) => {
+                        let right = self.closure(right, env.clone());
+                        stack.push(right);
+                        self.closure(left, env.clone())
+                    }
+                    Expr::S if stack.len() >= 3 => {
+                        self.charge(&focus, &stack)?;
+                        let f = stack.pop().expect("S has three arguments");
+                        let g = stack.pop().expect("S has three arguments");
+                        let x = stack.pop().expect("S has three arguments");
+                        stack.push(app(g, Rc::clone(&x)));
+                        stack.push(x);
+                        f
+                    }
+                    Expr::K if stack.len() >= 2 => {
+                        self.charge(&focus, &stack)?;
+                        let x = stack.pop().expect("K has two arguments");
+                        stack.pop();
+                        x
+                    }
+                    Expr::I if !stack.is_empty() => {
+                        self.charge(&focus, &stack)?;
+                        stack.pop().expect("I has an argument")
+                    }
+                    Expr::Quine(inner) => {
+                        self.charge(&focus, &stack)?;
+                        self.closure(inner, env.clone())
+                    }
+                    Expr::Muse(name, resonance) => Rc::new(Thunk::Muse(name.clone(), *resonance)),
+                    atom => return Ok((Head::Atom(atom.clone()), stack)),
+                },
+            };
+            focus = next;
+        }
+    }
+
+    /// Strong normalization: head reduce, then go under binders and into arguments
+    ///
+    /// The normal form is built with an explicit stack of frames, and its
+    /// size and depth are held to the budget as it grows.
+    fn normalize(&mut self, root: Rc<Thunk<'a>>) -> Result<Expr, Stop> {
+        let mut frames = Vec::new();
+        let mut focus = root;
+        self.depth = 1;
+        'normalize: loop {
+            let size = self.built.saturating_add(self.pending).saturating_add(focus.size());
+            if let Some(kind) = self.engine.budget.check_shape(size, self.depth) {
+                let stop = Stop { reason: StopReason::Budget(kind), partial: self.read_back(&focus) };
+                return Err(self.unwind(frames, stop));
+            }
+            let (head, stack) = match self.whnf(focus) {
+                Ok(stuck) => stuck,
+                Err(stop) => return Err(self.unwind(frames, stop)),
+            };
+
+            match head {
+                Head::Lambda(param, body, env) => {
+                    let name = self.fresh_binder(param);
+                    self.scope.push(name.clone());
+                    let neutral = Rc::new(Thunk::Neutral(name.clone()));
+                    frames.push(Frame::Lambda(name, self.depth));
+                    self.built += 1;
+                    self.depth += 1;
+                    focus = self.closure(body, bind(&env, param, neutral));
+                    continue;
+                }
+                Head::Atom(atom) => {
+                    self.built += 1 + stack.len();
+                    let arguments: Vec<Rc<Thunk<'a>>> = stack.arguments.into_iter().rev().collect();
+                    self.pending = arguments.iter().fold(self.pending, |pending, argument| pending.saturating_add(argument.size()));
+                    frames.push(Frame::Arguments(atom, arguments.into_iter(), self.depth));
+                }
+            }
+
+            // Finish every frame that is complete, until one has an argument left
+            let mut normalized = None;
+            loop {
+                match frames.pop() {
+                    None => return Ok(normalized.expect("the root is normalized last")),
+                    Some(Frame::Lambda(name, depth)) => {
+                        self.scope.pop();
+                        self.depth = depth;
+                        normalized = normalized.map(|body| Expr::lambda(&name, body));
+                    }
+                    Some(Frame::Arguments(expr, mut rest, depth)) => {
+                        let expr = match normalized.take() {
+                            Some(argument) => Expr::app(expr, argument),
+                            None => expr,
+                        };
+                        match rest.next() {
+                            Some(argument) => {
+                                self.pending = self.pending.saturating_sub(argument.size());
+                                self.depth = depth + rest.len() + 1;
+                                frames.push(Frame::Arguments(expr, rest, depth));
+                                focus = argument;
+                                continue 'normalize;
+                            }
+                            None => {
+                                self.depth = depth;
+                                normalized = Some(expr);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Wrap the partial term of `stop` in everything `normalize` had built around it
+    fn unwind(&self, frames: Vec<Frame<'a>>, mut stop: Stop) -> Stop {
+        for frame in frames.into_iter().rev() {
+            stop.partial = match frame {
+                Frame::Lambda(name, _) => Expr::lambda(&name, stop.partial),
+                Frame::Arguments(expr, rest, _) => {
+                    rest.fold(Expr::app(expr, stop.partial), |partial, argument| Expr::app(partial, self.read_back(&argument)))
+                }
+            };
+        }
+        stop
+    }
+
+    /// Binder name for a lambda the machine goes under, primed away from
+    /// every name that could occur free beneath it
+    fn fresh_binder(&self, param: &str) -> String {
+        let mut name = param.to_string();
+        while self.reserved.contains(&name) || self.scope.contains(&name) {
+            name.push('\'');
+        }
+        name
+    }
+
+    /// 📤 Read a suspended computation back as the term it stands for
+    fn read_back(&self, thunk: &Rc<Thunk<'a>>) -> Expr {
+        let mut arena = TermArena::new();
+        let id = intern_thunk(&mut arena, thunk);
+        arena.to_expr(id)
+    }
+
+    fn read_back_state(&self, focus: &Rc<Thunk<'a>>, stack: &Spine<'a>) -> Expr {
+        let mut arena = TermArena::new();
+        let head = intern_thunk(&mut arena, focus);
+        let id = stack.arguments.iter().rev().fold(head, |id, argument| {
+            let argument = intern_thunk(&mut arena, argument);
+            arena.app(id, argument)
+        });
+        arena.to_expr(id)
+    }
+}
+
+/// Store the term a thunk stands for, with every closure's bindings
+/// substituted in
+///
+/// The arena's read-back renames binders that would capture, and the walk
+/// keeps its own stack and reads each shared thunk once, so long chains of
+/// closures cannot overflow the call stack.
+fn intern_thunk<'a>(arena: &mut TermArena, root: &Rc<Thunk<'a>>) -> TermId {
+    enum Visit<'a> {
+        Thunk(Rc<Thunk<'a>>),
+        /// A subterm of a closure, whose own binders start at `binders[base]`
+        Expr(&'a Expr, Env<'a>, usize),
+        Lambda(&'a str),
+        App,
+        Quine,
+        /// The thunk at this address has just been stored
+        Stored(*const Thunk<'a>),
+    }
+
+    let mut stored = HashMap::new();
+    let mut binders: Vec<&'a str> = Vec::new();
+    let mut pending = vec![Visit::Thunk(Rc::clone(root))];
+    let mut built = Vec::new();
+    while let Some(visit) = pending.pop() {
+        let term = match visit {
+            Visit::Thunk(thunk) => {
+                if let Some(&id) = stored.get(&Rc::as_ptr(&thunk)) {
+                    built.push(id);
+                    continue;
+                }
+                pending.push(Visit::Stored(Rc::as_ptr(&thunk)));
+                match &*thunk {
+                    Thunk::Closure(expr, env, _) => {
+                        pending.push(Visit::Expr(expr, env.clone(), binders.len()));
+                        continue;
+                    }
+                    Thunk::App(function, argument, _) => {
+                        pending.extend([Visit::App, Visit::Thunk(Rc::clone(argument)), Visit::Thunk(Rc::clone(function))]);
+                        continue;
+                    }
+                    Thunk::Muse(name, resonance) => Term::Muse(name.clone(), *resonance),
+                    Thunk::Neutral(name) => Term::Var(name.clone()),
+                }
+            }
+            Visit::Stored(thunk) => {
+                stored.insert(thunk, *built.last().expect("thunk stored"));
+                continue;
+            }
+            Visit::Expr(expr, env, base) => match expr {
+                Expr::Var(name) => match binders[base..].iter().rev().position(|binder| binder == name) {
+                    Some(index) => Term::Bound(index as u32),
+                    None => match lookup(&env, name) {
+                        Some(value) => {
+                            pending.push(Visit::Thunk(value));
+                            continue;
+                        }
+                        None => Term::Var(name.clone()),
+                    },
+                },
+                Expr::Lambda(param, body) => {
+                    binders.push(param);
+                    pending.extend([Visit::Lambda(param), Visit::Expr(body, env, base)]);
+                    continue;
+                }
+                Expr::App(left, right) => {
+                    pending.extend([Visit::App, Visit::Expr(right, env.clone(), base), Visit::Expr(left, env, base)]);
+                    continue;
+                }
+                Expr::Quine(inner) => {
+                    pending.extend([Visit::Quine, Visit::Expr(inner, env, base)]);
+                    continue;
+                }
+                Expr::Sym(symbol) => Term::Sym(symbol.clone()),
+                Expr::S => Term::S,
+                Expr::K => Term::K,
+                Expr::I => Term::I,
+                Expr::Muse(name, resonance) => Term::Muse(name.clone(), *resonance),
+                Expr::DNA(data) => Term::DNA(data.clone()),
+            },
+            Visit::Lambda(param) => {
+                binders.pop();
+                Term::Lambda(param.to_string(), built.pop().expect("body stored"))
+            }
+            Visit::App => {
+                let argument = built.pop().expect("argument stored");
+                let function = built.pop().expect("function stored");
+                Term::App(function, argument)
+            }
+            Visit::Quine => Term::Quine(built.pop().expect("inner term stored")),
+        };
+        built.push(arena.intern(term));
+    }
+    built.pop().expect("root stored")
+}
+
+impl LambdaEngine {
+    /// Set the backend used by `normalize`
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// 🎰 Normalize with the Krivine machine, honouring `max_steps` and the budget
+    pub(crate) fn normalize_machine(&self, expr: &Expr) -> Result<ReductionTrace, EvalError> {
+        if let Some(kind) = self.budget.check_term(expr) {
+            return Err(EvalError::BudgetExceeded { kind, partial: expr.clone(), steps: 0 });
+        }
+        info!("🎰 Starting machine normalization of: {}", expr);
+
+        let mut reserved = expr.free_vars();
+        reserved.extend(self.environment.values().flat_map(Expr::free_vars));
+        let mut machine = Machine {
+            engine: self,
+            started: Instant::now(),
+            steps: 0,
+            reserved,
+            scope: Vec::new(),
+            built: 0,
+            pending: 0,
+            depth: 0,
+            shapes: HashMap::new(),
+        };
+
+        let root = machine.closure(expr, None);
+        let (final_form, termination) = match machine.normalize(root) {
+            Ok(normal) => (normal, Termination::NormalForm),
+            Err(Stop { reason: StopReason::Budget(kind), partial }) => {
+                return Err(EvalError::BudgetExceeded { kind, partial, steps: machine.steps });
+            }
+            Err(Stop { reason: StopReason::StepLimit, partial }) => (partial, Termination::StepLimit),
+        };
+        debug!("🎰 Machine stopped after {} steps: {}", machine.steps, termination);
+
+        if let Some(kind) = self.budget.check_term(&final_form) {
+            return Err(EvalError::BudgetExceeded { kind, partial: final_form, steps: machine.steps });
+        }
+
+        // The machine never builds the terms in between, so only the ends are measured
+        let mut metrics = ReductionMetrics::default();
+        metrics.record(self.measure(expr));
+        metrics.record(self.measure(&final_form));
+        
+        let mut arena = TermArena::new();
+        let terms = vec![arena.from_expr(expr), arena.from_expr(&final_form)];
+        Ok(ReductionTrace {
+            step_count: machine.steps,
+            final_form,
+            is_normal_form: termination == Termination::NormalForm,
+            termination,
+            strategy: Strategy::NormalOrder,
+            metrics,
+            arena,
+            terms,
+            redexes: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn church(n: usize) -> Expr {
+        let mut body = Expr::var("x");
+        for _ in 0..n {
+            body = Expr::app(Expr::var("f"), body);
+        }
+        Expr::lambda("f", Expr::lambda("x", body))
+    }
+
+    fn both(expr: &Expr) -> (ReductionTrace, ReductionTrace) {
+        let substitution = LambdaEngine::new().try_normalize(expr.clone()).unwrap();
+        let machine = LambdaEngine::new().with_backend(Backend::Machine).try_normalize(expr.clone()).unwrap();
+        (substitution, machine)
+    }
+
+    #[test]
+    fn test_machine_normalizes_church_arithmetic() {
+        // 2 3 = 3^2 = 9
+        let (substitution, machine) = both(&Expr::app(church(2), church(3)));
+        assert!(machine.is_normal_form);
+        assert!(machine.final_form.alpha_eq(&church(9)));
+        assert!(machine.final_form.alpha_eq(&substitution.final_form));
+        assert!(machine.steps().is_empty());
+        assert!(!machine.metrics.per_step && substitution.metrics.per_step);
+        assert_eq!(machine.metrics.known_peak_size(), None);
+    }
+
+    #[test]
+    fn test_machine_avoids_capture_like_substitution() {
+        let expr = Expr::app(Expr::lambda("x", Expr::lambda("y", Expr::var("x"))), Expr::var("y"));
+        let (substitution, machine) = both(&expr);
+        assert_eq!(machine.final_form, substitution.final_form);
+        assert_eq!(machine.final_form, Expr::lambda("y'", Expr::var("y")));
+    }
+
+    #[test]
+    fn test_machine_binders_shadow_the_environment() {
+        let mut engine = LambdaEngine::new();
+        engine.bind("seven", church(7));
+        let identity = Expr::lambda("seven", Expr::var("seven"));
+        for expr in [identity.clone(), Expr::app(identity, Expr::var("seven"))] {
+            let substitution = engine.clone().try_normalize(expr.clone()).unwrap();
+            let machine = engine.clone().with_backend(Backend::Machine).try_normalize(expr.clone()).unwrap();
+            assert_eq!(machine.final_form, substitution.final_form, "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_machine_holds_the_normal_form_to_the_budget() {
+        // 2 2 2 2 2 = 2^65536, far beyond the size limit and too deep to build recursively
+        let tower = (0..4).fold(church(2), |tower, _| Expr::app(tower, church(2)));
+        let budget = crate::EvalBudget::unlimited().with_max_term_size(100_000);
+        let mut engine = LambdaEngine::new().with_max_steps(usize::MAX).with_budget(budget).with_backend(Backend::Machine);
+        let err = engine.try_normalize(tower).unwrap_err();
+        assert!(matches!(err, EvalError::BudgetExceeded { kind: BudgetKind::TermSize, .. }), "{}", err);
+    }
+
+    #[test]
+    fn test_machine_runs_combinators_muses_and_quines() {
+        let expr = Expr::app(
+            Expr::app(Expr::app(Expr::S, Expr::K), Expr::quine(Expr::muse("Sol", 0.99))),
+            Expr::app(Expr::muse("Fun", 0.98), Expr::app(Expr::I, Expr::sym("🌀"))),
+        );
+        let (substitution, machine) = both(&expr);
+        assert_eq!(machine.final_form, substitution.final_form);
+        assert!(machine.is_normal_form);
+    }
+
+    #[test]
+    fn test_machine_stops_at_step_limit_with_partial_term() {
+        let engine = LambdaEngine::new().with_max_steps(25).with_backend(Backend::Machine);
+        let trace = engine.clone().try_normalize(Expr::app(Expr::y_combinator(), Expr::sym("f"))).unwrap();
+        assert_eq!(trace.termination, Termination::StepLimit);
+        assert_eq!(trace.step_count, 25);
+        assert!(matches!(trace.final_form, Expr::App(ref f, _) if **f == Expr::sym("f")));
+    }
+
+    #[test]
+    fn test_backend_names_parse() {
+        assert_eq!("machine".parse::<Backend>(), Ok(Backend::Machine));
+        assert_eq!("Substitution".parse::<Backend>(), Ok(Backend::Substitution));
+        assert!("bytecode".parse::<Backend>().is_err());
+    }
+}
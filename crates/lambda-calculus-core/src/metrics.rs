@@ -0,0 +1,118 @@
+//! # 📈 Reduction Metrics: How Hard Did a Term Work?
+//!
+//! `step_count` says how long a reduction ran, not how much it did. These
+//! metrics follow the shape of the term through the whole reduction: its
+//! size, depth and number of redexes after every step, the largest it ever
+//! got, and how often a step copied an argument. Together they give rarity
+//! scoring a complexity measure that sees the difference between ten steps
+//! of `I` and ten steps of exponential blow-up.
+
+use serde::{Deserialize, Serialize};
+
+use crate::arena::Reducer;
+use crate::trace::Redex;
+use crate::{Expr, LambdaEngine};
+
+/// 📏 The shape of one term in a reduction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TermMetrics {
+    /// Nodes, as `Expr::size`
+    pub size: usize,
+    /// Nesting depth, as `Expr::depth`
+    pub depth: usize,
+    /// Subterms any strategy could contract next
+    pub redexes: usize,
+}
+
+/// 📈 Term growth and duplication over a whole reduction
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReductionMetrics {
+    /// The original term first, then the term after each step
+    pub terms: Vec<TermMetrics>,
+    /// False when only the original and final terms were measured, as by the
+    /// machine backend; the peaks then miss any growth in between
+    pub per_step: bool,
+    /// Largest size reached
+    pub peak_size: usize,
+    /// Greatest depth reached
+    pub peak_depth: usize,
+    /// Most redexes present at once
+    pub peak_redexes: usize,
+    /// Steps that copied an argument: β with the variable used more than
+    /// once, and `S`, which always passes its last argument twice
+    pub duplications: usize,
+}
+
+impl ReductionMetrics {
+    /// Redexes in the original term
+    pub fn initial_redexes(&self) -> usize {
+        self.terms.first().map_or(0, |term| term.redexes)
+    }
+
+    /// Largest size reached, if every step was measured
+    pub fn known_peak_size(&self) -> Option<usize> {
+        self.per_step.then_some(self.peak_size)
+    }
+
+    pub(crate) fn record(&mut self, term: TermMetrics) {
+        self.peak_size = self.peak_size.max(term.size);
+        self.peak_depth = self.peak_depth.max(term.depth);
+        self.peak_redexes = self.peak_redexes.max(term.redexes);
+        self.terms.push(term);
+    }
+
+    /// Count the step if its redex copies an argument
+    pub(crate) fn record_redex(&mut self, redex: &Redex) {
+        if redex.duplicates {
+            self.duplications += 1;
+        }
+    }
+}
+
+impl LambdaEngine {
+    /// 📏 Size, depth and redex count of `expr` under this engine's rules
+    pub fn measure(&self, expr: &Expr) -> TermMetrics {
+        let mut reducer = Reducer::new(self);
+        let id = reducer.arena.from_expr(expr);
+        reducer.measure(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redexes_are_counted_everywhere() {
+        let engine = LambdaEngine::new();
+        // I a, K b c under a lambda, and a standalone muse
+        let expr = Expr::app(
+            Expr::app(Expr::I, Expr::sym("a")),
+            Expr::lambda("x", Expr::app(Expr::app(Expr::app(Expr::K, Expr::sym("b")), Expr::sym("c")), Expr::muse("Sol", 0.5))),
+        );
+        let metrics = engine.measure(&expr);
+        assert_eq!(metrics.redexes, 3);
+        assert_eq!(metrics.size, expr.size());
+        assert_eq!(metrics.depth, expr.depth());
+
+        // Partially applied combinators are not redexes
+        assert_eq!(engine.measure(&Expr::app(Expr::app(Expr::S, Expr::K), Expr::K)).redexes, 0);
+    }
+
+    #[test]
+    fn test_normalize_tracks_growth_and_duplication() {
+        // (λx.x x x) (I a) copies its argument three times, then each copy reduces
+        let triple = Expr::lambda("x", Expr::app(Expr::app(Expr::var("x"), Expr::var("x")), Expr::var("x")));
+        let expr = Expr::app(triple, Expr::app(Expr::I, Expr::sym("a")));
+        let trace = LambdaEngine::new().normalize(expr.clone()).unwrap();
+        let metrics = &trace.metrics;
+
+        assert_eq!(metrics.terms.len(), trace.step_count + 1);
+        assert_eq!(metrics.initial_redexes(), 2);
+        assert_eq!(metrics.duplications, 1);
+        assert_eq!(metrics.peak_redexes, 3);
+        assert_eq!(metrics.peak_size, 11);
+        assert!(metrics.peak_size > expr.size());
+        assert_eq!(metrics.terms.last().unwrap().redexes, 0);
+    }
+}
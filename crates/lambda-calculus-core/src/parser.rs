@@ -0,0 +1,511 @@
+//! # 📖 Expression Parser: Turning Poetry Back Into Code
+//!
+//! Parses the textual form produced by `impl Display for Expr` back into an
+//! [`Expr`], so stored `lambda_expression` strings can be reloaded and reduced.
+//!
+//! ## Grammar
+//!
+//! - `λx.body` or `\x.body` - a lambda whose body is a *single* term, exactly as
+//!   `Display` prints it (`λx.(x y)`, not `λx.x y`)
+//! - `(f x)` - application; more terms associate to the left, `(f x y)` = `((f x) y)`
+//! - `S`, `K`, `I` - the combinators
+//! - `🎭Name[950]` - a muse with its resonance in thousandths; `🎭"name"[950]`
+//!   when the name would not read back on its own
+//! - `🌀Q(expr)` - a quine
+//! - `🧬DNA[0007ff]` - DNA, its bytes as pairs of hex digits
+//! - identifiers made of letters, digits, `_` and `'` are variables; any other
+//!   glyph run (emoji, punctuation) is a symbol
+//! - `` `name` `` is a variable and `"name"` a symbol whatever the name
+//!   contains; `\` escapes the closing quote or itself. `Display` quotes
+//!   exactly the names that would otherwise read back as something else, such
+//!   as the variable `` `S` `` or the symbols `"y"` and `"a b"`
+//!
+//! Parentheses, lambdas and quines nest at most [`MAX_NESTING`] deep, so
+//! hostile input cannot exhaust the stack.
+
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::Expr;
+
+/// ❌ A parse failure with the byte span of the offending input
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{message} at {}..{}", span.start, span.end)]
+pub struct ParseError {
+    /// Human readable description of what went wrong
+    pub message: String,
+    /// Byte range in the source text
+    pub span: Range<usize>,
+}
+
+impl ParseError {
+    fn new(message: impl Into<String>, span: Range<usize>) -> Self {
+        Self { message: message.into(), span }
+    }
+}
+
+/// How deeply parentheses, lambdas and quines may nest before parsing fails
+pub const MAX_NESTING: usize = 256;
+
+impl Expr {
+    /// 📖 Parse an expression from its textual form
+    pub fn parse(input: &str) -> Result<Expr, ParseError> {
+        let mut parser = Parser::new(input);
+        let expr = parser.parse_sequence()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            let end = parser.pos + c.len_utf8();
+            return Err(ParseError::new(format!("unexpected `{}`", c), parser.pos..end));
+        }
+        Ok(expr)
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Expr::parse(s)
+    }
+}
+
+const QUINE_PREFIX: &str = "🌀Q(";
+const DNA_PREFIX: &str = "🧬DNA[";
+const MUSE_PREFIX: &str = "🎭";
+const VARIABLE_QUOTE: char = '`';
+const SYMBOL_QUOTE: char = '"';
+
+/// ✍️ Write a variable (or binder) name so that it parses back as that variable
+pub(crate) fn write_variable(f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+    if reads_back_as(name, &Expr::Var(name.to_string())) {
+        write!(f, "{}", name)
+    } else {
+        write_quoted(f, name, VARIABLE_QUOTE)
+    }
+}
+
+/// ✍️ Write a symbol so that it parses back as that symbol
+pub(crate) fn write_symbol(f: &mut fmt::Formatter<'_>, symbol: &str) -> fmt::Result {
+    if reads_back_as(symbol, &Expr::Sym(symbol.to_string())) {
+        write!(f, "{}", symbol)
+    } else {
+        write_quoted(f, symbol, SYMBOL_QUOTE)
+    }
+}
+
+/// ✍️ Write a muse so that it parses back as that muse, quoting the name
+/// like a symbol when needed
+pub(crate) fn write_muse(f: &mut fmt::Formatter<'_>, name: &str, resonance: u32) -> fmt::Result {
+    let plain = format!("{}{}[{}]", MUSE_PREFIX, name, resonance);
+    if reads_back_as(&plain, &Expr::Muse(name.to_string(), resonance)) {
+        write!(f, "{}", plain)
+    } else {
+        write!(f, "{}", MUSE_PREFIX)?;
+        write_quoted(f, name, SYMBOL_QUOTE)?;
+        write!(f, "[{}]", resonance)
+    }
+}
+
+/// ✍️ Write DNA with its bytes in hex
+pub(crate) fn write_dna(f: &mut fmt::Formatter<'_>, data: &[u8]) -> fmt::Result {
+    write!(f, "{}", DNA_PREFIX)?;
+    for byte in data {
+        write!(f, "{:02x}", byte)?;
+    }
+    write!(f, "]")
+}
+
+/// True when `text` on its own parses as exactly one term, equal to `expected`
+fn reads_back_as(text: &str, expected: &Expr) -> bool {
+    let mut parser = Parser::new(text);
+    parser.parse_term().is_ok_and(|expr| expr == *expected) && parser.pos == text.len()
+}
+
+fn write_quoted(f: &mut fmt::Formatter<'_>, text: &str, quote: char) -> fmt::Result {
+    write!(f, "{}", quote)?;
+    for c in text.chars() {
+        if c == quote || c == '\\' {
+            write!(f, "\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    write!(f, "{}", quote)
+}
+
+/// Recursive-descent parser over the source text
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    /// Parentheses, lambdas and quines open around the current position
+    nesting: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0, nesting: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.bump();
+                Ok(())
+            }
+            Some(c) => Err(ParseError::new(
+                format!("expected `{}`, found `{}`", expected, c),
+                self.pos..self.pos + c.len_utf8(),
+            )),
+            None => Err(ParseError::new(
+                format!("expected `{}`, found end of input", expected),
+                self.pos..self.pos,
+            )),
+        }
+    }
+
+    /// One or more juxtaposed terms, folded into left-associated applications
+    fn parse_sequence(&mut self) -> Result<Expr, ParseError> {
+        let mut expr: Option<Expr> = None;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None | Some(')') => break,
+                _ => {
+                    let term = self.parse_term()?;
+                    expr = Some(match expr {
+                        Some(left) => Expr::app(left, term),
+                        None => term,
+                    });
+                }
+            }
+        }
+        expr.ok_or_else(|| ParseError::new("expected expression", self.pos..self.pos))
+    }
+
+    /// Parse a term nested one level deeper than the current position
+    fn nested(&mut self, start: usize, parse: impl FnOnce(&mut Self) -> Result<Expr, ParseError>) -> Result<Expr, ParseError> {
+        if self.nesting == MAX_NESTING {
+            return Err(ParseError::new(format!("nested deeper than {}", MAX_NESTING), start..self.pos));
+        }
+        self.nesting += 1;
+        let expr = parse(self);
+        self.nesting -= 1;
+        expr
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            None => Err(ParseError::new("expected expression, found end of input", start..start)),
+            Some(')') => Err(ParseError::new("unexpected `)`", start..start + 1)),
+            Some('(') => self.nested(start, |parser| {
+                parser.bump();
+                let inner = parser.parse_sequence()?;
+                parser.expect(')')?;
+                Ok(inner)
+            }),
+            Some('λ') | Some('\\') => self.nested(start, Self::parse_lambda),
+            Some(VARIABLE_QUOTE) => Ok(Expr::Var(self.parse_quoted(VARIABLE_QUOTE)?)),
+            Some(SYMBOL_QUOTE) => Ok(Expr::Sym(self.parse_quoted(SYMBOL_QUOTE)?)),
+            Some(_) => {
+                if self.rest().starts_with(QUINE_PREFIX) {
+                    self.nested(start, |parser| {
+                        parser.pos += QUINE_PREFIX.len();
+                        let inner = parser.parse_sequence()?;
+                        parser.expect(')')?;
+                        Ok(Expr::quine(inner))
+                    })
+                } else if self.rest().starts_with(DNA_PREFIX) {
+                    self.pos += DNA_PREFIX.len();
+                    self.parse_dna()
+                } else if self.rest().strip_prefix(MUSE_PREFIX).is_some_and(|name| name.starts_with(SYMBOL_QUOTE)) {
+                    self.pos += MUSE_PREFIX.len();
+                    let name = self.parse_quoted(SYMBOL_QUOTE)?;
+                    self.expect('[')?;
+                    let resonance = self.parse_number()?;
+                    self.expect(']')?;
+                    Ok(Expr::Muse(name, resonance))
+                } else if let Some(muse) = self.try_parse_muse() {
+                    Ok(muse)
+                } else {
+                    Ok(self.parse_atom())
+                }
+            }
+        }
+    }
+
+    fn parse_lambda(&mut self) -> Result<Expr, ParseError> {
+        self.bump();
+        self.skip_whitespace();
+        let param = if self.peek() == Some(VARIABLE_QUOTE) {
+            self.parse_quoted(VARIABLE_QUOTE)?
+        } else {
+            let start = self.pos;
+            while self.peek().is_some_and(|c| c != '.' && !is_delimiter(c)) {
+                self.bump();
+            }
+            if self.pos == start {
+                return Err(ParseError::new("expected binder name after lambda", start..start));
+            }
+            self.input[start..self.pos].to_string()
+        };
+        self.expect('.')?;
+        let body = self.parse_term()?;
+        Ok(Expr::Lambda(param, Box::new(body)))
+    }
+
+    /// A name between `quote`s, with `\` escaping the next character
+    fn parse_quoted(&mut self, quote: char) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.bump();
+        let mut name = String::new();
+        loop {
+            match self.bump() {
+                Some(c) if c == quote => return Ok(name),
+                Some('\\') => match self.bump() {
+                    Some(c) => name.push(c),
+                    None => break,
+                },
+                Some(c) => name.push(c),
+                None => break,
+            }
+        }
+        Err(ParseError::new(format!("unterminated `{}` quote", quote), start..self.pos))
+    }
+
+    /// Pairs of hex digits up to `]`, one byte each
+    fn parse_dna(&mut self) -> Result<Expr, ParseError> {
+        let mut data = Vec::new();
+        while !self.rest().starts_with(']') {
+            let pair = self.rest().get(..2).filter(|pair| pair.chars().all(|c| c.is_ascii_hexdigit()));
+            let Some(pair) = pair else {
+                return Err(ParseError::new("expected DNA bytes as pairs of hex digits", self.pos..self.pos));
+            };
+            data.push(u8::from_str_radix(pair, 16).expect("two hex digits"));
+            self.pos += 2;
+        }
+        self.bump();
+        Ok(Expr::dna(data))
+    }
+
+    fn parse_number(&mut self) -> Result<u32, ParseError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+        self.input[start..self.pos]
+            .parse()
+            .map_err(|_| ParseError::new("expected a number", start..self.pos))
+    }
+
+    /// Muse names are whatever `Display` wrote before the trailing `[score]`,
+    /// which may itself contain brackets or parenthesised subterms.
+    fn try_parse_muse(&mut self) -> Option<Expr> {
+        let rest = self.rest().strip_prefix(MUSE_PREFIX)?;
+        let mut depth = 0usize;
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 0 => return None,
+                ')' => depth -= 1,
+                c if c.is_whitespace() && depth == 0 => return None,
+                '[' if depth == 0 && i > 0 => {
+                    let after = &rest[i + 1..];
+                    let digits = after.chars().take_while(char::is_ascii_digit).count();
+                    if digits == 0 || !after[digits..].starts_with(']') {
+                        continue;
+                    }
+                    let tail = &after[digits + 1..];
+                    if tail.chars().next().is_some_and(|c| !is_delimiter(c)) {
+                        continue;
+                    }
+                    let resonance = after[..digits].parse().ok()?;
+                    self.pos += MUSE_PREFIX.len() + i + 1 + digits + 1;
+                    return Some(Expr::Muse(rest[..i].to_string(), resonance));
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn parse_atom(&mut self) -> Expr {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| !is_delimiter(c)) {
+            self.bump();
+        }
+        let token = &self.input[start..self.pos];
+        match token {
+            "S" => Expr::S,
+            "K" => Expr::K,
+            "I" => Expr::I,
+            _ if token.chars().all(is_ident_char) => Expr::var(token),
+            _ => Expr::sym(token),
+        }
+    }
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || c == '(' || c == ')'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '\''
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Names that read back as themselves, and names that only survive quoted
+    const NAMES: [&str; 16] = [
+        "x", "y", "f", "muse", "x'", "🌀", "🎭", "♾️", "S", "K", "a b", "λ", "\\", "a.b", "\"q`", "",
+    ];
+
+    fn random_name(rng: &mut StdRng) -> &'static str {
+        NAMES[rng.gen_range(0..NAMES.len())]
+    }
+
+    fn random_expr(rng: &mut StdRng, depth: u32) -> Expr {
+        let leaf = depth == 0 || rng.gen_bool(0.3);
+        let choice = if leaf { rng.gen_range(0..6) } else { rng.gen_range(6..9) };
+        match choice {
+            0 => Expr::var(random_name(rng)),
+            1 => Expr::sym(random_name(rng)),
+            2 => [Expr::S, Expr::K, Expr::I][rng.gen_range(0..3)].clone(),
+            3 => {
+                // Plain names, awkward ones, and the names muse-apply builds
+                let name = match rng.gen_range(0..3) {
+                    0 => ["Sol", "Fun", "Meme"][rng.gen_range(0..3)].to_string(),
+                    1 => random_name(rng).to_string(),
+                    _ => format!("Sol+{}", random_expr(rng, depth.saturating_sub(1))),
+                };
+                Expr::Muse(name, rng.gen_range(0..1500))
+            }
+            4 => Expr::dna((0..rng.gen_range(0..8)).map(|_| rng.gen()).collect()),
+            5 => Expr::quine(random_expr(rng, depth.saturating_sub(1))),
+            6 => Expr::lambda(random_name(rng), random_expr(rng, depth - 1)),
+            _ => Expr::app(random_expr(rng, depth - 1), random_expr(rng, depth - 1)),
+        }
+    }
+
+    #[test]
+    fn test_parse_round_trips_display() {
+        let mut rng = StdRng::seed_from_u64(0x5013);
+        for _ in 0..500 {
+            let expr = random_expr(&mut rng, 5);
+            let text = format!("{}", expr);
+            assert_eq!(Expr::parse(&text), Ok(expr), "round trip failed for {}", text);
+        }
+    }
+
+    #[test]
+    fn test_ambiguous_names_are_quoted() {
+        for (expr, text) in [
+            (Expr::sym("y"), r#""y""#),
+            (Expr::var("🌀"), "`🌀`"),
+            (Expr::var("S"), "`S`"),
+            (Expr::sym("a b"), r#""a b""#),
+            (Expr::sym("λ"), r#""λ""#),
+            (Expr::sym("\\"), r#""\\""#),
+            (Expr::lambda("S", Expr::var("S")), "λ`S`.`S`"),
+            (Expr::sym("🌀"), "🌀"),
+            (Expr::var("x'"), "x'"),
+            (Expr::Muse("Sol".to_string(), 970), "🎭Sol[970]"),
+            (Expr::Muse("a b".to_string(), 5), r#"🎭"a b"[5]"#),
+            (Expr::Muse(String::new(), 5), r#"🎭""[5]"#),
+            (Expr::Muse("a)".to_string(), 5), r#"🎭"a)"[5]"#),
+            (Expr::dna(vec![0, 7, 255]), "🧬DNA[0007ff]"),
+        ] {
+            assert_eq!(expr.to_string(), text);
+            assert_eq!(Expr::parse(text), Ok(expr));
+        }
+
+        let err = Expr::parse(r#"(f "open)"#).unwrap_err();
+        assert_eq!(err.span, 3..9);
+    }
+
+    #[test]
+    fn test_parse_builtin_expressions() {
+        for expr in [Expr::y_combinator(), Expr::metameme_combinator(), Expr::solfunmeme()] {
+            assert_eq!(format!("{}", expr).parse::<Expr>(), Ok(expr));
+        }
+    }
+
+    #[test]
+    fn test_parse_ascii_lambda_and_juxtaposition() {
+        let expr = Expr::parse(r"\x.\y.(x y z)").unwrap();
+        let body = Expr::app(Expr::app(Expr::var("x"), Expr::var("y")), Expr::var("z"));
+        assert_eq!(expr, Expr::lambda("x", Expr::lambda("y", body)));
+    }
+
+    #[test]
+    fn test_parse_evolved_muse_name() {
+        let expr = Expr::Muse("Muse+(🌀 🎭Sol[970])".to_string(), 999);
+        assert_eq!(Expr::parse(&format!("({} x)", expr)), Ok(Expr::app(expr, Expr::var("x"))));
+    }
+
+    #[test]
+    fn test_applied_muse_names_round_trip() {
+        // 🎭Sol "a b" → 🎭Sol+"a b", whose name holds a space and brackets
+        let applied = crate::LambdaEngine::new().normalize(Expr::app(Expr::muse("Sol", 1.0), Expr::sym("a b"))).unwrap().final_form;
+        assert!(matches!(&applied, Expr::Muse(name, _) if name == r#"Sol+"a b""#));
+        assert_eq!(Expr::parse(&applied.to_string()), Ok(applied));
+    }
+
+    #[test]
+    fn test_nesting_and_dna_are_bounded() {
+        let deep = format!("{}x{}", "(".repeat(100_000), ")".repeat(100_000));
+        let err = Expr::parse(&deep).unwrap_err();
+        assert_eq!(err.message, format!("nested deeper than {}", MAX_NESTING));
+        assert!(Expr::parse(&"λx.".repeat(100_000)).is_err());
+        assert!(Expr::parse(&format!("{}x{}", "🌀Q(".repeat(100_000), ")".repeat(100_000))).is_err());
+
+        let shallow = format!("{}x{}", "(".repeat(MAX_NESTING), ")".repeat(MAX_NESTING));
+        assert_eq!(Expr::parse(&shallow), Ok(Expr::var("x")));
+
+        // Only the bytes written out are allocated
+        assert_eq!(Expr::parse("🧬DNA[4294967295]"), Ok(Expr::dna(vec![0x42, 0x94, 0x96, 0x72, 0x95])));
+        assert!(Expr::parse("🧬DNA[429496729]").is_err());
+        assert!(Expr::parse("🧬DNA[0").is_err());
+        assert_eq!(Expr::parse("🧬DNA[]"), Ok(Expr::dna(Vec::new())));
+    }
+
+    #[test]
+    fn test_parse_errors_have_spans() {
+        let err = Expr::parse("(S K").unwrap_err();
+        assert_eq!(err.span, 4..4);
+
+        let err = Expr::parse("λ.x").unwrap_err();
+        assert_eq!(err.span, 2..2);
+
+        let err = Expr::parse("S K)").unwrap_err();
+        assert_eq!(err.span, 3..4);
+    }
+}
@@ -0,0 +1,492 @@
+//! # 📚 Prelude: Church Encodings for Poems That Compute
+//!
+//! Standard Church encodings of numerals, booleans, pairs and lists, with the
+//! usual arithmetic, plus decoders that recognize a normal form as one of
+//! these values. An emoji poem built from S, K and I can now be checked:
+//! `🔮💫` is `K I`, which [`decode`] reports as the number 0.
+//!
+//! Lists use the right-fold encoding `λc.λn.c x₁ (c x₂ … n)`. Zero, `false`
+//! and the empty list share the normal form `λx.λy.y`; decoders report it as
+//! the number 0.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{DeBruijn, Expr, LambdaEngine, Termination};
+
+/// Steps allowed when a decoder has to reduce an S/K/I term to lambdas first
+const DECODE_STEPS: usize = 1000;
+
+/// Largest term, in nodes, that reduction may build
+const DECODE_TERM_SIZE: usize = 4096;
+
+fn var(name: &str) -> Expr {
+    Expr::var(name)
+}
+
+/// `λp₁.λp₂.… body`
+fn abs(params: &[&str], body: Expr) -> Expr {
+    params.iter().rev().fold(body, |body, param| Expr::lambda(param, body))
+}
+
+/// `f a₁ a₂ …`
+fn call(f: Expr, args: impl IntoIterator<Item = Expr>) -> Expr {
+    args.into_iter().fold(f, Expr::app)
+}
+
+/// 🔢 The Church numeral `λf.λx.fⁿ x`
+pub fn numeral(n: u64) -> Expr {
+    let body = (0..n).fold(var("x"), |body, _| Expr::app(var("f"), body));
+    abs(&["f", "x"], body)
+}
+
+/// `succ = λn.λf.λx.f (n f x)`
+pub fn succ() -> Expr {
+    abs(&["n", "f", "x"], Expr::app(var("f"), call(var("n"), [var("f"), var("x")])))
+}
+
+/// `add = λm.λn.λf.λx.m f (n f x)`
+pub fn add() -> Expr {
+    abs(&["m", "n", "f", "x"], call(var("m"), [var("f"), call(var("n"), [var("f"), var("x")])]))
+}
+
+/// `mul = λm.λn.λf.m (n f)`
+pub fn mul() -> Expr {
+    abs(&["m", "n", "f"], Expr::app(var("m"), Expr::app(var("n"), var("f"))))
+}
+
+/// `pred = λn.λf.λx.n (λg.λh.h (g f)) (λu.x) (λu.u)`; `pred 0 = 0`
+pub fn pred() -> Expr {
+    let step = abs(&["g", "h"], Expr::app(var("h"), Expr::app(var("g"), var("f"))));
+    abs(
+        &["n", "f", "x"],
+        call(var("n"), [step, Expr::lambda("u", var("x")), Expr::lambda("u", var("u"))]),
+    )
+}
+
+/// `is_zero = λn.n (λx.false) true`
+pub fn is_zero() -> Expr {
+    Expr::lambda("n", call(var("n"), [Expr::lambda("x", boolean(false)), boolean(true)]))
+}
+
+/// ✅ `true = λt.λf.t`, `false = λt.λf.f`
+pub fn boolean(value: bool) -> Expr {
+    abs(&["t", "f"], var(if value { "t" } else { "f" }))
+}
+
+/// `and = λp.λq.p q p`
+pub fn and() -> Expr {
+    abs(&["p", "q"], call(var("p"), [var("q"), var("p")]))
+}
+
+/// `or = λp.λq.p p q`
+pub fn or() -> Expr {
+    abs(&["p", "q"], call(var("p"), [var("p"), var("q")]))
+}
+
+/// `not = λp.λa.λb.p b a`
+pub fn not() -> Expr {
+    abs(&["p", "a", "b"], call(var("p"), [var("b"), var("a")]))
+}
+
+/// 🤝 `pair = λa.λb.λs.s a b`
+pub fn pair() -> Expr {
+    abs(&["a", "b", "s"], call(var("s"), [var("a"), var("b")]))
+}
+
+/// `fst = λp.p true`
+pub fn fst() -> Expr {
+    Expr::lambda("p", Expr::app(var("p"), boolean(true)))
+}
+
+/// `snd = λp.p false`
+pub fn snd() -> Expr {
+    Expr::lambda("p", Expr::app(var("p"), boolean(false)))
+}
+
+/// 📜 `nil = λc.λn.n`
+pub fn nil() -> Expr {
+    abs(&["c", "n"], var("n"))
+}
+
+/// `cons = λh.λt.λc.λn.c h (t c n)`
+pub fn cons() -> Expr {
+    abs(
+        &["h", "t", "c", "n"],
+        call(var("c"), [var("h"), call(var("t"), [var("c"), var("n")])]),
+    )
+}
+
+/// `is_nil = λl.l (λh.λt.false) true`
+pub fn is_nil() -> Expr {
+    Expr::lambda("l", call(var("l"), [abs(&["h", "t"], boolean(false)), boolean(true)]))
+}
+
+/// `head = λl.l (λh.λt.h) nil`; the head of `nil` is `nil`
+pub fn head() -> Expr {
+    Expr::lambda("l", call(var("l"), [abs(&["h", "t"], var("h")), nil()]))
+}
+
+/// `tail = λl.fst (l (λx.λp.pair (snd p) (cons x (snd p))) (pair nil nil))`
+pub fn tail() -> Expr {
+    let snd_p = Expr::app(snd(), var("p"));
+    let step = abs(
+        &["x", "p"],
+        call(pair(), [snd_p.clone(), call(cons(), [var("x"), snd_p])]),
+    );
+    Expr::lambda(
+        "l",
+        Expr::app(fst(), call(var("l"), [step, call(pair(), [nil(), nil()])])),
+    )
+}
+
+/// The Church list of `items`, built directly in normal form
+pub fn list(items: impl IntoIterator<Item = Expr>) -> Expr {
+    let items: Vec<Expr> = items.into_iter().collect();
+    let body = items
+        .into_iter()
+        .rev()
+        .fold(var("n"), |rest, item| call(var("c"), [item, rest]));
+    abs(&["c", "n"], body)
+}
+
+/// 🗂️ Every prelude combinator by name, ready for `LambdaEngine::bind`
+pub fn environment() -> HashMap<String, Expr> {
+    [
+        ("succ", succ()),
+        ("add", add()),
+        ("mul", mul()),
+        ("pred", pred()),
+        ("is_zero", is_zero()),
+        ("true", boolean(true)),
+        ("false", boolean(false)),
+        ("and", and()),
+        ("or", or()),
+        ("not", not()),
+        ("pair", pair()),
+        ("fst", fst()),
+        ("snd", snd()),
+        ("nil", nil()),
+        ("cons", cons()),
+        ("is_nil", is_nil()),
+        ("head", head()),
+        ("tail", tail()),
+    ]
+    .into_iter()
+    .map(|(name, expr)| (name.to_string(), expr))
+    .collect()
+}
+
+impl LambdaEngine {
+    /// Bind every prelude combinator (`succ`, `add`, `pair`, `head`, ...) in the environment
+    pub fn with_prelude(mut self) -> Self {
+        self.environment.extend(environment());
+        self
+    }
+}
+
+/// 🔍 A value recognized in a normal form
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum ChurchValue {
+    Number(u64),
+    Boolean(bool),
+    Pair(Box<ChurchValue>, Box<ChurchValue>),
+    List(Vec<ChurchValue>),
+    /// An element of a pair or list that is not itself an encoded value
+    Term(String),
+}
+
+impl ChurchValue {
+    /// Human-readable description, e.g. "the number 7"
+    pub fn describe(&self) -> String {
+        match self {
+            ChurchValue::Number(_) => format!("the number {}", self),
+            ChurchValue::Boolean(_) => format!("the boolean {}", self),
+            ChurchValue::Pair(..) => format!("the pair {}", self),
+            ChurchValue::List(_) => format!("the list {}", self),
+            ChurchValue::Term(_) => format!("the term {}", self),
+        }
+    }
+}
+
+impl fmt::Display for ChurchValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChurchValue::Number(n) => write!(f, "{}", n),
+            ChurchValue::Boolean(b) => write!(f, "{}", b),
+            ChurchValue::Pair(first, second) => write!(f, "({}, {})", first, second),
+            ChurchValue::List(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            ChurchValue::Term(term) => write!(f, "{}", term),
+        }
+    }
+}
+
+/// 🔍 Recognize `expr` as a numeral, boolean, list or pair
+///
+/// Terms that still mention S, K or I are first expanded into lambdas and
+/// normalized, so combinator poems decode too. Zero is tried first, so the
+/// shared normal form of 0, `false` and `nil` comes back as the number 0.
+pub fn decode(expr: &Expr) -> Option<ChurchValue> {
+    LambdaEngine::seeded(0).decode(expr)
+}
+
+impl LambdaEngine {
+    /// 🔍 `decode`, with the reduction to lambdas also held to this
+    /// engine's step limit and budget, deadline included
+    pub fn decode(&self, expr: &Expr) -> Option<ChurchValue> {
+        let term = to_lambda_normal_form(expr, self)?.to_debruijn();
+        decode_term(&term)
+    }
+}
+
+/// 🔢 Recognize `expr` as a Church numeral; `λf.f` counts as 1 (η-short)
+pub fn decode_numeral(expr: &Expr) -> Option<u64> {
+    numeral_of(&to_lambda_normal_form(expr, &LambdaEngine::seeded(0))?.to_debruijn())
+}
+
+/// ✅ Recognize `expr` as a Church boolean
+pub fn decode_boolean(expr: &Expr) -> Option<bool> {
+    boolean_of(&to_lambda_normal_form(expr, &LambdaEngine::seeded(0))?.to_debruijn())
+}
+
+/// 📜 Recognize `expr` as a Church list; elements are decoded where possible
+pub fn decode_list(expr: &Expr) -> Option<Vec<ChurchValue>> {
+    list_of(&to_lambda_normal_form(expr, &LambdaEngine::seeded(0))?.to_debruijn())
+}
+
+/// 🤝 Recognize `expr` as a Church pair; components are decoded where possible
+pub fn decode_pair(expr: &Expr) -> Option<(ChurchValue, ChurchValue)> {
+    pair_of(&to_lambda_normal_form(expr, &LambdaEngine::seeded(0))?.to_debruijn())
+}
+
+/// Replace S, K and I by their lambda definitions and normalize; `None` when
+/// the expanded term does not reach a normal form within `DECODE_STEPS` and
+/// `DECODE_TERM_SIZE`, or within the limits of `engine`
+///
+/// Reduction runs on a seeded engine with the limits and backend of
+/// `engine`, under normal order and without its environment.
+fn to_lambda_normal_form(expr: &Expr, engine: &LambdaEngine) -> Option<Expr> {
+    fn mentions_combinators(expr: &Expr) -> bool {
+        match expr {
+            Expr::S | Expr::K | Expr::I => true,
+            Expr::Lambda(_, body) | Expr::Quine(body) => mentions_combinators(body),
+            Expr::App(left, right) => mentions_combinators(left) || mentions_combinators(right),
+            _ => false,
+        }
+    }
+
+    fn expand(expr: &Expr) -> Expr {
+        match expr {
+            Expr::S => abs(&["x", "y", "z"], call(var("x"), [var("z"), Expr::app(var("y"), var("z"))])),
+            Expr::K => abs(&["x", "y"], var("x")),
+            Expr::I => Expr::lambda("x", var("x")),
+            Expr::Lambda(param, body) => Expr::lambda(param, expand(body)),
+            Expr::App(left, right) => Expr::app(expand(left), expand(right)),
+            Expr::Quine(inner) => Expr::quine(expand(inner)),
+            other => other.clone(),
+        }
+    }
+
+    if !mentions_combinators(expr) {
+        return Some(expr.clone());
+    }
+    let mut budget = engine.budget;
+    budget.max_term_size = Some(budget.max_term_size.map_or(DECODE_TERM_SIZE, |limit| limit.min(DECODE_TERM_SIZE)));
+    let trace = LambdaEngine::seeded(0)
+        .with_max_steps(engine.max_steps.min(DECODE_STEPS))
+        .with_budget(budget)
+        .with_backend(engine.backend)
+        .normalize(expand(expr))
+        .ok()?;
+    (trace.termination == Termination::NormalForm).then_some(trace.final_form)
+}
+
+fn decode_term(term: &DeBruijn) -> Option<ChurchValue> {
+    if let Some(n) = numeral_of(term) {
+        Some(ChurchValue::Number(n))
+    } else if let Some(b) = boolean_of(term) {
+        Some(ChurchValue::Boolean(b))
+    } else if let Some(items) = list_of(term) {
+        Some(ChurchValue::List(items))
+    } else {
+        pair_of(term).map(|(first, second)| ChurchValue::Pair(Box::new(first), Box::new(second)))
+    }
+}
+
+/// A pair or list element: a value if it encodes one, otherwise its text
+fn element(term: &DeBruijn) -> ChurchValue {
+    decode_term(term).unwrap_or_else(|| ChurchValue::Term(term.to_expr().to_string()))
+}
+
+/// True when no variable in `term` refers past its own binders
+fn is_closed(term: &DeBruijn) -> bool {
+    fn closed_under(term: &DeBruijn, depth: usize) -> bool {
+        match term {
+            DeBruijn::Bound(index) => *index < depth,
+            DeBruijn::Lambda(body) => closed_under(body, depth + 1),
+            DeBruijn::Quine(inner) => closed_under(inner, depth),
+            DeBruijn::App(left, right) => closed_under(left, depth) && closed_under(right, depth),
+            _ => true,
+        }
+    }
+    closed_under(term, 0)
+}
+
+fn numeral_of(term: &DeBruijn) -> Option<u64> {
+    match term {
+        DeBruijn::Lambda(body) => match body.as_ref() {
+            // η-short one: λf.f
+            DeBruijn::Bound(0) => Some(1),
+            DeBruijn::Lambda(body) => {
+                let mut count = 0;
+                let mut current = body.as_ref();
+                loop {
+                    match current {
+                        DeBruijn::Bound(0) => return Some(count),
+                        DeBruijn::App(f, rest) if **f == DeBruijn::Bound(1) => {
+                            count += 1;
+                            current = rest;
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn boolean_of(term: &DeBruijn) -> Option<bool> {
+    match term {
+        DeBruijn::Lambda(body) => match body.as_ref() {
+            DeBruijn::Lambda(body) => match body.as_ref() {
+                DeBruijn::Bound(1) => Some(true),
+                DeBruijn::Bound(0) => Some(false),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn list_of(term: &DeBruijn) -> Option<Vec<ChurchValue>> {
+    let DeBruijn::Lambda(body) = term else { return None };
+    let DeBruijn::Lambda(body) = body.as_ref() else { return None };
+
+    let mut items = Vec::new();
+    let mut current = body.as_ref();
+    loop {
+        match current {
+            DeBruijn::Bound(0) => return Some(items),
+            DeBruijn::App(cons, rest) => match cons.as_ref() {
+                DeBruijn::App(c, item) if **c == DeBruijn::Bound(1) && is_closed(item) => {
+                    items.push(element(item));
+                    current = rest;
+                }
+                _ => return None,
+            },
+            _ => return None,
+        }
+    }
+}
+
+fn pair_of(term: &DeBruijn) -> Option<(ChurchValue, ChurchValue)> {
+    let DeBruijn::Lambda(body) = term else { return None };
+    let DeBruijn::App(selector_first, second) = body.as_ref() else { return None };
+    let DeBruijn::App(selector, first) = selector_first.as_ref() else { return None };
+    (**selector == DeBruijn::Bound(0) && is_closed(first) && is_closed(second))
+        .then(|| (element(first), element(second)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expr: Expr) -> Expr {
+        let trace = LambdaEngine::new().normalize(expr).unwrap();
+        assert_eq!(trace.termination, Termination::NormalForm);
+        trace.final_form
+    }
+
+    #[test]
+    fn test_numeral_arithmetic() {
+        assert_eq!(decode_numeral(&eval(call(add(), [numeral(3), numeral(4)]))), Some(7));
+        assert_eq!(decode_numeral(&eval(call(mul(), [numeral(2), numeral(3)]))), Some(6));
+        assert_eq!(decode_numeral(&eval(Expr::app(succ(), numeral(6)))), Some(7));
+        assert_eq!(decode_numeral(&eval(Expr::app(pred(), numeral(5)))), Some(4));
+        assert_eq!(decode_numeral(&eval(Expr::app(pred(), numeral(0)))), Some(0));
+        assert_eq!(decode(&eval(Expr::app(is_zero(), numeral(0)))), Some(ChurchValue::Boolean(true)));
+        assert_eq!(decode_boolean(&eval(Expr::app(is_zero(), numeral(2)))), Some(false));
+    }
+
+    #[test]
+    fn test_boolean_logic() {
+        let t = boolean(true);
+        let f = boolean(false);
+        assert_eq!(decode_boolean(&eval(call(and(), [t.clone(), f.clone()]))), Some(false));
+        assert_eq!(decode_boolean(&eval(call(or(), [f.clone(), t.clone()]))), Some(true));
+        assert_eq!(decode_boolean(&eval(Expr::app(not(), t))), Some(false));
+    }
+
+    #[test]
+    fn test_pairs_and_lists() {
+        let p = call(pair(), [numeral(1), boolean(true)]);
+        assert_eq!(decode_numeral(&eval(Expr::app(fst(), p.clone()))), Some(1));
+        assert_eq!(
+            decode(&eval(p)),
+            Some(ChurchValue::Pair(Box::new(ChurchValue::Number(1)), Box::new(ChurchValue::Boolean(true))))
+        );
+
+        let xs = call(cons(), [numeral(1), call(cons(), [numeral(2), call(cons(), [numeral(3), nil()])])]);
+        let xs = eval(xs);
+        assert!(xs.alpha_eq(&list([numeral(1), numeral(2), numeral(3)])));
+        assert_eq!(decode(&xs).map(|value| value.to_string()), Some("[1, 2, 3]".to_string()));
+        assert_eq!(decode_numeral(&eval(Expr::app(head(), xs.clone()))), Some(1));
+        assert_eq!(
+            decode_list(&eval(Expr::app(tail(), xs.clone()))),
+            Some(vec![ChurchValue::Number(2), ChurchValue::Number(3)])
+        );
+        assert_eq!(decode_boolean(&eval(Expr::app(is_nil(), xs))), Some(false));
+    }
+
+    #[test]
+    fn test_decode_combinator_poems() {
+        // K I is zero, S K K is the identity, which is η-equal to one
+        assert_eq!(decode(&Expr::app(Expr::K, Expr::I)), Some(ChurchValue::Number(0)));
+        assert_eq!(decode_numeral(&Expr::app(Expr::app(Expr::S, Expr::K), Expr::K)), Some(1));
+        assert_eq!(decode(&Expr::app(Expr::K, Expr::K)), None);
+        assert_eq!(decode(&Expr::sym("🌀")), None);
+    }
+
+    #[test]
+    fn test_decoding_is_bounded() {
+        // I (3 3 3) = 3^27 would blow far past the decoder's size limit
+        let tower = Expr::app(Expr::I, call(numeral(3), [numeral(3), numeral(3)]));
+        assert_eq!(decode(&tower), None);
+
+        // The caller's budget applies too: S K K needs more than one step
+        let skk = Expr::app(Expr::app(Expr::S, Expr::K), Expr::K);
+        let tight = LambdaEngine::new().with_budget(crate::EvalBudget::unlimited().with_max_steps(1));
+        assert_eq!(tight.decode(&skk), None);
+        assert_eq!(LambdaEngine::new().decode(&skk), Some(ChurchValue::Number(1)));
+        let machine = LambdaEngine::new().with_strategy(crate::Strategy::WeakHead).with_backend(crate::Backend::Machine);
+        assert_eq!(machine.decode(&skk), Some(ChurchValue::Number(1)));
+    }
+
+    #[test]
+    fn test_prelude_environment_evaluates_by_name() {
+        let mut engine = LambdaEngine::new().with_prelude();
+        let expr = call(var("add"), [numeral(2), Expr::app(var("succ"), numeral(4))]);
+        let value = decode(&engine.normalize(expr).unwrap().final_form).unwrap();
+        assert_eq!(value.describe(), "the number 7");
+        assert_eq!(serde_json::to_value(&value).unwrap(), serde_json::json!({"kind": "number", "value": 7}));
+    }
+}
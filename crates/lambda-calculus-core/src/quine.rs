@@ -0,0 +1,215 @@
+//! # 🪞 Quine Verification: Does a Term Really Reproduce Itself?
+//!
+//! A term is a quine when reducing it gives the term back. This module
+//! decides that over real [`Expr`] values rather than rendered strings: it
+//! reduces the term within the engine's step limit and budget and looks for
+//! a later term that is alpha-equivalent to the original, or that is the
+//! original's own canonical emoji code (`Sym(expr.to_emoji_code())`).
+//!
+//! - **Exact** — the term reduces to itself (or its encoding) in one step,
+//!   like `(λx.x x) (λx.x x)`
+//! - **Cyclic** — the term comes back after `n > 1` steps, passing through
+//!   other terms on the way
+//! - **Not a quine** — anything else, including terms that fall into a loop
+//!   the original is not part of
+//!
+//! Every verdict comes with the reduction that proves it.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Backend, BudgetKind, EvalError, Expr, LambdaEngine, ReductionStep, ReductionTrace, Termination};
+
+/// 🔎 What the reproduced copy was
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuineWitness {
+    /// A term alpha-equivalent to the original
+    AlphaEquivalent,
+    /// The original's canonical emoji code as a symbol
+    Encoding,
+}
+
+/// ❔ Why a term is not a quine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NotQuineReason {
+    /// Reduction ended without the original coming back
+    Reduced { termination: Termination },
+    /// A budget limit stopped reduction first
+    Budget { limit: BudgetKind },
+}
+
+/// ⚖️ Classification of a term under quine verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QuineVerdict {
+    /// Reproduces itself in a single step
+    Exact { witness: QuineWitness },
+    /// Reproduces itself after `period` steps
+    Cyclic { period: usize, witness: QuineWitness },
+    NotQuine { reason: NotQuineReason },
+}
+
+impl QuineVerdict {
+    /// True for exact and cyclic quines
+    pub fn is_quine(&self) -> bool {
+        !matches!(self, QuineVerdict::NotQuine { .. })
+    }
+
+    /// Steps until the term reproduces itself, if it does
+    pub fn period(&self) -> Option<usize> {
+        match self {
+            QuineVerdict::Exact { .. } => Some(1),
+            QuineVerdict::Cyclic { period, .. } => Some(*period),
+            QuineVerdict::NotQuine { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for QuineVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let via = |witness: &QuineWitness| match witness {
+            QuineWitness::AlphaEquivalent => "",
+            QuineWitness::Encoding => " (via its emoji code)",
+        };
+        match self {
+            QuineVerdict::Exact { witness } => write!(f, "exact quine{}", via(witness)),
+            QuineVerdict::Cyclic { period, witness } => write!(f, "cyclic quine of period {}{}", period, via(witness)),
+            QuineVerdict::NotQuine { reason: NotQuineReason::Reduced { termination } } => {
+                write!(f, "not a quine ({})", termination)
+            }
+            QuineVerdict::NotQuine { reason: NotQuineReason::Budget { limit } } => {
+                write!(f, "not a quine within budget ({} limit)", limit)
+            }
+        }
+    }
+}
+
+/// 📜 A verdict and the reduction that justifies it
+#[derive(Debug, Clone)]
+pub struct QuineReport {
+    pub verdict: QuineVerdict,
+    /// Terms from the original up to the reproduced copy, or to where
+    /// reduction stopped; just the original when a budget limit cut it short
+    pub terms: Vec<Expr>,
+    /// The contractions between consecutive `terms`; empty when a budget
+    /// limit cut reduction short
+    pub steps: Vec<ReductionStep>,
+}
+
+/// ⚖️ Classify `original` from a reduction of it that has already run
+///
+/// `trace` must come from normalizing `original` with the substitution
+/// backend: cycle detection already stops the first time a term comes back,
+/// so `original` reappeared exactly when the cycle starts at step 0.
+pub fn classify(original: &Expr, trace: &ReductionTrace) -> QuineVerdict {
+    let reproduced = match trace.termination {
+        Termination::Cycle { period, first_seen_step: 0 } => Some((period, QuineWitness::AlphaEquivalent)),
+        Termination::NormalForm
+            if trace.step_count > 0
+                && matches!(&trace.final_form, Expr::Sym(symbol) if *symbol == original.to_emoji_code()) =>
+        {
+            Some((trace.step_count, QuineWitness::Encoding))
+        }
+        _ => None,
+    };
+    match reproduced {
+        Some((1, witness)) => QuineVerdict::Exact { witness },
+        Some((period, witness)) => QuineVerdict::Cyclic { period, witness },
+        None => QuineVerdict::NotQuine { reason: NotQuineReason::Reduced { termination: trace.termination } },
+    }
+}
+
+impl LambdaEngine {
+    /// 🪞 Reduce `expr` within this engine's limits and decide whether it is a quine
+    ///
+    /// Verification always uses the substitution backend, since it needs
+    /// every intermediate term. Crossing a budget limit is a verdict, not an
+    /// error.
+    pub fn verify_quine(&self, expr: &Expr) -> Result<QuineReport, EvalError> {
+        let mut engine = self.clone().with_backend(Backend::Substitution);
+        let trace = match engine.try_normalize(expr.clone()) {
+            Ok(trace) => trace,
+            Err(EvalError::BudgetExceeded { kind, .. }) => {
+                let verdict = QuineVerdict::NotQuine { reason: NotQuineReason::Budget { limit: kind } };
+                return Ok(QuineReport { verdict, terms: vec![expr.clone()], steps: Vec::new() });
+            }
+        };
+
+        let verdict = classify(expr, &trace);
+        let (mut terms, mut steps) = (trace.terms(), trace.steps());
+        if let Some(period) = verdict.period() {
+            terms.truncate(period + 1);
+            steps.truncate(period);
+        }
+        Ok(QuineReport { verdict, terms, steps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn omega_half(body: Expr) -> Expr {
+        Expr::lambda("x", body)
+    }
+
+    fn self_app() -> Expr {
+        Expr::app(Expr::var("x"), Expr::var("x"))
+    }
+
+    #[test]
+    fn test_omega_is_an_exact_quine_with_proof() {
+        let half = omega_half(self_app());
+        let omega = Expr::app(half.clone(), half);
+        let report = LambdaEngine::new().verify_quine(&omega).unwrap();
+
+        assert_eq!(report.verdict, QuineVerdict::Exact { witness: QuineWitness::AlphaEquivalent });
+        assert_eq!(report.terms.len(), 2);
+        assert_eq!(report.steps.len(), 1);
+        assert!(report.terms[1].alpha_eq(&omega));
+    }
+
+    #[test]
+    fn test_cyclic_and_eventually_cyclic_terms() {
+        // M M with M = λx.I (x x): M M → I (M M) → M M
+        let half = omega_half(Expr::app(Expr::I, self_app()));
+        let report = LambdaEngine::new().verify_quine(&Expr::app(half.clone(), half)).unwrap();
+        assert_eq!(report.verdict, QuineVerdict::Cyclic { period: 2, witness: QuineWitness::AlphaEquivalent });
+        assert_eq!(report.verdict.period(), Some(2));
+
+        // I Ω loops forever, but never back to I Ω itself
+        let omega = Expr::app(omega_half(self_app()), omega_half(self_app()));
+        let report = LambdaEngine::new().verify_quine(&Expr::app(Expr::I, omega)).unwrap();
+        assert_eq!(
+            report.verdict,
+            QuineVerdict::NotQuine {
+                reason: NotQuineReason::Reduced { termination: Termination::Cycle { period: 1, first_seen_step: 1 } }
+            }
+        );
+    }
+
+    #[test]
+    fn test_reducing_to_own_encoding_counts() {
+        let mut engine = LambdaEngine::new();
+        let quote = Expr::var("quote");
+        engine.bind("quote", Expr::Sym(quote.to_emoji_code()));
+
+        let report = engine.verify_quine(&quote).unwrap();
+        assert_eq!(report.verdict, QuineVerdict::Exact { witness: QuineWitness::Encoding });
+    }
+
+    #[test]
+    fn test_normal_forms_and_budgets_are_not_quines() {
+        let report = LambdaEngine::new().verify_quine(&Expr::app(Expr::I, Expr::K)).unwrap();
+        assert!(!report.verdict.is_quine());
+        assert_eq!(report.terms, vec![Expr::app(Expr::I, Expr::K), Expr::K]);
+
+        let growing = Expr::app(Expr::y_combinator(), Expr::sym("f"));
+        let engine = LambdaEngine::new().with_budget(crate::EvalBudget::unlimited().with_max_steps(5));
+        let report = engine.verify_quine(&growing).unwrap();
+        assert_eq!(report.verdict, QuineVerdict::NotQuine { reason: NotQuineReason::Budget { limit: BudgetKind::Steps } });
+    }
+}
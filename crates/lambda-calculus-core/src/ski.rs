@@ -0,0 +1,176 @@
+//! # 🔮 Bracket Abstraction: Lambda Terms as Pure Combinators
+//!
+//! [`Expr::to_ski`] removes every lambda by bracket abstraction, leaving a
+//! term built only from `S`, `K`, `I`, applications and whatever constants
+//! (symbols, muses, DNA, free variables) the original mentioned. A closed
+//! term without constants becomes a pure `🌀🔮💫` poem.
+//!
+//! `[x]M` is compiled with Turner's optimizations, using `B` and `C` (spelled
+//! out in `S` and `K`) so a variable is only routed to the side that uses it:
+//!
+//! - `[x] x = I`
+//! - `[x] M = K M` when `x` is not free in `M`
+//! - `[x] M x = M` when `x` is not free in `M` (η)
+//! - `[x] M N = B M ([x] N)` when `x` is only free in `N`
+//! - `[x] M N = C ([x] M) N` when `x` is only free in `M`
+//! - `[x] M N = S ([x] M) ([x] N)` otherwise
+//!
+//! [`Expr::from_ski`] goes back by replacing each combinator with its lambda
+//! definition. Both directions preserve meaning up to η: applied to the same
+//! arguments, the original and the translation reduce to the same result.
+
+use crate::Expr;
+
+/// `B = S (K S) K`, composition: `B f g x = f (g x)`
+fn b() -> Expr {
+    Expr::metameme_combinator()
+}
+
+/// `C = S (B B S) (K K)`, flip: `C f x y = f y x`
+fn c() -> Expr {
+    let bbs = Expr::app(Expr::app(b(), b()), Expr::S);
+    Expr::app(Expr::app(Expr::S, bbs), Expr::app(Expr::K, Expr::K))
+}
+
+/// True when `var` occurs in a combinator term; there are no binders left
+/// to shadow it
+fn mentions(expr: &Expr, var: &str) -> bool {
+    match expr {
+        Expr::Var(name) => name == var,
+        Expr::App(left, right) => mentions(left, var) || mentions(right, var),
+        Expr::Quine(inner) => mentions(inner, var),
+        _ => false,
+    }
+}
+
+/// `[var] expr` for a term that is already free of lambdas
+fn abstract_var(var: &str, expr: &Expr) -> Expr {
+    if !mentions(expr, var) {
+        return Expr::app(Expr::K, expr.clone());
+    }
+    match expr {
+        Expr::Var(_) => Expr::I,
+        Expr::App(left, right) => match (mentions(left, var), right.as_ref()) {
+            (false, Expr::Var(name)) if name == var => (**left).clone(),
+            (false, _) => Expr::app(Expr::app(b(), (**left).clone()), abstract_var(var, right)),
+            (true, _) if !mentions(right, var) => Expr::app(Expr::app(c(), abstract_var(var, left)), (**right).clone()),
+            (true, _) => Expr::app(Expr::app(Expr::S, abstract_var(var, left)), abstract_var(var, right)),
+        },
+        // A quine over the variable unwraps first, as `QuineUnwrap` would
+        Expr::Quine(inner) => abstract_var(var, inner),
+        _ => unreachable!("only variables, applications and quines mention a variable"),
+    }
+}
+
+impl Expr {
+    /// 🔮 Compile away every lambda by optimized bracket abstraction
+    ///
+    /// Quines are compiled in place, except that one capturing a bound
+    /// variable is unwrapped, since a combinator cannot reach inside it.
+    pub fn to_ski(&self) -> Expr {
+        match self {
+            Expr::Lambda(param, body) => abstract_var(param, &body.to_ski()),
+            Expr::App(left, right) => Expr::app(left.to_ski(), right.to_ski()),
+            Expr::Quine(inner) => Expr::quine(inner.to_ski()),
+            other => other.clone(),
+        }
+    }
+
+    /// 🪄 Replace each `S`, `K` and `I` in `ski` with its lambda definition
+    ///
+    /// The result has no combinators left; normalize it to read the term
+    /// the combinators compute.
+    pub fn from_ski(ski: &Expr) -> Expr {
+        let (x, y, z) = (Expr::var("x"), Expr::var("y"), Expr::var("z"));
+        match ski {
+            // λx.λy.λz.x z (y z)
+            Expr::S => Expr::lambda(
+                "x",
+                Expr::lambda(
+                    "y",
+                    Expr::lambda("z", Expr::app(Expr::app(x, z.clone()), Expr::app(y, z))),
+                ),
+            ),
+            // λx.λy.x
+            Expr::K => Expr::lambda("x", Expr::lambda("y", x)),
+            // λx.x
+            Expr::I => Expr::lambda("x", x),
+            Expr::App(left, right) => Expr::app(Expr::from_ski(left), Expr::from_ski(right)),
+            Expr::Lambda(param, body) => Expr::lambda(param, Expr::from_ski(body)),
+            Expr::Quine(inner) => Expr::quine(Expr::from_ski(inner)),
+            other => other.clone(),
+        }
+    }
+
+    /// True when the term contains no lambdas and no variables
+    pub fn is_pure_ski(&self) -> bool {
+        match self {
+            Expr::S | Expr::K | Expr::I => true,
+            Expr::App(left, right) => left.is_pure_ski() && right.is_pure_ski(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude, LambdaEngine};
+
+    fn apply(head: Expr, args: &[Expr]) -> Expr {
+        args.iter().cloned().fold(head, Expr::app)
+    }
+
+    fn normal_form(expr: Expr) -> Expr {
+        let trace = LambdaEngine::new().normalize(expr).unwrap();
+        assert!(trace.is_normal_form);
+        trace.final_form
+    }
+
+    #[test]
+    fn test_abstraction_rules() {
+        let identity = Expr::lambda("x", Expr::var("x"));
+        assert_eq!(identity.to_ski(), Expr::I);
+
+        let konst = Expr::lambda("x", Expr::lambda("y", Expr::var("x")));
+        // [x] K x = K by η
+        assert_eq!(konst.to_ski(), Expr::K);
+
+        // λf.λx.f x is η-equivalent to the identity
+        let apply_fn = Expr::lambda("f", Expr::lambda("x", Expr::app(Expr::var("f"), Expr::var("x"))));
+        assert_eq!(apply_fn.to_ski(), Expr::I);
+
+        // λx.🌀 x is the symbol itself, and constants stay as they are
+        assert_eq!(Expr::lambda("x", Expr::app(Expr::sym("🌀"), Expr::var("x"))).to_ski(), Expr::sym("🌀"));
+        assert_eq!(Expr::lambda("x", Expr::sym("🌀")).to_ski(), Expr::app(Expr::K, Expr::sym("🌀")));
+    }
+
+    #[test]
+    fn test_combinator_and_lambda_forms_compute_the_same() {
+        let (f, x) = (Expr::sym("f"), Expr::sym("x"));
+        let terms = [
+            apply(prelude::add(), &[prelude::numeral(2), prelude::numeral(3)]),
+            apply(prelude::mul(), &[prelude::numeral(2), prelude::numeral(3)]),
+            Expr::lambda("a", Expr::lambda("b", Expr::app(Expr::var("b"), Expr::var("a")))),
+        ];
+        for term in terms {
+            let ski = term.to_ski();
+            assert!(ski.is_pure_ski(), "{}", ski);
+            let expected = normal_form(apply(term.clone(), &[f.clone(), x.clone()]));
+            assert_eq!(normal_form(apply(ski.clone(), &[f.clone(), x.clone()])), expected, "{}", term);
+            assert_eq!(normal_form(apply(Expr::from_ski(&ski), &[f.clone(), x.clone()])), expected, "{}", term);
+        }
+    }
+
+    #[test]
+    fn test_y_combinator_compiles_to_pure_ski() {
+        let y = Expr::y_combinator().to_ski();
+        assert!(y.is_pure_ski());
+
+        // Y (K 🌀) = K 🌀 (Y (K 🌀)) = 🌀, in both forms
+        let stop = Expr::app(Expr::K, Expr::sym("🌀"));
+        assert_eq!(normal_form(Expr::app(Expr::y_combinator(), stop.clone())), Expr::sym("🌀"));
+        assert_eq!(normal_form(Expr::app(y.clone(), stop.clone())), Expr::sym("🌀"));
+        assert_eq!(normal_form(Expr::app(Expr::from_ski(&y), stop)), Expr::sym("🌀"));
+    }
+}
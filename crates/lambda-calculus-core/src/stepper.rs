@@ -0,0 +1,485 @@
+//! # 🐞 Step Debugger: Reduce a Term One Redex at a Time
+//!
+//! A [`Stepper`] holds a term and its history. [`Stepper::step`] contracts
+//! one redex, [`Stepper::step_back`] rewinds to the term before it, and
+//! [`Stepper::run_until`] keeps stepping until a predicate or a
+//! [`Breakpoint`] fires or the term reaches its normal form.
+//!
+//! Steps honour the engine's [`EvalBudget`](crate::EvalBudget): a step
+//! that would pass its step count, term size or depth is not taken, and
+//! `run_until` also stops at its deadline. Only the last
+//! [`HISTORY_LIMIT`] terms are kept for rewinding, so a long run does not
+//! hold every term it went through.
+//!
+//! Redexes are chosen by the engine's strategy unless a chooser is set.
+//! A chooser sees every redex in the term, leftmost-outermost first, and
+//! picks the one to contract, so a user or a test can walk any reduction
+//! path, not just the ones the built-in strategies take.
+//!
+//! ```text
+//! :break K      stop after a K step
+//! :break 🐱     stop when the symbol 🐱 appears
+//! :break 🎭Love stop when the muse Love appears
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Instant;
+
+use thiserror::Error;
+
+use crate::arena::Reducer;
+use crate::trace::PathStep;
+use crate::{BudgetKind, Expr, LambdaEngine, ReductionStep, Rule};
+
+/// Terms a `Stepper` keeps for rewinding unless told otherwise
+pub const HISTORY_LIMIT: usize = 1024;
+
+/// 🔴 A condition that pauses `Stepper::run_until`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// A step contracted a redex with this rule
+    Rule(Rule),
+    /// This symbol appeared in the term
+    Sym(String),
+    /// A muse with this name appeared in the term; an empty name matches any muse
+    Muse(String),
+}
+
+impl Breakpoint {
+    /// True when the step from `before` to `after` trips this breakpoint
+    pub fn hit(&self, before: &Expr, after: &Expr, step: &ReductionStep) -> bool {
+        match self {
+            Breakpoint::Rule(rule) => step.rule == *rule,
+            Breakpoint::Sym(_) | Breakpoint::Muse(_) => self.occurs_in(after) && !self.occurs_in(before),
+        }
+    }
+
+    fn occurs_in(&self, expr: &Expr) -> bool {
+        match (self, expr) {
+            (Breakpoint::Sym(wanted), Expr::Sym(symbol)) => symbol == wanted,
+            (Breakpoint::Muse(wanted), Expr::Muse(name, _)) => wanted.is_empty() || name == wanted,
+            (_, Expr::App(left, right)) => self.occurs_in(left) || self.occurs_in(right),
+            (_, Expr::Lambda(_, body) | Expr::Quine(body)) => self.occurs_in(body),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Breakpoint::Rule(rule) => write!(f, "{} step", rule),
+            Breakpoint::Sym(symbol) => write!(f, "{} appears", symbol),
+            Breakpoint::Muse(name) if name.is_empty() => write!(f, "any muse appears"),
+            Breakpoint::Muse(name) => write!(f, "🎭{} appears", name),
+        }
+    }
+}
+
+/// ❌ Text that `Breakpoint::from_str` cannot read
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("invalid breakpoint `{0}` (expected a rule name, a symbol or 🎭 and a muse name)")]
+pub struct InvalidBreakpoint(pub String);
+
+impl FromStr for Breakpoint {
+    type Err = InvalidBreakpoint;
+
+    /// Rule names as `Rule` displays them (plus `beta`) come first, then
+    /// `🎭name` for a muse, and anything else is a symbol
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || s.contains(char::is_whitespace) {
+            return Err(InvalidBreakpoint(s.to_string()));
+        }
+        let rule = match s.to_ascii_lowercase().as_str() {
+            "β" | "beta" => Some(Rule::Beta),
+            "s" => Some(Rule::S),
+            "k" => Some(Rule::K),
+            "i" => Some(Rule::I),
+            "muse-evolve" => Some(Rule::MuseEvolve),
+            "muse-apply" => Some(Rule::MuseApply),
+            "quine-unwrap" => Some(Rule::QuineUnwrap),
+            "quine-apply" => Some(Rule::QuineApply),
+            "env-lookup" => Some(Rule::EnvLookup),
+            _ => None,
+        };
+        Ok(match (rule, s.strip_prefix('🎭')) {
+            (Some(rule), _) => Breakpoint::Rule(rule),
+            (None, Some(name)) => Breakpoint::Muse(name.to_string()),
+            (None, None) => Breakpoint::Sym(s.to_string()),
+        })
+    }
+}
+
+/// 🎯 A redex the chooser may pick
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedexChoice {
+    /// Path from the root of the term to the redex
+    pub path: Vec<PathStep>,
+    /// Rule that would contract it
+    pub rule: Rule,
+    /// The redex itself
+    pub redex: Expr,
+}
+
+/// 🎯 Picks which redex to contract: an index into the candidates, or
+/// `None` to leave the choice to the engine's strategy
+pub type RedexChooser = Box<dyn FnMut(&Expr, &[RedexChoice]) -> Option<usize>>;
+
+/// 🏁 Why `Stepper::run_until` stopped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// The predicate returned true after a step
+    Predicate,
+    /// A breakpoint fired after a step
+    Breakpoint(Breakpoint),
+    /// No redex left to contract
+    NormalForm,
+    /// `max_steps` steps were taken without stopping
+    StepLimit,
+    /// The engine's budget ran out; the step that would break it was not taken
+    Budget(BudgetKind),
+}
+
+impl fmt::Display for RunOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunOutcome::Predicate => write!(f, "condition met"),
+            RunOutcome::Breakpoint(breakpoint) => write!(f, "breakpoint: {}", breakpoint),
+            RunOutcome::NormalForm => write!(f, "normal form"),
+            RunOutcome::StepLimit => write!(f, "step limit reached"),
+            RunOutcome::Budget(kind) => write!(f, "{} budget exceeded", kind),
+        }
+    }
+}
+
+/// 🐞 Interactive reduction of one term, with rewind
+pub struct Stepper {
+    engine: LambdaEngine,
+    /// The oldest remembered term first, then the term after each later step
+    history: Vec<Expr>,
+    steps: Vec<ReductionStep>,
+    /// Steps dropped from the front of `history` to stay within `history_limit`
+    forgotten: usize,
+    history_limit: usize,
+    breakpoints: Vec<Breakpoint>,
+    chooser: Option<RedexChooser>,
+}
+
+impl Stepper {
+    /// Start debugging `expr` under `engine`'s environment, strategy, step
+    /// limit and budget
+    pub fn new(engine: LambdaEngine, expr: Expr) -> Self {
+        Self {
+            engine,
+            history: vec![expr],
+            steps: Vec::new(),
+            forgotten: 0,
+            history_limit: HISTORY_LIMIT,
+            breakpoints: Vec::new(),
+            chooser: None,
+        }
+    }
+
+    /// Keep only the last `limit` terms for rewinding; at least two, so the
+    /// last step is always at hand
+    pub fn with_history_limit(mut self, limit: usize) -> Self {
+        self.history_limit = limit.max(2);
+        self.trim_history();
+        self
+    }
+
+    /// Choose redexes with `chooser` instead of the engine's strategy
+    pub fn with_chooser(mut self, chooser: impl FnMut(&Expr, &[RedexChoice]) -> Option<usize> + 'static) -> Self {
+        self.set_chooser(chooser);
+        self
+    }
+
+    pub fn set_chooser(&mut self, chooser: impl FnMut(&Expr, &[RedexChoice]) -> Option<usize> + 'static) {
+        self.chooser = Some(Box::new(chooser));
+    }
+
+    /// Go back to choosing redexes by the engine's strategy
+    pub fn clear_chooser(&mut self) {
+        self.chooser = None;
+    }
+
+    /// The term as it stands now
+    pub fn current(&self) -> &Expr {
+        self.history.last().expect("history always holds the original term")
+    }
+
+    /// Steps taken since the original term, after any rewinds
+    pub fn position(&self) -> usize {
+        self.forgotten + self.steps.len()
+    }
+
+    /// The oldest remembered term first, then the term after each later
+    /// step; the oldest is the original term until more than the history
+    /// limit of terms have been seen
+    pub fn history(&self) -> &[Expr] {
+        &self.history
+    }
+
+    /// The contractions between consecutive `history` terms
+    pub fn steps(&self) -> &[ReductionStep] {
+        &self.steps
+    }
+
+    pub fn engine(&self) -> &LambdaEngine {
+        &self.engine
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// 🎯 Every redex in the current term, leftmost-outermost first
+    pub fn candidates(&self) -> Vec<RedexChoice> {
+        let current = self.current();
+        let mut reducer = Reducer::new(&self.engine);
+        let id = reducer.arena.from_expr(current);
+        reducer
+            .redexes(id)
+            .into_iter()
+            .filter_map(|(path, rule)| {
+                let redex = current.subterm_at(&path)?.clone();
+                Some(RedexChoice { path, rule, redex })
+            })
+            .collect()
+    }
+
+    /// ⏭️ Contract one redex, or return `None` at a normal form
+    ///
+    /// A step that would pass the budget's step count, term size or depth
+    /// is not taken; the limit it would break is the error.
+    pub fn step(&mut self) -> Result<Option<&ReductionStep>, BudgetKind> {
+        let chosen = self.choose();
+        let current = self.current();
+        let mut reducer = Reducer::new(&self.engine);
+        let id = reducer.arena.from_expr(current);
+        let contracted = match chosen {
+            Some(choice) => reducer.contract_at(id, &choice.path),
+            None => reducer.step(id, self.engine.strategy),
+        };
+        let Some((reduced, redex)) = contracted else {
+            return Ok(None);
+        };
+
+        let budget = &self.engine.budget;
+        if budget.max_steps == Some(self.position()) {
+            return Err(BudgetKind::Steps);
+        }
+        if let Some(kind) = budget.check_shape(reducer.arena.size(reduced), reducer.arena.depth(reduced)) {
+            return Err(kind);
+        }
+
+        let reduced = reducer.arena.to_expr(reduced);
+        self.steps.push(ReductionStep::between(redex.rule, redex.path.to_vec(), current, &reduced));
+        self.history.push(reduced);
+        self.trim_history();
+        Ok(self.steps.last())
+    }
+
+    /// Forget the oldest terms beyond the history limit
+    fn trim_history(&mut self) {
+        let excess = self.history.len().saturating_sub(self.history_limit);
+        if excess > 0 {
+            self.history.drain(..excess);
+            self.steps.drain(..excess);
+            self.forgotten += excess;
+        }
+    }
+
+    /// ⏮️ Undo the last step, returning it, or `None` at the oldest
+    /// remembered term
+    pub fn step_back(&mut self) -> Option<ReductionStep> {
+        let step = self.steps.pop()?;
+        self.history.pop();
+        Some(step)
+    }
+
+    /// ▶️ Step until `predicate` holds for the new term and its step, a
+    /// breakpoint fires, the term is normal or the budget runs out
+    ///
+    /// At most `max_steps` steps are taken per call, so a divergent term
+    /// returns control instead of running forever. The budget's deadline
+    /// applies to each call.
+    pub fn run_until(&mut self, mut predicate: impl FnMut(&Expr, &ReductionStep) -> bool) -> RunOutcome {
+        let started = Instant::now();
+        for _ in 0..self.engine.max_steps {
+            if self.engine.budget.deadline.is_some_and(|deadline| started.elapsed() >= deadline) {
+                return RunOutcome::Budget(BudgetKind::Deadline);
+            }
+            let before = self.current().clone();
+            match self.step() {
+                Ok(Some(_)) => {}
+                Ok(None) => return RunOutcome::NormalForm,
+                Err(kind) => return RunOutcome::Budget(kind),
+            }
+            let after = self.current();
+            let step = self.steps.last().expect("a step was just taken");
+            if let Some(breakpoint) = self.breakpoints.iter().find(|breakpoint| breakpoint.hit(&before, after, step)) {
+                return RunOutcome::Breakpoint(breakpoint.clone());
+            }
+            if predicate(after, step) {
+                return RunOutcome::Predicate;
+            }
+        }
+        RunOutcome::StepLimit
+    }
+
+    /// ▶️ Step until a breakpoint fires or the term is normal
+    pub fn run(&mut self) -> RunOutcome {
+        self.run_until(|_, _| false)
+    }
+
+    /// The redex the chooser picks, if a chooser is set and picks one in range
+    fn choose(&mut self) -> Option<RedexChoice> {
+        self.chooser.as_ref()?;
+        let candidates = self.candidates();
+        let chooser = self.chooser.as_mut()?;
+        let index = chooser(self.history.last()?, &candidates)?;
+        candidates.into_iter().nth(index)
+    }
+}
+
+impl LambdaEngine {
+    /// 🐞 Debug the reduction of `expr` step by step with a copy of this engine
+    pub fn stepper(&self, expr: Expr) -> Stepper {
+        Stepper::new(self.clone(), expr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::EvalBudget;
+
+    /// `K (I a) (I b)` under normal order: K fires first, then `I a`
+    fn k_of_identities() -> Expr {
+        Expr::app(
+            Expr::app(Expr::K, Expr::app(Expr::I, Expr::sym("a"))),
+            Expr::app(Expr::I, Expr::sym("b")),
+        )
+    }
+
+    #[test]
+    fn test_step_and_step_back_round_trip() {
+        let expr = k_of_identities();
+        let mut stepper = LambdaEngine::new().stepper(expr.clone());
+
+        assert_eq!(stepper.step().unwrap().map(|step| step.rule), Some(Rule::K));
+        assert_eq!(stepper.step().unwrap().map(|step| step.rule), Some(Rule::I));
+        assert_eq!(stepper.current(), &Expr::sym("a"));
+        assert_eq!(stepper.step(), Ok(None));
+        assert_eq!(stepper.position(), 2);
+
+        assert_eq!(stepper.step_back().map(|step| step.rule), Some(Rule::I));
+        assert_eq!(stepper.step_back().map(|step| step.rule), Some(Rule::K));
+        assert!(stepper.step_back().is_none());
+        assert_eq!(stepper.current(), &expr);
+        assert_eq!(stepper.history().len(), 1);
+    }
+
+    #[test]
+    fn test_chooser_picks_any_redex() {
+        let expr = k_of_identities();
+        let mut stepper = LambdaEngine::new().stepper(expr.clone());
+        let rules: Vec<Rule> = stepper.candidates().iter().map(|choice| choice.rule).collect();
+        assert_eq!(rules, vec![Rule::K, Rule::I, Rule::I]);
+
+        // Always the last, innermost-rightmost redex: I b fires first
+        stepper.set_chooser(|_, candidates| candidates.len().checked_sub(1));
+        let step = stepper.step().unwrap().unwrap().clone();
+        assert_eq!(step.rule, Rule::I);
+        assert_eq!(step.path, vec![PathStep::Right]);
+        assert_eq!(expr.subterm_at(&step.path).map(|e| e.to_string()), Some(step.before));
+        assert_eq!(stepper.run(), RunOutcome::NormalForm);
+        assert_eq!(stepper.current(), &Expr::sym("a"));
+        assert_eq!(stepper.position(), 3);
+    }
+
+    #[test]
+    fn test_breakpoints_and_predicates_stop_a_run() {
+        let mut engine = LambdaEngine::new();
+        engine.bind("cat", Expr::sym("🐱"));
+        // I (K cat 🎭Love): I, then K, then the lookup that makes 🐱 appear
+        let expr = Expr::app(Expr::I, Expr::app(Expr::app(Expr::K, Expr::var("cat")), Expr::muse("Love", 1.0)));
+
+        let mut stepper = engine.stepper(expr);
+        stepper.add_breakpoint("K".parse().unwrap());
+        stepper.add_breakpoint("🐱".parse().unwrap());
+        assert_eq!(stepper.run(), RunOutcome::Breakpoint(Breakpoint::Rule(Rule::K)));
+        assert_eq!(stepper.position(), 2);
+        assert_eq!(stepper.run(), RunOutcome::Breakpoint(Breakpoint::Sym("🐱".to_string())));
+        assert_eq!(stepper.current(), &Expr::sym("🐱"));
+
+        stepper.step_back();
+        stepper.step_back();
+        stepper.clear_breakpoints();
+        assert_eq!(stepper.run_until(|expr, _| matches!(expr, Expr::Sym(_))), RunOutcome::Predicate);
+        assert_eq!(stepper.run(), RunOutcome::NormalForm);
+
+        assert_eq!("🎭Love".parse(), Ok(Breakpoint::Muse("Love".to_string())));
+        assert_eq!("beta".parse(), Ok(Breakpoint::Rule(Rule::Beta)));
+        assert!("".parse::<Breakpoint>().is_err());
+    }
+
+    #[test]
+    fn test_divergent_terms_stop_at_the_step_limit() {
+        let half = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("x")));
+        let omega = Expr::app(half.clone(), half);
+        let mut stepper = LambdaEngine::new().with_max_steps(10).stepper(omega);
+        assert_eq!(stepper.run(), RunOutcome::StepLimit);
+        assert_eq!(stepper.position(), 10);
+    }
+
+    #[test]
+    fn test_steps_stay_within_the_budget() {
+        let half = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("x")));
+        let omega = Expr::app(half.clone(), half);
+        let mut stepper = LambdaEngine::new().with_budget(EvalBudget::unlimited().with_max_steps(3)).stepper(omega);
+        assert_eq!(stepper.run(), RunOutcome::Budget(BudgetKind::Steps));
+        assert_eq!(stepper.position(), 3);
+        assert_eq!(stepper.step(), Err(BudgetKind::Steps));
+
+        // Y f grows by one f each unfolding; the step past the size limit is refused
+        let expr = Expr::app(Expr::y_combinator(), Expr::sym("f"));
+        let mut stepper = LambdaEngine::new().with_budget(EvalBudget::unlimited().with_max_term_size(30)).stepper(expr);
+        assert_eq!(stepper.run(), RunOutcome::Budget(BudgetKind::TermSize));
+        assert!(stepper.current().size() <= 30);
+
+        let expr = Expr::app(Expr::y_combinator(), Expr::sym("f"));
+        let mut stepper = LambdaEngine::new().with_budget(EvalBudget::unlimited().with_deadline(Duration::ZERO)).stepper(expr);
+        assert_eq!(stepper.run(), RunOutcome::Budget(BudgetKind::Deadline));
+        assert_eq!(stepper.position(), 0);
+    }
+
+    #[test]
+    fn test_history_keeps_only_the_latest_terms() {
+        let half = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("x")));
+        let omega = Expr::app(half.clone(), half);
+        let mut stepper = LambdaEngine::new().with_max_steps(50).stepper(omega).with_history_limit(4);
+        assert_eq!(stepper.run(), RunOutcome::StepLimit);
+        assert_eq!(stepper.position(), 50);
+        assert_eq!(stepper.history().len(), 4);
+        assert_eq!(stepper.steps().len(), 3);
+
+        while stepper.step_back().is_some() {}
+        assert_eq!(stepper.position(), 47);
+    }
+}
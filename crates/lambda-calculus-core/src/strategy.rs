@@ -0,0 +1,565 @@
+//! # 🧭 Evaluation Strategies: Which Redex Fires Next
+//!
+//! The same emoji poem can take very different paths to its normal form.
+//! A [`Strategy`] decides which redex [`LambdaEngine`](crate::LambdaEngine)
+//! contracts at each step and how far reduction goes (full normal form, head
+//! normal form, or weak head normal form), so step counts can be compared
+//! across strategies.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::arena::{Reducer, Term, TermId};
+use crate::trace::{PathStep, Redex, Rule};
+
+/// 🧭 Order in which redexes are chosen during reduction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Strategy {
+    /// Leftmost-outermost, reducing under lambdas - reaches the normal form whenever one exists
+    #[default]
+    NormalOrder,
+    /// Leftmost-innermost: function and argument are normalized before the redex fires
+    ApplicativeOrder,
+    /// Leftmost-outermost without entering lambdas; stuck arguments still reduce
+    CallByName,
+    /// Call-by-name, but an argument used more than once is shared: it is
+    /// reduced only when the body demands it, and then only once
+    CallByNeed,
+    /// Head reduction only - stops at `λx..xn. h M1..Mk` with `h` not a redex
+    HeadNormal,
+    /// Head reduction outside lambdas only - stops at a lambda or a stuck application
+    WeakHead,
+}
+
+impl Strategy {
+    /// Every strategy, for comparisons across all of them
+    pub const ALL: [Strategy; 6] = [
+        Strategy::NormalOrder,
+        Strategy::ApplicativeOrder,
+        Strategy::CallByName,
+        Strategy::CallByNeed,
+        Strategy::HeadNormal,
+        Strategy::WeakHead,
+    ];
+
+    /// Stable kebab-case name, as accepted by `FromStr`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::NormalOrder => "normal-order",
+            Strategy::ApplicativeOrder => "applicative-order",
+            Strategy::CallByName => "call-by-name",
+            Strategy::CallByNeed => "call-by-need",
+            Strategy::HeadNormal => "head-normal",
+            Strategy::WeakHead => "weak-head",
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// ❌ A strategy name that `Strategy::from_str` does not recognize
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("unknown evaluation strategy `{0}` (expected one of: normal-order, applicative-order, call-by-name, call-by-need, head-normal, weak-head)")]
+pub struct UnknownStrategy(pub String);
+
+impl FromStr for Strategy {
+    type Err = UnknownStrategy;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "normal-order" | "normal" => Ok(Strategy::NormalOrder),
+            "applicative-order" | "applicative" => Ok(Strategy::ApplicativeOrder),
+            "call-by-name" | "cbn" => Ok(Strategy::CallByName),
+            "call-by-need" | "need" | "lazy" => Ok(Strategy::CallByNeed),
+            "head-normal" | "head" | "hnf" => Ok(Strategy::HeadNormal),
+            "weak-head" | "whnf" => Ok(Strategy::WeakHead),
+            _ => Err(UnknownStrategy(s.to_string())),
+        }
+    }
+}
+
+/// One walk a strategy makes to find its next redex, as the key under which
+/// `Reducer` remembers what a walk found below a term
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Walk {
+    Outermost { under_lambda: bool, into_arguments: bool },
+    Innermost,
+    Need { applied: bool, weak: bool },
+}
+
+/// Where a walk went from a parent term, while it searches the child
+#[derive(Debug, Clone, Copy)]
+enum Frame {
+    Body,
+    Left,
+    Right,
+    Inner,
+    /// The body of `let x = M in B`, written `(λx.B) M`
+    LetBody,
+    /// The shared argument of `let x = M in B`
+    LetArgument,
+}
+
+/// How call-by-need treats an application whose argument it shares
+enum Sharing {
+    /// `let x = M in B` reduces `B` first
+    Body,
+    /// `B` has `x` at its head, so `M` is forced
+    Argument,
+    /// `S f g M` becomes `(λx.f x (g x)) M`
+    S,
+}
+
+impl Reducer {
+    /// 🧭 Perform one reduction step of `id` under `strategy`, returning the
+    /// new term and the redex that was contracted
+    pub(crate) fn step(&mut self, id: TermId, strategy: Strategy) -> Option<(TermId, Redex)> {
+        match strategy {
+            Strategy::NormalOrder => self.outermost_step(id, true, true),
+            Strategy::CallByName => self.outermost_step(id, false, true),
+            Strategy::HeadNormal => self.outermost_step(id, true, false),
+            Strategy::WeakHead => self.outermost_step(id, false, false),
+            Strategy::ApplicativeOrder => self.innermost_step(id),
+            Strategy::CallByNeed => self.need_step(id, false, false),
+        }
+    }
+
+    /// What `walk` found below `id` on an earlier step
+    fn recall(&self, id: TermId, walk: Walk) -> Option<Option<(TermId, Redex)>> {
+        self.walks.get(&(id, walk)).cloned()
+    }
+
+    /// Remember what `walk` found below `id`, unless it was a muse rule:
+    /// those render the names of the enclosing lambdas into their result
+    fn remember(&mut self, id: TermId, walk: Walk, found: &Option<(TermId, Redex)>) {
+        if !matches!(found, Some((_, redex)) if matches!(redex.rule, Rule::MuseEvolve | Rule::MuseApply)) {
+            self.walks.insert((id, walk), found.clone());
+        }
+    }
+
+    /// Contract the root redex, otherwise descend into the head and optionally
+    /// into lambda bodies and the arguments of a stuck head
+    fn outermost_step(&mut self, root: TermId, under_lambda: bool, into_arguments: bool) -> Option<(TermId, Redex)> {
+        let walk = Walk::Outermost { under_lambda, into_arguments };
+        let mut frames = Vec::new();
+        let mut id = root;
+        'descend: loop {
+            let mut found = match self.recall(id, walk) {
+                Some(found) => found,
+                None => match self.contract(id) {
+                    Some(contracted) => Some(contracted),
+                    None => match self.arena.term(id).clone() {
+                        Term::Lambda(param, body) if under_lambda => {
+                            self.scope.push(param);
+                            frames.push((id, Frame::Body));
+                            id = body;
+                            continue;
+                        }
+                        // The function position never holds a lambda here (that
+                        // would have been a beta redex), so only the argument rule matters
+                        Term::App(left, _) => {
+                            frames.push((id, Frame::Left));
+                            id = left;
+                            continue;
+                        }
+                        _ => None,
+                    },
+                },
+            };
+
+            loop {
+                self.remember(id, walk, &found);
+                let Some((parent, frame)) = frames.pop() else {
+                    return found;
+                };
+                found = match (frame, self.arena.term(parent).clone()) {
+                    (Frame::Body, Term::Lambda(param, _)) => {
+                        self.scope.pop();
+                        found.map(|(body, redex)| (self.arena.intern(Term::Lambda(param, body)), redex.under(PathStep::Body)))
+                    }
+                    (Frame::Left, Term::App(_, right)) => match found {
+                        Some((left, redex)) => Some((self.arena.app(left, right), redex.under(PathStep::Left))),
+                        None if into_arguments => {
+                            frames.push((parent, Frame::Right));
+                            id = right;
+                            continue 'descend;
+                        }
+                        None => None,
+                    },
+                    (Frame::Right, Term::App(left, _)) => {
+                        found.map(|(right, redex)| (self.arena.app(left, right), redex.under(PathStep::Right)))
+                    }
+                    _ => unreachable!("outermost walk left {:?} of a mismatched term", frame),
+                };
+                id = parent;
+            }
+        }
+    }
+
+    /// Reduce inside subterms first; contract the root only once they are normal
+    fn innermost_step(&mut self, root: TermId) -> Option<(TermId, Redex)> {
+        let walk = Walk::Innermost;
+        let mut frames = Vec::new();
+        let mut id = root;
+        'descend: loop {
+            let mut found = match self.recall(id, walk) {
+                Some(found) => found,
+                None => match self.arena.term(id).clone() {
+                    Term::Lambda(param, body) => {
+                        self.scope.push(param);
+                        frames.push((id, Frame::Body));
+                        id = body;
+                        continue;
+                    }
+                    Term::App(left, _) => {
+                        frames.push((id, Frame::Left));
+                        id = left;
+                        continue;
+                    }
+                    Term::Quine(inner) => {
+                        frames.push((id, Frame::Inner));
+                        id = inner;
+                        continue;
+                    }
+                    _ => self.contract(id),
+                },
+            };
+
+            loop {
+                self.remember(id, walk, &found);
+                let Some((parent, frame)) = frames.pop() else {
+                    return found;
+                };
+                found = match (frame, self.arena.term(parent).clone()) {
+                    (Frame::Body, Term::Lambda(param, _)) => {
+                        self.scope.pop();
+                        found.map(|(body, redex)| (self.arena.intern(Term::Lambda(param, body)), redex.under(PathStep::Body)))
+                    }
+                    (Frame::Left, Term::App(_, right)) => match found {
+                        Some((left, redex)) => Some((self.arena.app(left, right), redex.under(PathStep::Left))),
+                        None => {
+                            frames.push((parent, Frame::Right));
+                            id = right;
+                            continue 'descend;
+                        }
+                    },
+                    (Frame::Right, Term::App(left, _)) => match found {
+                        Some((right, redex)) => Some((self.arena.app(left, right), redex.under(PathStep::Right))),
+                        None => self.contract(parent),
+                    },
+                    (Frame::Inner, Term::Quine(_)) => match found {
+                        Some((inner, redex)) => Some((self.arena.intern(Term::Quine(inner)), redex.under(PathStep::Inner))),
+                        None => self.contract(parent),
+                    },
+                    _ => unreachable!("innermost walk left {:?} of a mismatched term", frame),
+                };
+                id = parent;
+            }
+        }
+    }
+
+    /// Call-by-name with sharing: `(λx.B) M` whose `M` is not a value and
+    /// whose `x` occurs more than once is treated as `let x = M in B`
+    ///
+    /// `B` reduces first. `M` takes a weak head step only when `B` needs it:
+    /// when `x` is the head of `B`, or when `B` is stuck with `x` somewhere
+    /// outside a lambda, where call-by-name would reduce a copy of it. Once
+    /// `M` is a value, or `B` no longer needs it, the β step fires. An `S`
+    /// redex whose last argument is not a value becomes `(λx.f x (g x)) M`
+    /// so that argument is shared the same way.
+    ///
+    /// `applied` says `id` has arguments waiting outside it, and `weak`
+    /// keeps the step on the head spine, out of stuck arguments. While
+    /// arguments wait outside a `let`, `B` only takes head steps: the β step
+    /// may saturate its head, and call-by-name would contract that first.
+    fn need_step(&mut self, root: TermId, applied: bool, weak: bool) -> Option<(TermId, Redex)> {
+        let mut frames: Vec<(TermId, Walk, Frame)> = Vec::new();
+        let mut id = root;
+        let mut walk = Walk::Need { applied, weak };
+        'descend: loop {
+            let Walk::Need { applied, weak } = walk else { unreachable!("need walks only") };
+            let mut found = match self.recall(id, walk) {
+                Some(found) => found,
+                None => match self.shared_argument(id) {
+                    Some(Sharing::Body) => {
+                        let Term::App(function, _) = *self.arena.term(id) else { unreachable!("a let is an application") };
+                        let Term::Lambda(hint, body) = self.arena.term(function).clone() else { unreachable!("a let binds a lambda") };
+                        self.scope.push(hint);
+                        frames.push((id, walk, Frame::LetBody));
+                        id = body;
+                        walk = Walk::Need { applied, weak: weak || applied };
+                        continue;
+                    }
+                    Some(Sharing::Argument) => {
+                        // Demanded, and not a value: force it once, in place
+                        let Term::App(_, argument) = *self.arena.term(id) else { unreachable!("a let is an application") };
+                        frames.push((id, walk, Frame::LetArgument));
+                        id = argument;
+                        walk = Walk::Need { applied: false, weak: true };
+                        continue;
+                    }
+                    Some(Sharing::S) => Some((self.share_s(id), Redex::at_root(Rule::S, false))),
+                    None => match self.contract(id) {
+                        Some(contracted) => Some(contracted),
+                        None => match *self.arena.term(id) {
+                            Term::App(left, _) => {
+                                frames.push((id, walk, Frame::Left));
+                                id = left;
+                                walk = Walk::Need { applied: true, weak };
+                                continue;
+                            }
+                            _ => None,
+                        },
+                    },
+                },
+            };
+
+            loop {
+                self.remember(id, walk, &found);
+                let Some((parent, parent_walk, frame)) = frames.pop() else {
+                    return found;
+                };
+                let Walk::Need { applied, weak } = parent_walk else { unreachable!("need walks only") };
+                let Term::App(function, argument) = *self.arena.term(parent) else {
+                    unreachable!("need walk left {:?} of a mismatched term", frame)
+                };
+                found = match frame {
+                    Frame::Left => match found {
+                        Some((left, redex)) => Some((self.arena.app(left, argument), redex.under(PathStep::Left))),
+                        None if weak => None,
+                        None => {
+                            frames.push((parent, parent_walk, Frame::Right));
+                            id = argument;
+                            walk = Walk::Need { applied: false, weak: false };
+                            continue 'descend;
+                        }
+                    },
+                    Frame::Right | Frame::LetArgument => {
+                        found.map(|(argument, redex)| (self.arena.app(function, argument), redex.under(PathStep::Right)))
+                    }
+                    Frame::LetBody => {
+                        self.scope.pop();
+                        let Term::Lambda(hint, body) = self.arena.term(function).clone() else {
+                            unreachable!("a let binds a lambda")
+                        };
+                        match found {
+                            Some((body, redex)) => {
+                                let function = self.arena.intern(Term::Lambda(hint, body));
+                                Some((self.arena.app(function, argument), redex.under(PathStep::Body).under(PathStep::Left)))
+                            }
+                            None if applied || weak || !self.reaches(body, 0) => self.contract(parent),
+                            None => {
+                                frames.push((parent, parent_walk, Frame::LetArgument));
+                                id = argument;
+                                walk = Walk::Need { applied: false, weak: true };
+                                continue 'descend;
+                            }
+                        }
+                    }
+                    Frame::Body | Frame::Inner => unreachable!("need walks stay outside lambdas and quines"),
+                };
+                id = parent;
+                walk = parent_walk;
+            }
+        }
+    }
+
+    /// How call-by-need shares the argument of `id`, if it does
+    fn shared_argument(&self, id: TermId) -> Option<Sharing> {
+        let Term::App(function, argument) = *self.arena.term(id) else {
+            return None;
+        };
+        if self.is_value(argument) {
+            return None;
+        }
+        match *self.arena.term(function) {
+            Term::Lambda(_, body) if self.arena.occurrences(body, 0) > 1 => {
+                Some(if self.head(body) == Term::Bound(0) { Sharing::Argument } else { Sharing::Body })
+            }
+            Term::Lambda(_, _) => None,
+            _ => (self.redex_rule(id) == Some(Rule::S)).then_some(Sharing::S),
+        }
+    }
+
+    /// `S f g M → (λx.f x (g x)) M`, sharing `M` instead of copying it
+    fn share_s(&mut self, id: TermId) -> TermId {
+        let Term::App(function, argument) = *self.arena.term(id) else { unreachable!("S needs three arguments") };
+        let Term::App(sf, g) = *self.arena.term(function) else { unreachable!("S needs three arguments") };
+        let Term::App(_, f) = *self.arena.term(sf) else { unreachable!("S needs three arguments") };
+        let x = self.arena.intern(Term::Bound(0));
+        let (f, g) = (self.arena.lift(f), self.arena.lift(g));
+        let fx = self.arena.app(f, x);
+        let gx = self.arena.app(g, x);
+        let body = self.arena.app(fx, gx);
+        let shared = self.arena.intern(Term::Lambda("x".to_string(), body));
+        self.arena.app(shared, argument)
+    }
+
+    /// True when `id` is in weak head normal form, so copying it copies no
+    /// pending work at its head
+    fn is_value(&self, mut id: TermId) -> bool {
+        loop {
+            if self.redex_rule(id).is_some() {
+                return false;
+            }
+            match *self.arena.term(id) {
+                Term::App(left, _) => id = left,
+                _ => return true,
+            }
+        }
+    }
+
+    /// The term at the end of the left spine of `id`
+    fn head(&self, mut id: TermId) -> Term {
+        while let Term::App(left, _) = *self.arena.term(id) {
+            id = left;
+        }
+        self.arena.term(id).clone()
+    }
+
+    /// True when the variable with de Bruijn `index` occurs in `id` outside
+    /// every lambda, where reduction without entering lambdas will reach it
+    fn reaches(&self, id: TermId, index: u32) -> bool {
+        let mut visited = HashSet::new();
+        let mut pending = vec![id];
+        while let Some(id) = pending.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            match *self.arena.term(id) {
+                Term::Bound(bound) if bound == index => return true,
+                Term::App(left, right) => pending.extend([right, left]),
+                Term::Quine(inner) => pending.push(inner),
+                _ => {}
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Expr, LambdaEngine};
+
+    fn omega() -> Expr {
+        let self_app = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("x")));
+        Expr::app(self_app.clone(), self_app)
+    }
+
+    fn run(strategy: Strategy, expr: Expr) -> crate::ReductionTrace {
+        LambdaEngine::new()
+            .with_max_steps(50)
+            .with_strategy(strategy)
+            .normalize(expr)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_strategy_names_round_trip() {
+        for strategy in Strategy::ALL {
+            assert_eq!(strategy.name().parse::<Strategy>(), Ok(strategy));
+        }
+        assert_eq!("call_by_need".parse::<Strategy>(), Ok(Strategy::CallByNeed));
+        assert!("eager".parse::<Strategy>().is_err());
+    }
+
+    #[test]
+    fn test_normal_order_discards_divergent_argument() {
+        // K I Ω → I under normal order; applicative order loops on Ω forever
+        let expr = Expr::app(Expr::app(Expr::K, Expr::I), omega());
+
+        let lazy = run(Strategy::NormalOrder, expr.clone());
+        assert_eq!(lazy.final_form, Expr::I);
+        assert_eq!(lazy.strategy, Strategy::NormalOrder);
+
+        let strict = run(Strategy::ApplicativeOrder, expr);
+        assert!(!strict.is_normal_form);
+    }
+
+    #[test]
+    fn test_weak_strategies_stop_at_lambdas() {
+        // λy.(I y): only the strategies that enter lambdas reduce the body
+        let expr = Expr::lambda("y", Expr::app(Expr::I, Expr::var("y")));
+        for strategy in Strategy::ALL {
+            let trace = run(strategy, expr.clone());
+            let enters_lambdas = matches!(
+                strategy,
+                Strategy::NormalOrder | Strategy::ApplicativeOrder | Strategy::HeadNormal
+            );
+            assert_eq!(trace.step_count, usize::from(enters_lambdas), "{}", strategy);
+        }
+    }
+
+    #[test]
+    fn test_head_strategies_leave_arguments_alone() {
+        // f (I x): the head `f` is stuck, so only argument-reducing strategies continue
+        let expr = Expr::app(Expr::sym("f"), Expr::app(Expr::I, Expr::sym("x")));
+        assert_eq!(run(Strategy::WeakHead, expr.clone()).final_form, expr);
+        assert_eq!(run(Strategy::HeadNormal, expr.clone()).final_form, expr);
+        assert_eq!(
+            run(Strategy::CallByName, expr).final_form,
+            Expr::app(Expr::sym("f"), Expr::sym("x"))
+        );
+    }
+
+    #[test]
+    fn test_call_by_need_shares_duplicated_work() {
+        // (λx.(x x)) (I I): call-by-name copies `I I` and reduces it twice
+        let dup = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("x")));
+        let expr = Expr::app(dup, Expr::app(Expr::I, Expr::I));
+
+        let by_name = run(Strategy::CallByName, expr.clone());
+        let by_need = run(Strategy::CallByNeed, expr);
+        assert_eq!(by_name.final_form, Expr::I);
+        assert_eq!(by_need.final_form, Expr::I);
+        assert!(by_need.step_count < by_name.step_count);
+    }
+
+    #[test]
+    fn test_call_by_need_leaves_unneeded_arguments_alone() {
+        // The duplicated Ω is never demanded, so it must never be forced
+        let dup = Expr::lambda("x", Expr::app(Expr::app(Expr::K, Expr::I), Expr::app(Expr::var("x"), Expr::var("x"))));
+        let discarding_lambda = Expr::app(dup, omega());
+        let k_ki = Expr::app(Expr::K, Expr::app(Expr::K, Expr::I));
+        let discarding_s = Expr::app(Expr::app(Expr::app(Expr::S, k_ki), Expr::K), omega());
+
+        for expr in [discarding_lambda, discarding_s] {
+            for strategy in [Strategy::NormalOrder, Strategy::CallByName, Strategy::CallByNeed] {
+                let trace = run(strategy, expr.clone());
+                assert_eq!(trace.final_form, Expr::I, "{} on {}", strategy, expr);
+                assert_eq!(trace.termination, crate::Termination::NormalForm);
+            }
+        }
+    }
+
+    #[test]
+    fn test_call_by_need_forces_a_shared_argument_once() {
+        // S I I (I a) needs its argument at the head: it is reduced once, then copied
+        let expr = Expr::app(Expr::app(Expr::app(Expr::S, Expr::I), Expr::I), Expr::app(Expr::I, Expr::sym("a")));
+        let by_name = run(Strategy::CallByName, expr.clone());
+        let by_need = run(Strategy::CallByNeed, expr);
+        let rules = |trace: &crate::ReductionTrace| trace.steps().iter().filter(|step| step.rule == crate::Rule::I).count();
+
+        assert_eq!(by_need.final_form, by_name.final_form);
+        assert_eq!(rules(&by_name), 4);
+        assert_eq!(rules(&by_need), 3);
+    }
+
+    #[test]
+    fn test_strategies_serialize_by_name() {
+        for strategy in Strategy::ALL {
+            assert_eq!(serde_json::to_value(strategy).unwrap(), strategy.name());
+        }
+        assert_eq!(serde_json::from_str::<Strategy>("\"call-by-need\"").unwrap(), Strategy::CallByNeed);
+    }
+}
@@ -0,0 +1,179 @@
+//! # 🔭 Quine Synthesis: Searching for Terms That Reproduce Themselves
+//!
+//! Where [`crate::quine`] checks a given term, this module goes looking for
+//! one. Self-reproduction needs a term that hands a copy of itself to itself,
+//! so the search enumerates *halves* `M = λx.B` and tries the self-application
+//! `M M`. Bodies `B` are applicative trees over `x`, `S`, `K`, `I` and the
+//! seed symbol; each must use both `x` (otherwise `M M` just discards the
+//! copy) and the seed. Bodies are enumerated by leaf count, so candidates
+//! come in increasing size and the first verified quine is a smallest one in
+//! the search space.
+//!
+//! For example, `(λx.K (x x) 🌀) (λx.K (x x) 🌀)` rebuilds itself in two
+//! steps while carrying the seed `🌀` along.
+
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+use crate::quine::QuineReport;
+use crate::{EvalError, Expr, LambdaEngine};
+
+/// 📏 Limits on a quine search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SynthesisBudget {
+    /// Largest candidate, in `Expr::size` nodes
+    pub max_size: usize,
+    /// Stop after verifying this many candidates
+    pub max_candidates: usize,
+    /// Reduction steps allowed when verifying one candidate
+    pub max_steps: usize,
+}
+
+impl Default for SynthesisBudget {
+    fn default() -> Self {
+        Self { max_size: 21, max_candidates: 100_000, max_steps: 16 }
+    }
+}
+
+impl SynthesisBudget {
+    /// Set the largest candidate size
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Set how many candidates may be verified
+    pub fn with_max_candidates(mut self, max_candidates: usize) -> Self {
+        self.max_candidates = max_candidates;
+        self
+    }
+
+    /// Set the reduction steps allowed per candidate
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+}
+
+/// 🔭 Outcome of a quine search
+#[derive(Debug, Clone)]
+pub struct QuineSearch {
+    pub seed: String,
+    /// The smallest quine found, with its proof
+    pub quine: Option<QuineReport>,
+    /// Candidates verified before the search stopped
+    pub candidates_checked: usize,
+    /// True when `max_candidates` stopped the search before `max_size` was covered
+    pub budget_exhausted: bool,
+}
+
+impl QuineSearch {
+    /// The synthesized quine, if one was found
+    pub fn expr(&self) -> Option<&Expr> {
+        self.quine.as_ref().and_then(|report| report.terms.first())
+    }
+}
+
+/// Every applicative tree over `atoms` with exactly `leaves` leaves, built
+/// from the trees with fewer leaves in `smaller` (indexed by leaf count)
+fn trees_with_leaves(leaves: usize, atoms: &[Expr], smaller: &[Vec<Expr>]) -> Vec<Expr> {
+    if leaves == 1 {
+        return atoms.to_vec();
+    }
+    let mut trees = Vec::new();
+    for left_leaves in 1..leaves {
+        for left in &smaller[left_leaves] {
+            for right in &smaller[leaves - left_leaves] {
+                trees.push(Expr::app(left.clone(), right.clone()));
+            }
+        }
+    }
+    trees
+}
+
+fn mentions(expr: &Expr, leaf: &Expr) -> bool {
+    match expr {
+        Expr::App(left, right) => mentions(left, leaf) || mentions(right, leaf),
+        other => other == leaf,
+    }
+}
+
+impl LambdaEngine {
+    /// 🔭 Search for the smallest quine containing `seed` as a symbol
+    ///
+    /// Candidates are verified with `verify_quine` under this engine's
+    /// environment and strategy, with the budget's step limit and a term
+    /// size cap that keeps exploding candidates cheap to reject.
+    pub fn synthesize_quine(&self, seed: &str, budget: SynthesisBudget) -> Result<QuineSearch, EvalError> {
+        info!("🔭 Searching for a quine containing {} up to size {}", seed, budget.max_size);
+        let variable = Expr::var("x");
+        let seed_expr = Expr::sym(seed);
+        let atoms = [variable.clone(), Expr::S, Expr::K, Expr::I, seed_expr.clone()];
+
+        let mut search = QuineSearch { seed: seed.to_string(), quine: None, candidates_checked: 0, budget_exhausted: false };
+        // M M with M = λx.B has size 2 (size(B) + 1) + 1, and a body with n leaves has size 2n - 1
+        let max_leaves = budget.max_size.saturating_sub(1) / 4;
+        let mut trees: Vec<Vec<Expr>> = vec![Vec::new()];
+
+        for leaves in 1..=max_leaves {
+            let layer = trees_with_leaves(leaves, &atoms, &trees);
+            debug!("🔭 {} bodies with {} leaves", layer.len(), leaves);
+
+            for body in layer.iter().filter(|body| mentions(body, &variable) && mentions(body, &seed_expr)) {
+                if search.candidates_checked >= budget.max_candidates {
+                    search.budget_exhausted = true;
+                    return Ok(search);
+                }
+                let half = Expr::lambda("x", body.clone());
+                let candidate = Expr::app(half.clone(), half);
+                let verifier = self
+                    .clone()
+                    .with_max_steps(budget.max_steps)
+                    .with_budget(self.budget.with_max_term_size(4 * candidate.size()));
+
+                search.candidates_checked += 1;
+                let report = verifier.verify_quine(&candidate)?;
+                if report.verdict.is_quine() {
+                    info!("🪞 Found {} after {} candidates: {}", report.verdict, search.candidates_checked, candidate);
+                    search.quine = Some(report);
+                    return Ok(search);
+                }
+            }
+            trees.push(layer);
+        }
+
+        info!("🔭 No quine containing {} up to size {}", seed, budget.max_size);
+        Ok(search)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QuineVerdict;
+
+    #[test]
+    fn test_search_finds_smallest_seeded_quine() {
+        let search = LambdaEngine::new().synthesize_quine("🌀", SynthesisBudget::default()).unwrap();
+        let quine = search.quine.as_ref().expect("a quine within the default budget");
+
+        assert!(matches!(quine.verdict, QuineVerdict::Cyclic { period: 2, .. }));
+        let expr = search.expr().unwrap();
+        assert_eq!(expr.size(), 17);
+        assert!(expr.to_string().contains("🌀"));
+        // Independently re-verified
+        assert!(LambdaEngine::new().verify_quine(expr).unwrap().verdict.is_quine());
+    }
+
+    #[test]
+    fn test_search_respects_size_and_candidate_limits() {
+        let search = LambdaEngine::new().synthesize_quine("🌀", SynthesisBudget::default().with_max_size(13)).unwrap();
+        assert!(search.quine.is_none());
+        assert!(!search.budget_exhausted);
+
+        let search = LambdaEngine::new().synthesize_quine("🌀", SynthesisBudget::default().with_max_candidates(3)).unwrap();
+        assert!(search.quine.is_none());
+        assert!(search.budget_exhausted);
+        assert_eq!(search.candidates_checked, 3);
+    }
+}
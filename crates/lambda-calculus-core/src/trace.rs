@@ -0,0 +1,370 @@
+//! # 🔍 Reduction Steps: Which Redex Fired, Where, and Why
+//!
+//! Each step of a [`crate::ReductionTrace`] records the rule that fired, the
+//! path from the root of the expression down to the contracted redex, and the
+//! redex before and after contraction. Everything serializes with serde so the
+//! REPL, `/api/v1/analyze` and visualizers can highlight the redex.
+
+use std::fmt;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Expr;
+
+/// ⚡ The reduction rule that contracted a redex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Rule {
+    /// `(λx.body) arg → body[x := arg]`
+    Beta,
+    /// `S f g x → f x (g x)`
+    S,
+    /// `K x y → x`
+    K,
+    /// `I x → x`
+    I,
+    /// A standalone muse gains resonance: `🎭Sol[970] → 🎭Sol[971]`
+    MuseEvolve,
+    /// A muse absorbs its argument into its name: `🎭Sol x → 🎭Sol+x`
+    MuseApply,
+    /// A quine releases its body: `🌀Q(e) → e`
+    QuineUnwrap,
+    /// A quine carries its argument inside: `🌀Q(e) x → 🌀Q(e x)`
+    QuineApply,
+    /// A variable bound in the engine environment is replaced by its value
+    EnvLookup,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Rule::Beta => "β",
+            Rule::S => "S",
+            Rule::K => "K",
+            Rule::I => "I",
+            Rule::MuseEvolve => "muse-evolve",
+            Rule::MuseApply => "muse-apply",
+            Rule::QuineUnwrap => "quine-unwrap",
+            Rule::QuineApply => "quine-apply",
+            Rule::EnvLookup => "env-lookup",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 🧭 One move from a node to one of its children
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathStep {
+    /// Function side of an application
+    Left,
+    /// Argument side of an application
+    Right,
+    /// Body of a lambda
+    Body,
+    /// Body of a quine
+    Inner,
+}
+
+impl Expr {
+    /// 🧭 The subterm reached by following `path` from this expression
+    pub fn subterm_at(&self, path: &[PathStep]) -> Option<&Expr> {
+        path.iter().try_fold(self, |expr, step| match (step, expr) {
+            (PathStep::Left, Expr::App(left, _)) => Some(left.as_ref()),
+            (PathStep::Right, Expr::App(_, right)) => Some(right.as_ref()),
+            (PathStep::Body, Expr::Lambda(_, body)) => Some(body.as_ref()),
+            (PathStep::Inner, Expr::Quine(inner)) => Some(inner.as_ref()),
+            _ => None,
+        })
+    }
+}
+
+/// 🔍 A single contraction recorded in a reduction trace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReductionStep {
+    /// Rule that fired
+    pub rule: Rule,
+    /// Path from the root of the expression to the redex (empty for the root)
+    pub path: Vec<PathStep>,
+    /// The redex before contraction
+    pub before: String,
+    /// What the redex was replaced with
+    pub after: String,
+    /// The whole expression after this step
+    pub expression: String,
+}
+
+impl fmt::Display for ReductionStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at ", self.rule)?;
+        if self.path.is_empty() {
+            write!(f, "root")?;
+        } else {
+            let path: Vec<&str> = self
+                .path
+                .iter()
+                .map(|step| match step {
+                    PathStep::Left => "left",
+                    PathStep::Right => "right",
+                    PathStep::Body => "body",
+                    PathStep::Inner => "inner",
+                })
+                .collect();
+            write!(f, "{}", path.join("."))?;
+        }
+        write!(f, ": {} → {}  ⟹  {}", self.before, self.after, self.expression)
+    }
+}
+
+/// 🏁 Why a reduction stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Termination {
+    /// No redex left for the strategy to contract
+    NormalForm,
+    /// The term at the last step is alpha-equivalent to the one at
+    /// `first_seen_step`; it will repeat every `period` steps forever
+    Cycle { period: usize, first_seen_step: usize },
+    /// The step limit was hit while the term kept getting bigger. A hint of
+    /// divergence, not a proof: the term may still normalize given more steps
+    Growth { initial_size: usize, final_size: usize },
+    /// The step limit was hit without a clear cycle or growth
+    StepLimit,
+}
+
+impl Termination {
+    /// Classify a run that hit the step limit from the term size at each step:
+    /// growth means the final term is larger than every term seen during the
+    /// first half of the run
+    pub(crate) fn from_sizes(sizes: &[usize]) -> Self {
+        let (Some(&initial_size), Some(&final_size)) = (sizes.first(), sizes.last()) else {
+            return Termination::StepLimit;
+        };
+        let early_peak = sizes[..sizes.len().div_ceil(2)].iter().copied().max().unwrap_or(0);
+        if final_size > early_peak {
+            Termination::Growth { initial_size, final_size }
+        } else {
+            Termination::StepLimit
+        }
+    }
+
+    /// True when the term provably never reaches a normal form, which is only
+    /// the case for a cycle; `Growth` is a heuristic and does not count
+    pub fn is_divergent(&self) -> bool {
+        matches!(self, Termination::Cycle { .. })
+    }
+}
+
+impl fmt::Display for Termination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Termination::NormalForm => write!(f, "normal form"),
+            Termination::Cycle { period, first_seen_step } => {
+                write!(f, "cycle of period {} from step {}", period, first_seen_step)
+            }
+            Termination::Growth { initial_size, final_size } => {
+                write!(f, "still growing at step limit ({} → {} nodes)", initial_size, final_size)
+            }
+            Termination::StepLimit => write!(f, "step limit reached"),
+        }
+    }
+}
+
+/// A path from the root to a redex, root first
+///
+/// Walkers build it from the redex upward, one step at a time, and a trace
+/// keeps one per step; sharing the rest of the path makes both O(1) however
+/// deep the redex sits.
+#[derive(Clone, Default)]
+pub(crate) struct Path(Option<Rc<PathNode>>);
+
+struct PathNode {
+    step: PathStep,
+    rest: Path,
+}
+
+impl Path {
+    /// This path, reached from the parent via `step`
+    pub(crate) fn under(&self, step: PathStep) -> Self {
+        Path(Some(Rc::new(PathNode { step, rest: self.clone() })))
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<PathStep> {
+        let mut steps = Vec::new();
+        let mut current = &self.0;
+        while let Some(node) = current {
+            steps.push(node.step);
+            current = &node.rest.0;
+        }
+        steps
+    }
+}
+
+impl From<&[PathStep]> for Path {
+    fn from(steps: &[PathStep]) -> Self {
+        steps.iter().rev().fold(Path::default(), |path, &step| path.under(step))
+    }
+}
+
+impl fmt::Debug for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_vec().fmt(f)
+    }
+}
+
+/// Long paths are unlinked one node at a time, so dropping them cannot
+/// overflow the stack
+impl Drop for Path {
+    fn drop(&mut self) {
+        let mut next = self.0.take();
+        while let Some(node) = next {
+            next = match Rc::try_unwrap(node) {
+                Ok(mut node) => node.rest.0.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+/// A contraction found by a strategy, before it is rendered into a `ReductionStep`
+#[derive(Debug, Clone)]
+pub(crate) struct Redex {
+    pub(crate) rule: Rule,
+    pub(crate) path: Path,
+    /// True when the step copied an argument
+    pub(crate) duplicates: bool,
+}
+
+impl Redex {
+    pub(crate) fn at_root(rule: Rule, duplicates: bool) -> Self {
+        Self { rule, path: Path::default(), duplicates }
+    }
+
+    /// Record that this redex sits below the current node via `step`
+    pub(crate) fn under(mut self, step: PathStep) -> Self {
+        self.path = self.path.under(step);
+        self
+    }
+}
+
+impl ReductionStep {
+    /// The step at `path` that turned `before` into `after`, both whole terms
+    pub(crate) fn between(rule: Rule, path: Vec<PathStep>, before: &Expr, after: &Expr) -> Self {
+        let at = |expr: &Expr| expr.subterm_at(&path).map(ToString::to_string).unwrap_or_default();
+        ReductionStep { rule, before: at(before), after: at(after), expression: after.to_string(), path }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LambdaEngine;
+
+    #[test]
+    fn test_steps_record_rule_and_path() {
+        // λz.(K z (I a)): normal order fires K under the lambda
+        let expr = Expr::lambda(
+            "z",
+            Expr::app(Expr::app(Expr::K, Expr::var("z")), Expr::app(Expr::I, Expr::sym("a"))),
+        );
+        let trace = LambdaEngine::new().normalize(expr.clone()).unwrap();
+
+        assert_eq!(trace.steps().len(), 1);
+        let step = &trace.steps()[0];
+        assert_eq!(step.rule, Rule::K);
+        assert_eq!(step.path, vec![PathStep::Body]);
+        assert_eq!(step.after, "z");
+        assert_eq!(step.expression, "λz.z");
+        assert_eq!(expr.subterm_at(&step.path).map(|e| format!("{}", e)), Some(step.before.clone()));
+    }
+
+    #[test]
+    fn test_steps_name_every_rule() {
+        let mut engine = LambdaEngine::new();
+        engine.bind("seven", Expr::sym("7"));
+        let expr = Expr::app(
+            Expr::app(Expr::lambda("x", Expr::var("x")), Expr::var("seven")),
+            Expr::app(Expr::I, Expr::quine(Expr::sym("q"))),
+        );
+        let trace = engine.normalize(expr).unwrap();
+
+        let steps = trace.steps();
+        let rules: Vec<Rule> = steps.iter().map(|step| step.rule).collect();
+        assert_eq!(rules, vec![Rule::Beta, Rule::EnvLookup, Rule::I, Rule::QuineUnwrap]);
+        assert_eq!(steps[2].path, vec![PathStep::Right]);
+        assert_eq!(steps[3].path, vec![PathStep::Right]);
+    }
+
+    #[test]
+    fn test_omega_is_a_cycle() {
+        let self_app = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("x")));
+        let omega = Expr::app(self_app.clone(), self_app);
+        let trace = LambdaEngine::new().normalize(omega).unwrap();
+
+        assert_eq!(trace.termination, Termination::Cycle { period: 1, first_seen_step: 0 });
+        assert_eq!(trace.step_count, 1);
+        assert!(!trace.is_normal_form);
+    }
+
+    #[test]
+    fn test_cycle_detection_is_modulo_alpha() {
+        // (λa.a a) (λb.b b) renames its binder every time but still loops
+        let a = Expr::lambda("a", Expr::app(Expr::var("a"), Expr::var("a")));
+        let b = Expr::lambda("b", Expr::app(Expr::var("b"), Expr::var("b")));
+        let trace = LambdaEngine::new().normalize(Expr::app(a, b)).unwrap();
+        assert!(matches!(trace.termination, Termination::Cycle { period: 1, .. }));
+    }
+
+    #[test]
+    fn test_y_combinator_diverges_by_growth() {
+        let expr = Expr::app(Expr::y_combinator(), Expr::sym("f"));
+        let trace = LambdaEngine::new().with_max_steps(40).normalize(expr).unwrap();
+
+        assert!(matches!(trace.termination, Termination::Growth { .. }));
+        assert_eq!(trace.step_count, 40);
+    }
+
+    #[test]
+    fn test_growth_is_not_divergence() {
+        // 3 3 = 27 swells while it unfolds, then normalizes given enough steps
+        let church = |n| {
+            let body = (0..n).fold(Expr::var("x"), |body, _| Expr::app(Expr::var("f"), body));
+            Expr::lambda("f", Expr::lambda("x", body))
+        };
+        let expr = Expr::app(church(3), church(3));
+
+        let cut_short = LambdaEngine::new().with_max_steps(3).normalize(expr.clone()).unwrap();
+        assert!(matches!(cut_short.termination, Termination::Growth { .. }));
+        assert!(!cut_short.termination.is_divergent());
+
+        let finished = LambdaEngine::new().normalize(expr).unwrap();
+        assert_eq!(finished.termination, Termination::NormalForm);
+        assert!(Termination::Cycle { period: 1, first_seen_step: 0 }.is_divergent());
+    }
+
+    #[test]
+    fn test_normal_form_at_exact_step_limit() {
+        let trace = LambdaEngine::new()
+            .with_max_steps(1)
+            .normalize(Expr::app(Expr::I, Expr::sym("x")))
+            .unwrap();
+        assert_eq!(trace.step_count, 1);
+        assert_eq!(trace.termination, Termination::NormalForm);
+        assert!(trace.is_normal_form);
+    }
+
+    #[test]
+    fn test_step_serializes() {
+        let trace = LambdaEngine::new()
+            .normalize(Expr::app(Expr::I, Expr::sym("🌀")))
+            .unwrap();
+        let step = trace.steps().remove(0);
+        let json = serde_json::to_value(&step).unwrap();
+        assert_eq!(json["rule"], "I");
+        assert_eq!(json["path"], serde_json::json!([]));
+        assert_eq!(json["before"], "(I 🌀)");
+
+        let back: ReductionStep = serde_json::from_value(json).unwrap();
+        assert_eq!(back, step);
+    }
+}
@@ -0,0 +1,362 @@
+//! # 🏷️ Type Inference: Which Poems Are Guaranteed to Finish?
+//!
+//! Hindley–Milner inference over [`Expr`]. Lambdas and applications get
+//! simple types, every occurrence of `S`, `K` and `I` gets a fresh instance
+//! of its polymorphic type, and muses, symbols and DNA are base types that
+//! cannot be applied. Environment bindings are typed where they are used,
+//! so a bound combinator is as polymorphic as one written inline.
+//!
+//! A closed term with a type is strongly normalizing: however its redexes
+//! are chosen, reduction reaches a normal form. Muses evolve a thousandth of
+//! resonance per step and stop at 1.0, so each adds at most a thousand
+//! steps. Environment bindings are not part of the term, and a binding that
+//! refers to itself can still loop. Self-application such as
+//! `λx.x x`, the root of `Ω` and the Y combinator, has no type, which makes
+//! the checker a cheap way to spot poems that may never finish.
+//!
+//! ```text
+//! S K K    : a → a
+//! λf.λx.f x : (a → b) → a → b
+//! λx.x x   : infinite type
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Expr, LambdaEngine};
+
+/// 🏷️ A simple type with type variables
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Type {
+    /// Type variable, shown as `a`, `b`, … in order of appearance
+    Var(u32),
+    /// Type of every `Muse`
+    Muse,
+    /// Type of every `Sym`
+    Sym,
+    /// Type of every `DNA` strand
+    Dna,
+    /// Function type `argument → result`
+    Arrow(Box<Type>, Box<Type>),
+}
+
+impl Type {
+    /// Create a function type `argument → result`
+    pub fn arrow(argument: Type, result: Type) -> Type {
+        Type::Arrow(Box::new(argument), Box::new(result))
+    }
+
+    /// Renumber type variables from 0 in order of first appearance, so
+    /// alpha-equivalent types compare equal
+    pub fn canonical(&self) -> Type {
+        self.renumbered(&mut HashMap::new())
+    }
+
+    fn renumbered(&self, names: &mut HashMap<u32, u32>) -> Type {
+        match self {
+            Type::Var(var) => {
+                let next = names.len() as u32;
+                Type::Var(*names.entry(*var).or_insert(next))
+            }
+            Type::Arrow(argument, result) => {
+                let argument = argument.renumbered(names);
+                Type::arrow(argument, result.renumbered(names))
+            }
+            base => base.clone(),
+        }
+    }
+
+    fn mentions(&self, var: u32) -> bool {
+        match self {
+            Type::Var(other) => *other == var,
+            Type::Arrow(argument, result) => argument.mentions(var) || result.mentions(var),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Var(var) => {
+                let letter = char::from(b'a' + (var % 26) as u8);
+                match var / 26 {
+                    0 => write!(f, "{}", letter),
+                    round => write!(f, "{}{}", letter, round),
+                }
+            }
+            Type::Muse => write!(f, "Muse"),
+            Type::Sym => write!(f, "Sym"),
+            Type::Dna => write!(f, "DNA"),
+            Type::Arrow(argument, result) => match argument.as_ref() {
+                Type::Arrow(_, _) => write!(f, "({}) → {}", argument, result),
+                _ => write!(f, "{} → {}", argument, result),
+            },
+        }
+    }
+}
+
+/// ❌ Why a term has no type
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum TypeError {
+    /// A function was applied to an argument of the wrong type, or something
+    /// that is not a function was applied
+    #[error("cannot use {found} as {expected} in {term}")]
+    Mismatch { expected: Type, found: Type, term: Expr },
+    /// The term would need a type that contains itself, as self-application does
+    #[error("infinite type: {var} would have to equal {ty} in {term}")]
+    Infinite { var: Type, ty: Type, term: Expr },
+    /// An environment binding refers back to itself
+    #[error("`{name}` is bound in terms of itself")]
+    RecursiveBinding { name: String },
+}
+
+/// Why two types could not be unified
+enum Clash {
+    /// Expected and found types with different constructors
+    Mismatch(Type, Type),
+    Occurs(u32, Type),
+}
+
+/// Unification state for one inference run
+struct Inference<'a> {
+    environment: &'a HashMap<String, Expr>,
+    /// What each type variable has been solved to, if anything
+    solutions: Vec<Option<Type>>,
+    /// Types shared by every occurrence of an unbound free variable
+    free: HashMap<String, Type>,
+    /// Environment bindings currently being typed
+    expanding: Vec<String>,
+}
+
+impl Inference<'_> {
+    fn fresh(&mut self) -> Type {
+        self.solutions.push(None);
+        Type::Var(self.solutions.len() as u32 - 1)
+    }
+
+    /// Follow solved variables until reaching an unsolved one or a constructor
+    fn shallow(&self, ty: &Type) -> Type {
+        let mut ty = ty.clone();
+        while let Type::Var(var) = ty {
+            match &self.solutions[var as usize] {
+                Some(solution) => ty = solution.clone(),
+                None => break,
+            }
+        }
+        ty
+    }
+
+    /// Apply every solution, all the way down
+    fn resolve(&self, ty: &Type) -> Type {
+        match self.shallow(ty) {
+            Type::Arrow(argument, result) => Type::arrow(self.resolve(&argument), self.resolve(&result)),
+            ty => ty,
+        }
+    }
+
+    /// Make `expected` and `found` equal by solving type variables
+    fn unify(&mut self, expected: &Type, found: &Type) -> Result<(), Clash> {
+        match (self.shallow(expected), self.shallow(found)) {
+            (Type::Var(a), Type::Var(b)) if a == b => Ok(()),
+            (Type::Var(var), ty) | (ty, Type::Var(var)) => {
+                if self.resolve(&ty).mentions(var) {
+                    return Err(Clash::Occurs(var, ty));
+                }
+                self.solutions[var as usize] = Some(ty);
+                Ok(())
+            }
+            (Type::Arrow(a1, r1), Type::Arrow(a2, r2)) => {
+                self.unify(&a1, &a2)?;
+                self.unify(&r1, &r2)
+            }
+            (a, b) if a == b => Ok(()),
+            (a, b) => Err(Clash::Mismatch(a, b)),
+        }
+    }
+
+    fn infer(&mut self, expr: &Expr, scope: &mut Vec<(String, Type)>) -> Result<Type, TypeError> {
+        match expr {
+            Expr::Var(name) => {
+                if let Some((_, ty)) = scope.iter().rev().find(|(bound, _)| bound == name) {
+                    return Ok(ty.clone());
+                }
+                if let Some(value) = self.environment.get(name) {
+                    if self.expanding.contains(name) {
+                        return Err(TypeError::RecursiveBinding { name: name.clone() });
+                    }
+                    // Typing the binding afresh at every use makes it polymorphic
+                    self.expanding.push(name.clone());
+                    let ty = self.infer(value, &mut Vec::new());
+                    self.expanding.pop();
+                    return ty;
+                }
+                if let Some(ty) = self.free.get(name) {
+                    return Ok(ty.clone());
+                }
+                let ty = self.fresh();
+                self.free.insert(name.clone(), ty.clone());
+                Ok(ty)
+            }
+            Expr::Lambda(param, body) => {
+                let argument = self.fresh();
+                scope.push((param.clone(), argument.clone()));
+                let result = self.infer(body, scope);
+                scope.pop();
+                Ok(Type::arrow(argument, result?))
+            }
+            Expr::App(function, argument) => {
+                let function_type = self.infer(function, scope)?;
+                let argument_type = self.infer(argument, scope)?;
+                let (parameter, result) = (self.fresh(), self.fresh());
+                self.unify(&Type::arrow(parameter.clone(), result.clone()), &function_type)
+                    .and_then(|()| self.unify(&parameter, &argument_type))
+                    .map_err(|clash| self.error(clash, expr))?;
+                Ok(result)
+            }
+            // S : (a → b → c) → (a → b) → a → c
+            Expr::S => {
+                let (a, b, c) = (self.fresh(), self.fresh(), self.fresh());
+                Ok(Type::arrow(
+                    Type::arrow(a.clone(), Type::arrow(b.clone(), c.clone())),
+                    Type::arrow(Type::arrow(a.clone(), b), Type::arrow(a, c)),
+                ))
+            }
+            // K : a → b → a
+            Expr::K => {
+                let (a, b) = (self.fresh(), self.fresh());
+                Ok(Type::arrow(a.clone(), Type::arrow(b, a)))
+            }
+            // I : a → a
+            Expr::I => {
+                let a = self.fresh();
+                Ok(Type::arrow(a.clone(), a))
+            }
+            Expr::Sym(_) => Ok(Type::Sym),
+            Expr::Muse(_, _) => Ok(Type::Muse),
+            Expr::DNA(_) => Ok(Type::Dna),
+            // A quine unwraps to its body and applies by applying its body
+            Expr::Quine(inner) => self.infer(inner, scope),
+        }
+    }
+
+    /// Report a clash with both sides resolved and named consistently
+    fn error(&self, clash: Clash, term: &Expr) -> TypeError {
+        let mut names = HashMap::new();
+        match clash {
+            Clash::Mismatch(expected, found) => TypeError::Mismatch {
+                expected: self.resolve(&expected).renumbered(&mut names),
+                found: self.resolve(&found).renumbered(&mut names),
+                term: term.clone(),
+            },
+            Clash::Occurs(var, ty) => TypeError::Infinite {
+                var: Type::Var(var).renumbered(&mut names),
+                ty: self.resolve(&ty).renumbered(&mut names),
+                term: term.clone(),
+            },
+        }
+    }
+}
+
+impl Expr {
+    /// 🏷️ Infer the principal type of a closed term
+    ///
+    /// Free variables are treated as unknowns of any type; use
+    /// `LambdaEngine::infer_type` to type them by their environment bindings.
+    pub fn infer_type(&self) -> Result<Type, TypeError> {
+        infer(self, &HashMap::new())
+    }
+
+    /// True when the term has a type, and so normalizes if it is closed
+    pub fn is_typeable(&self) -> bool {
+        self.infer_type().is_ok()
+    }
+}
+
+impl LambdaEngine {
+    /// 🏷️ Infer the principal type of `expr`, typing free variables by this
+    /// engine's environment
+    ///
+    /// A typed term reaches a normal form under every strategy, although one
+    /// with muses may need more steps than `max_steps` while they evolve.
+    pub fn infer_type(&self, expr: &Expr) -> Result<Type, TypeError> {
+        infer(expr, &self.environment)
+    }
+}
+
+fn infer(expr: &Expr, environment: &HashMap<String, Expr>) -> Result<Type, TypeError> {
+    let mut inference = Inference { environment, solutions: Vec::new(), free: HashMap::new(), expanding: Vec::new() };
+    let ty = inference.infer(expr, &mut Vec::new())?;
+    Ok(inference.resolve(&ty).canonical())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude, Termination};
+
+    fn var(n: u32) -> Type {
+        Type::Var(n)
+    }
+
+    #[test]
+    fn test_combinators_have_their_standard_types() {
+        assert_eq!(Expr::I.infer_type().unwrap(), Type::arrow(var(0), var(0)));
+        assert_eq!(Expr::K.infer_type().unwrap().to_string(), "a → b → a");
+        assert_eq!(Expr::S.infer_type().unwrap().to_string(), "(a → b → c) → (a → b) → a → c");
+
+        // S K K behaves as I, and each K is instantiated separately
+        let skk = Expr::app(Expr::app(Expr::S, Expr::K), Expr::K);
+        assert_eq!(skk.infer_type().unwrap(), Type::arrow(var(0), var(0)));
+
+        let apply = Expr::lambda("f", Expr::lambda("x", Expr::app(Expr::var("f"), Expr::var("x"))));
+        assert_eq!(apply.infer_type().unwrap().to_string(), "(a → b) → a → b");
+        assert_eq!(Expr::app(Expr::K, Expr::muse("🎭", 0.5)).infer_type().unwrap().to_string(), "a → Muse");
+    }
+
+    #[test]
+    fn test_self_application_and_base_types_are_rejected() {
+        let self_app = Expr::lambda("x", Expr::app(Expr::var("x"), Expr::var("x")));
+        assert!(matches!(self_app.infer_type(), Err(TypeError::Infinite { .. })));
+        assert!(!Expr::y_combinator().is_typeable());
+
+        let err = Expr::app(Expr::sym("🌀"), Expr::I).infer_type().unwrap_err();
+        assert!(matches!(err, TypeError::Mismatch { found: Type::Sym, .. }), "{}", err);
+        assert!(err.to_string().contains("Sym"));
+    }
+
+    #[test]
+    fn test_environment_bindings_are_polymorphic_and_checked_for_recursion() {
+        let mut engine = LambdaEngine::new();
+        engine.bind("id", Expr::lambda("x", Expr::var("x")));
+        // id used at two different types
+        let twice = Expr::app(Expr::app(Expr::var("id"), Expr::var("id")), Expr::sym("🌀"));
+        assert_eq!(engine.infer_type(&twice).unwrap(), Type::Sym);
+
+        engine.bind("loop", Expr::app(Expr::var("loop"), Expr::I));
+        assert_eq!(
+            engine.infer_type(&Expr::var("loop")),
+            Err(TypeError::RecursiveBinding { name: "loop".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_typeable_terms_normalize() {
+        let terms = [
+            Expr::app(Expr::app(Expr::app(Expr::S, Expr::K), Expr::K), Expr::sym("🌀")),
+            Expr::app(Expr::app(prelude::add(), prelude::numeral(2)), prelude::numeral(3)),
+            Expr::app(Expr::app(prelude::mul(), prelude::numeral(3)), prelude::numeral(2)),
+            // Muses with low resonance evolve all the way to 1.0
+            Expr::Muse("Sol".to_string(), 9),
+            Expr::app(Expr::app(Expr::K, Expr::Muse("Sol".to_string(), 0)), Expr::Muse("Luna".to_string(), 0)),
+        ];
+        for term in terms {
+            assert!(term.is_typeable(), "{}", term);
+            let trace = LambdaEngine::new().with_max_steps(5000).normalize(term).unwrap();
+            assert_eq!(trace.termination, Termination::NormalForm);
+        }
+    }
+}
@@ -19,11 +19,14 @@ use tower_http::services::ServeDir;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use log::{info, error, warn};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 use minimal_runtime_server::{
     MetaMemeRuntime, PoemRequest, QuineRequest, AnalysisRequest, NFTRequest,
-    GeneratedPoem, QuineResult, AnalysisResult, RuntimeStats,
+    GeneratedPoem, QuineResult, AnalysisResult, RuntimeError, RuntimeStats,
 };
+use lambda_calculus_core::Strategy;
 
 /// 🌟 Application state
 type AppState = Arc<Mutex<MetaMemeRuntime>>;
@@ -40,6 +43,9 @@ struct SessionQuery {
     session_id: Option<String>,
 }
 
+/// ❌ A failed request: its status code and an error body
+type Failure<T> = (StatusCode, Json<ApiResponse<T>>);
+
 /// 🎯 API Response wrapper
 #[derive(Debug, Serialize)]
 struct ApiResponse<T> {
@@ -75,6 +81,12 @@ impl<T> ApiResponse<T> {
     }
 }
 
+/// Turn a runtime error into the status code it maps to
+fn failure<T>(e: RuntimeError) -> Failure<T> {
+    let status = StatusCode::from_u16(e.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, Json(ApiResponse::error(e.to_string())))
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging
@@ -176,7 +188,7 @@ async fn home_handler() -> Html<&'static str> {
         <div class="endpoint">
             <div><span class="method">POST</span> <span class="path">/api/v1/poem</span></div>
             <div class="description">Generate poetry from emoji sequences</div>
-            <div class="example">{"emoji_sequence": "🌀🎭🧬", "session_id": "optional"}</div>
+            <div class="example">{"emoji_sequence": "🌀🎭🧬", "session_id": "optional", "seed": 42}</div>
         </div>
         
         <div class="endpoint">
@@ -243,7 +255,7 @@ curl -X POST http://localhost:3000/api/v1/quine \
         <div class="example">
 curl -X POST http://localhost:3000/api/v1/analyze \
   -H "Content-Type: application/json" \
-  -d '{"emoji_sequence": "🌀🎭", "include_trace": true}'
+  -d '{"emoji_sequence": "🌀🎭", "include_trace": true, "strategy": "call-by-need"}'
         </div>
     </div>
 
@@ -281,13 +293,13 @@ async fn health_handler() -> Json<serde_json::Value> {
 }
 
 /// 📊 Statistics endpoint
-async fn stats_handler(State(state): State<AppState>) -> Result<Json<ApiResponse<RuntimeStats>>, StatusCode> {
+async fn stats_handler(State(state): State<AppState>) -> Result<Json<ApiResponse<RuntimeStats>>, Failure<RuntimeStats>> {
     let runtime = state.lock().unwrap();
     match runtime.get_stats() {
         Ok(stats) => Ok(Json(ApiResponse::success(stats))),
         Err(e) => {
             error!("Failed to get stats: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(failure(e))
         }
     }
 }
@@ -296,13 +308,13 @@ async fn stats_handler(State(state): State<AppState>) -> Result<Json<ApiResponse
 async fn generate_poem_handler(
     State(state): State<AppState>,
     Json(request): Json<PoemRequest>,
-) -> Result<Json<ApiResponse<GeneratedPoem>>, StatusCode> {
+) -> Result<Json<ApiResponse<GeneratedPoem>>, Failure<GeneratedPoem>> {
     let mut runtime = state.lock().unwrap();
     match runtime.generate_poem(request) {
         Ok(poem) => Ok(Json(ApiResponse::success(poem))),
         Err(e) => {
             error!("Failed to generate poem: {}", e);
-            Ok(Json(ApiResponse::error(e.to_string())))
+            Err(failure(e))
         }
     }
 }
@@ -311,13 +323,13 @@ async fn generate_poem_handler(
 async fn create_quine_handler(
     State(state): State<AppState>,
     Json(request): Json<QuineRequest>,
-) -> Result<Json<ApiResponse<QuineResult>>, StatusCode> {
+) -> Result<Json<ApiResponse<QuineResult>>, Failure<QuineResult>> {
     let mut runtime = state.lock().unwrap();
     match runtime.create_quine(request) {
         Ok(quine) => Ok(Json(ApiResponse::success(quine))),
         Err(e) => {
             error!("Failed to create quine: {}", e);
-            Ok(Json(ApiResponse::error(e.to_string())))
+            Err(failure(e))
         }
     }
 }
@@ -326,13 +338,13 @@ async fn create_quine_handler(
 async fn analyze_emoji_handler(
     State(state): State<AppState>,
     Json(request): Json<AnalysisRequest>,
-) -> Result<Json<ApiResponse<AnalysisResult>>, StatusCode> {
+) -> Result<Json<ApiResponse<AnalysisResult>>, Failure<AnalysisResult>> {
     let mut runtime = state.lock().unwrap();
     match runtime.analyze_emoji(request) {
         Ok(analysis) => Ok(Json(ApiResponse::success(analysis))),
         Err(e) => {
             error!("Failed to analyze emoji: {}", e);
-            Ok(Json(ApiResponse::error(e.to_string())))
+            Err(failure(e))
         }
     }
 }
@@ -341,13 +353,13 @@ async fn analyze_emoji_handler(
 async fn generate_nft_handler(
     State(state): State<AppState>,
     Json(request): Json<NFTRequest>,
-) -> Result<Json<ApiResponse<emoji_semantics::NFTMetadata>>, StatusCode> {
+) -> Result<Json<ApiResponse<emoji_semantics::NFTMetadata>>, Failure<emoji_semantics::NFTMetadata>> {
     let mut runtime = state.lock().unwrap();
     match runtime.generate_nft(request) {
         Ok(nft) => Ok(Json(ApiResponse::success(nft))),
         Err(e) => {
             error!("Failed to generate NFT: {}", e);
-            Ok(Json(ApiResponse::error(e.to_string())))
+            Err(failure(e))
         }
     }
 }
@@ -355,13 +367,13 @@ async fn generate_nft_handler(
 /// 👤 Create session endpoint
 async fn create_session_handler(
     State(state): State<AppState>,
-) -> Result<Json<ApiResponse<minimal_runtime_server::Session>>, StatusCode> {
+) -> Result<Json<ApiResponse<minimal_runtime_server::Session>>, Failure<minimal_runtime_server::Session>> {
     let runtime = state.lock().unwrap();
     match runtime.create_session() {
         Ok(session) => Ok(Json(ApiResponse::success(session))),
         Err(e) => {
             error!("Failed to create session: {}", e);
-            Ok(Json(ApiResponse::error(e.to_string())))
+            Err(failure(e))
         }
     }
 }
@@ -370,14 +382,9 @@ async fn create_session_handler(
 async fn get_session_handler(
     State(state): State<AppState>,
     Path(session_id): Path<String>,
-) -> Result<Json<ApiResponse<minimal_runtime_server::Session>>, StatusCode> {
+) -> Result<Json<ApiResponse<minimal_runtime_server::Session>>, Failure<minimal_runtime_server::Session>> {
     let runtime = state.lock().unwrap();
-    let sessions = runtime.sessions.read().unwrap();
-    
-    match sessions.get(&session_id) {
-        Some(session) => Ok(Json(ApiResponse::success(session.clone()))),
-        None => Ok(Json(ApiResponse::error("Session not found".to_string()))),
-    }
+    runtime.get_session(&session_id).map(|session| Json(ApiResponse::success(session))).map_err(failure)
 }
 
 /// 📝 List poems endpoint
@@ -405,14 +412,9 @@ async fn list_poems_handler(
 async fn get_poem_handler(
     State(state): State<AppState>,
     Path(poem_id): Path<String>,
-) -> Result<Json<ApiResponse<GeneratedPoem>>, StatusCode> {
+) -> Result<Json<ApiResponse<GeneratedPoem>>, Failure<GeneratedPoem>> {
     let runtime = state.lock().unwrap();
-    let poems = runtime.poems_cache.read().unwrap();
-    
-    match poems.get(&poem_id) {
-        Some(poem) => Ok(Json(ApiResponse::success(poem.clone()))),
-        None => Ok(Json(ApiResponse::error("Poem not found".to_string()))),
-    }
+    runtime.get_poem(&poem_id).map(|poem| Json(ApiResponse::success(poem))).map_err(failure)
 }
 
 /// 🎨 List NFTs endpoint
@@ -440,14 +442,9 @@ async fn list_nfts_handler(
 async fn get_nft_handler(
     State(state): State<AppState>,
     Path(token_id): Path<u32>,
-) -> Result<Json<ApiResponse<emoji_semantics::NFTMetadata>>, StatusCode> {
+) -> Result<Json<ApiResponse<emoji_semantics::NFTMetadata>>, Failure<emoji_semantics::NFTMetadata>> {
     let runtime = state.lock().unwrap();
-    let nfts = runtime.nft_cache.read().unwrap();
-    
-    match nfts.get(&token_id) {
-        Some(nft) => Ok(Json(ApiResponse::success(nft.clone()))),
-        None => Ok(Json(ApiResponse::error("NFT not found".to_string()))),
-    }
+    runtime.get_nft(token_id).map(|nft| Json(ApiResponse::success(nft))).map_err(failure)
 }
 
 /// 🔄 REPL-style interaction endpoint
@@ -467,13 +464,31 @@ async fn repl_handler(
         cmd if cmd.starts_with(":help") => {
             json!({
                 "type": "help",
-                "message": "Available commands: :help, :stats, :quit, or enter emoji sequences"
+                "message": "Available commands: :help, :stats, :trace <emoji>, :quit, or enter emoji sequences"
             })
         }
+        cmd if cmd.starts_with(":trace") => {
+            let analysis_request = AnalysisRequest {
+                emoji_sequence: cmd.trim_start_matches(":trace").trim().to_string(),
+                include_trace: true,
+                session_id: request.session_id.clone(),
+                strategy: None,
+            };
+            
+            match runtime.analyze_emoji(analysis_request) {
+                Ok(analysis) => json!({
+                    "type": "trace",
+                    "expression": analysis.lambda_expression,
+                    "reduced": analysis.reduced_expression,
+                    "steps": analysis.trace,
+                }),
+                Err(e) => json!({"type": "error", "status": e.status_code(), "message": e.to_string()}),
+            }
+        }
         cmd if cmd.starts_with(":stats") => {
             match runtime.get_stats() {
                 Ok(stats) => json!({"type": "stats", "data": stats}),
-                Err(e) => json!({"type": "error", "message": e.to_string()}),
+                Err(e) => json!({"type": "error", "status": e.status_code(), "message": e.to_string()}),
             }
         }
         cmd if cmd.starts_with(":quit") => {
@@ -485,6 +500,7 @@ async fn repl_handler(
                 emoji_sequence: emoji_sequence.to_string(),
                 include_trace: false,
                 session_id: request.session_id.clone(),
+                strategy: None,
             };
             
             match runtime.analyze_emoji(analysis_request) {
@@ -496,9 +512,14 @@ async fn repl_handler(
                     "resonance": analysis.resonance_score,
                     "steps": analysis.reduction_steps,
                     "output": analysis.output_emoji,
-                    "is_quine": analysis.is_quine
+                    "canonical": analysis.canonical_emoji,
+                    "is_quine": analysis.is_quine,
+                    "quine": analysis.quine,
+                    "value": analysis.value,
+                    "typeable": analysis.typeable,
+                    "type": analysis.principal_type
                 }),
-                Err(e) => json!({"type": "error", "message": e.to_string()}),
+                Err(e) => json!({"type": "error", "status": e.status_code(), "message": e.to_string()}),
             }
         }
     };
@@ -511,6 +532,10 @@ async fn repl_handler(
 struct BatchRequest {
     operations: Vec<BatchOperation>,
     session_id: Option<String>,
+    /// Seed every poem and NFT of the batch from one stream started at this
+    /// seed, leaving the runtime's own streams alone
+    #[serde(default)]
+    seed: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -521,7 +546,11 @@ enum BatchOperation {
     #[serde(rename = "quine")]
     Quine { seed: String },
     #[serde(rename = "analyze")]
-    Analyze { emoji_sequence: String },
+    Analyze {
+        emoji_sequence: String,
+        #[serde(default)]
+        strategy: Option<Strategy>,
+    },
     #[serde(rename = "nft")]
     Nft { emoji_sequence: String },
 }
@@ -533,6 +562,8 @@ async fn batch_handler(
     let mut runtime = state.lock().unwrap();
     let mut results = Vec::new();
     
+    let mut batch_rng = request.seed.map(StdRng::seed_from_u64);
+    
     for (i, operation) in request.operations.iter().enumerate() {
         let result = match operation {
             BatchOperation::Poem { emoji_sequence } => {
@@ -540,11 +571,12 @@ async fn batch_handler(
                     emoji_sequence: emoji_sequence.clone(),
                     session_id: request.session_id.clone(),
                     max_reduction_steps: Some(50),
+                    seed: batch_rng.as_mut().map(|rng| rng.gen()),
                 };
                 
                 match runtime.generate_poem(poem_request) {
                     Ok(poem) => json!({"index": i, "type": "poem", "success": true, "data": poem}),
-                    Err(e) => json!({"index": i, "type": "poem", "success": false, "error": e.to_string()}),
+                    Err(e) => json!({"index": i, "type": "poem", "success": false, "status": e.status_code(), "error": e.to_string()}),
                 }
             }
             BatchOperation::Quine { seed } => {
@@ -556,19 +588,20 @@ async fn batch_handler(
                 
                 match runtime.create_quine(quine_request) {
                     Ok(quine) => json!({"index": i, "type": "quine", "success": true, "data": quine}),
-                    Err(e) => json!({"index": i, "type": "quine", "success": false, "error": e.to_string()}),
+                    Err(e) => json!({"index": i, "type": "quine", "success": false, "status": e.status_code(), "error": e.to_string()}),
                 }
             }
-            BatchOperation::Analyze { emoji_sequence } => {
+            BatchOperation::Analyze { emoji_sequence, strategy } => {
                 let analysis_request = AnalysisRequest {
                     emoji_sequence: emoji_sequence.clone(),
                     include_trace: false,
                     session_id: request.session_id.clone(),
+                    strategy: *strategy,
                 };
                 
                 match runtime.analyze_emoji(analysis_request) {
                     Ok(analysis) => json!({"index": i, "type": "analyze", "success": true, "data": analysis}),
-                    Err(e) => json!({"index": i, "type": "analyze", "success": false, "error": e.to_string()}),
+                    Err(e) => json!({"index": i, "type": "analyze", "success": false, "status": e.status_code(), "error": e.to_string()}),
                 }
             }
             BatchOperation::Nft { emoji_sequence } => {
@@ -576,11 +609,12 @@ async fn batch_handler(
                     emoji_sequence: emoji_sequence.clone(),
                     session_id: request.session_id.clone(),
                     custom_attributes: None,
+                    seed: batch_rng.as_mut().map(|rng| rng.gen()),
                 };
                 
                 match runtime.generate_nft(nft_request) {
                     Ok(nft) => json!({"index": i, "type": "nft", "success": true, "data": nft}),
-                    Err(e) => json!({"index": i, "type": "nft", "success": false, "error": e.to_string()}),
+                    Err(e) => json!({"index": i, "type": "nft", "success": false, "status": e.status_code(), "error": e.to_string()}),
                 }
             }
         };
@@ -592,13 +626,13 @@ async fn batch_handler(
 }
 
 /// 🧹 Cleanup endpoint
-async fn cleanup_handler(State(state): State<AppState>) -> Result<Json<ApiResponse<String>>, StatusCode> {
+async fn cleanup_handler(State(state): State<AppState>) -> Result<Json<ApiResponse<String>>, Failure<String>> {
     let runtime = state.lock().unwrap();
     match runtime.cleanup() {
         Ok(()) => Ok(Json(ApiResponse::success("Cleanup completed successfully".to_string()))),
         Err(e) => {
             error!("Cleanup failed: {}", e);
-            Ok(Json(ApiResponse::error(e.to_string())))
+            Err(failure(e))
         }
     }
 }
@@ -16,13 +16,97 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use serde::{Serialize, Deserialize};
-use anyhow::Result;
+use thiserror::Error;
 use log::{info, debug};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use uuid::Uuid;
 
-use lambda_calculus_core::{Expr, LambdaEngine};
-use emoji_semantics::{EmojiSemantics, NFTMetadata, RarityTier};
-use stanza_universe::{StanzaUniverse};
+use std::time::{Duration, SystemTimeError};
+
+use lambda_calculus_core::{quine, BudgetKind, ChurchValue, EvalBudget, EvalError, Expr, LambdaEngine, QuineVerdict, ReductionMetrics, ReductionStep, Strategy, Termination};
+use emoji_semantics::{EmojiSemantics, NFTMetadata, PoemError, RarityTier, SemanticsError};
+use stanza_universe::{StanzaError, StanzaUniverse};
+
+/// ❌ Why a runtime request failed
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    /// The request itself is malformed
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("session {0} not found")]
+    SessionNotFound(String),
+    #[error("poem {0} not found")]
+    PoemNotFound(String),
+    #[error("NFT #{0} not found")]
+    NftNotFound(u32),
+    /// The emoji poem does not parse cleanly
+    #[error(transparent)]
+    Poem(#[from] PoemError),
+    /// Reduction crossed the request budget
+    #[error(transparent)]
+    Eval(#[from] EvalError),
+    #[error(transparent)]
+    Stanza(#[from] StanzaError),
+    #[error("system clock is set before the Unix epoch")]
+    Clock(#[from] SystemTimeError),
+}
+
+impl From<SemanticsError> for RuntimeError {
+    fn from(error: SemanticsError) -> Self {
+        match error {
+            SemanticsError::Poem(error) => RuntimeError::Poem(error),
+            SemanticsError::Eval(error) => RuntimeError::Eval(error),
+        }
+    }
+}
+
+impl RuntimeError {
+    /// 🚦 The HTTP status code this failure maps to
+    ///
+    /// Malformed requests are 400 and missing resources 404. A poem that
+    /// does not parse, or whose term outgrows the budget, is 422: sending it
+    /// again will fail again. Running out of time is 503, since the same
+    /// request may finish on a less busy server.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            RuntimeError::InvalidRequest(_) => 400,
+            RuntimeError::SessionNotFound(_) | RuntimeError::PoemNotFound(_) | RuntimeError::NftNotFound(_) => 404,
+            RuntimeError::Stanza(StanzaError::UnknownStanza(_)) => 404,
+            RuntimeError::Poem(_) | RuntimeError::Stanza(StanzaError::Poem(_)) => 422,
+            RuntimeError::Eval(EvalError::BudgetExceeded { kind: BudgetKind::Deadline, .. })
+            | RuntimeError::Stanza(StanzaError::Eval(EvalError::BudgetExceeded { kind: BudgetKind::Deadline, .. })) => 503,
+            RuntimeError::Eval(_) | RuntimeError::Stanza(StanzaError::Eval(_)) => 422,
+            RuntimeError::Stanza(_) | RuntimeError::Clock(_) => 500,
+        }
+    }
+}
+
+type Result<T, E = RuntimeError> = std::result::Result<T, E>;
+
+/// Longest emoji sequence, in bytes, accepted before tokenizing
+pub const MAX_POEM_BYTES: usize = 16 * 1024;
+
+/// Most emojis a poem may hold; each one can nest the term a level deeper
+pub const MAX_POEM_EMOJIS: usize = 1024;
+
+/// Reject empty or oversized requests before they reach the engines
+fn require_emoji_sequence(emoji_engine: &EmojiSemantics, emoji_sequence: &str) -> Result<()> {
+    if emoji_sequence.trim().is_empty() {
+        return Err(RuntimeError::InvalidRequest("emoji_sequence must not be empty".to_string()));
+    }
+    if emoji_sequence.len() > MAX_POEM_BYTES {
+        return Err(RuntimeError::InvalidRequest(format!(
+            "emoji_sequence must be at most {} bytes", MAX_POEM_BYTES
+        )));
+    }
+    if emoji_engine.emoji_count(emoji_sequence) > MAX_POEM_EMOJIS {
+        return Err(RuntimeError::InvalidRequest(format!(
+            "emoji_sequence must hold at most {} emojis", MAX_POEM_EMOJIS
+        )));
+    }
+    Ok(())
+}
 
 /// 🌟 The main runtime server state
 pub struct MetaMemeRuntime {
@@ -38,6 +122,10 @@ pub struct MetaMemeRuntime {
     pub poems_cache: Arc<RwLock<HashMap<String, GeneratedPoem>>>,
     /// NFT metadata cache
     pub nft_cache: Arc<RwLock<HashMap<u32, NFTMetadata>>>,
+    /// Resource limits applied to every reduction a request triggers
+    pub request_budget: EvalBudget,
+    /// Source of randomness for poetic text
+    rng: StdRng,
 }
 
 /// 🎭 A user session with the MetaMeme engine
@@ -80,7 +168,12 @@ pub struct QuineResult {
     pub final_expression: String,
     pub output_emoji: String,
     pub reduction_steps: usize,
+    /// True when the quine expression really reduces back to itself
     pub is_perfect_quine: bool,
+    /// How reduction of the quine body ended; a cycle means it reproduces itself
+    pub termination: Termination,
+    /// Exact, cyclic or not a quine, from the fixed-point check
+    pub verdict: QuineVerdict,
     pub created_at: u64,
 }
 
@@ -90,6 +183,10 @@ pub struct NFTRequest {
     pub emoji_sequence: String,
     pub session_id: Option<String>,
     pub custom_attributes: Option<HashMap<String, String>>,
+    /// Draw the metadata's randomness from a stream started at this seed,
+    /// leaving the runtime's own streams alone
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 /// 📊 Runtime statistics
@@ -112,6 +209,10 @@ pub struct PoemRequest {
     pub emoji_sequence: String,
     pub session_id: Option<String>,
     pub max_reduction_steps: Option<usize>,
+    /// Draw the poem's randomness from a stream started at this seed,
+    /// leaving the runtime's own streams alone
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,6 +227,9 @@ pub struct AnalysisRequest {
     pub emoji_sequence: String,
     pub include_trace: bool,
     pub session_id: Option<String>,
+    /// Evaluation strategy to reduce with (the runtime's engine default when absent)
+    #[serde(default)]
+    pub strategy: Option<Strategy>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -137,9 +241,26 @@ pub struct AnalysisResult {
     pub reduced_expression: String,
     pub reduction_steps: usize,
     pub is_normal_form: bool,
+    pub termination: Termination,
     pub output_emoji: String,
+    /// Lossless encoding of the reduced term, see `Expr::to_emoji_code`
+    pub canonical_emoji: String,
     pub is_quine: bool,
-    pub trace: Option<Vec<String>>,
+    /// Exact, cyclic or not a quine, from the fixed-point check
+    pub quine: QuineVerdict,
+    /// Contracted redexes with rule names and paths, when requested
+    pub trace: Option<Vec<ReductionStep>>,
+    pub strategy: Strategy,
+    /// Church numeral, boolean, pair or list the normal form encodes, if any
+    pub value: Option<ChurchValue>,
+    /// True when the expression has a type, so that it normalizes
+    pub typeable: bool,
+    /// Principal type of the expression, when it has one
+    pub principal_type: Option<String>,
+    /// Why the expression has no type
+    pub type_error: Option<String>,
+    /// Term size, depth and redexes at every step
+    pub metrics: ReductionMetrics,
 }
 
 impl Default for MetaMemeRuntime {
@@ -151,16 +272,53 @@ impl Default for MetaMemeRuntime {
 impl MetaMemeRuntime {
     /// Create a new MetaMeme runtime
     pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    
+    /// 🎲 Create a runtime whose poems, NFTs and evolution are reproducible
+    pub fn new_with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+    
+    fn with_rng(mut rng: StdRng) -> Self {
         info!("🚀 Initializing SOLFUNMEME MetaMeme Runtime...");
         
         Self {
-            lambda_engine: LambdaEngine::new(),
-            emoji_engine: EmojiSemantics::new(),
-            stanza_universe: StanzaUniverse::new(),
+            lambda_engine: LambdaEngine::new().with_seed(rng.gen()),
+            emoji_engine: EmojiSemantics::new_with_seed(rng.gen()),
+            stanza_universe: StanzaUniverse::new_with_seed(rng.gen()),
             sessions: Arc::new(RwLock::new(HashMap::new())),
             poems_cache: Arc::new(RwLock::new(HashMap::new())),
             nft_cache: Arc::new(RwLock::new(HashMap::new())),
+            request_budget: EvalBudget::unlimited(),
+            rng,
         }
+        .with_request_budget(Self::default_request_budget())
+    }
+    
+    /// Restart every random stream of the runtime and its engines from `seed`
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.lambda_engine.reseed(self.rng.gen());
+        self.emoji_engine.reseed(self.rng.gen());
+        self.stanza_universe.reseed(self.rng.gen());
+    }
+    
+    /// ⏳ Limits that keep one request from exhausting the process:
+    /// 100k-node terms, 5k levels of nesting and two seconds per reduction
+    pub fn default_request_budget() -> EvalBudget {
+        EvalBudget::unlimited()
+            .with_max_term_size(100_000)
+            .with_max_depth(5_000)
+            .with_deadline(Duration::from_secs(2))
+    }
+    
+    /// Replace the per-request budget on every engine the runtime reduces with
+    pub fn with_request_budget(mut self, budget: EvalBudget) -> Self {
+        self.request_budget = budget;
+        self.lambda_engine.budget = budget;
+        self.emoji_engine.lambda_engine.budget = budget;
+        self
     }
     
     /// Create a new user session
@@ -209,9 +367,28 @@ impl MetaMemeRuntime {
         }
     }
     
+    /// Look up an existing session
+    pub fn get_session(&self, session_id: &str) -> Result<Session> {
+        self.sessions.read().unwrap().get(session_id).cloned()
+            .ok_or_else(|| RuntimeError::SessionNotFound(session_id.to_string()))
+    }
+    
+    /// Look up a generated poem
+    pub fn get_poem(&self, poem_id: &str) -> Result<GeneratedPoem> {
+        self.poems_cache.read().unwrap().get(poem_id).cloned()
+            .ok_or_else(|| RuntimeError::PoemNotFound(poem_id.to_string()))
+    }
+    
+    /// Look up generated NFT metadata
+    pub fn get_nft(&self, token_id: u32) -> Result<NFTMetadata> {
+        self.nft_cache.read().unwrap().get(&token_id).cloned()
+            .ok_or(RuntimeError::NftNotFound(token_id))
+    }
+    
     /// Generate a poem from emoji sequence
     pub fn generate_poem(&mut self, request: PoemRequest) -> Result<GeneratedPoem> {
         debug!("🎭 Generating poem from: {}", request.emoji_sequence);
+        require_emoji_sequence(&self.emoji_engine, &request.emoji_sequence)?;
         
         let session = self.get_or_create_session(request.session_id)?;
         
@@ -227,13 +404,16 @@ impl MetaMemeRuntime {
         let trace = self.lambda_engine.normalize(expr.clone())?;
         
         // Generate poetic text
-        let poetic_text = self.generate_poetic_text(&expr, resonance);
+        let poetic_text = match request.seed {
+            Some(seed) => Self::generate_poetic_text(&mut StdRng::seed_from_u64(seed), &expr, resonance),
+            None => Self::generate_poetic_text(&mut self.rng, &expr, resonance),
+        };
         
         // Convert back to emoji
         let output_emoji = self.emoji_engine.expr_to_emoji(&trace.final_form);
         
         // Determine rarity
-        let rarity_tier = self.calculate_rarity(resonance, request.emoji_sequence.chars().count());
+        let rarity_tier = self.calculate_rarity(resonance, self.emoji_engine.emoji_count(&request.emoji_sequence));
         
         let poem_id = Uuid::new_v4().to_string();
         let now = std::time::SystemTime::now()
@@ -250,7 +430,7 @@ impl MetaMemeRuntime {
             poetic_text,
             resonance_score: resonance,
             reduction_steps: trace.step_count,
-            is_quine: output_emoji == request.emoji_sequence,
+            is_quine: quine::classify(&expr, &trace).is_quine(),
             created_at: now,
             rarity_tier,
         };
@@ -259,6 +439,7 @@ impl MetaMemeRuntime {
         self.poems_cache.write().unwrap().insert(poem_id.clone(), poem.clone());
         
         // Update session stats
+        let tokens = self.emoji_engine.tokenize(&request.emoji_sequence);
         let mut sessions = self.sessions.write().unwrap();
         if let Some(mut session) = sessions.get(&session.id).cloned() {
             session.poems_generated += 1;
@@ -266,10 +447,9 @@ impl MetaMemeRuntime {
             session.last_activity = now;
             
             // Track favorite emojis
-            for emoji in request.emoji_sequence.chars() {
-                let emoji_str = emoji.to_string();
-                if !session.favorite_emojis.contains(&emoji_str) {
-                    session.favorite_emojis.push(emoji_str);
+            for token in tokens {
+                if !session.favorite_emojis.contains(&token.text) {
+                    session.favorite_emojis.push(token.text);
                 }
             }
             
@@ -295,7 +475,8 @@ impl MetaMemeRuntime {
         let trace = self.lambda_engine.normalize(quine_expr.clone())?;
         let output_emoji = self.emoji_engine.expr_to_emoji(&trace.final_form);
         
-        let is_perfect_quine = output_emoji.contains(&request.seed);
+        let verdict = quine::classify(&quine_expr, &trace);
+        let is_perfect_quine = verdict.is_quine();
         
         let quine_id = Uuid::new_v4().to_string();
         let now = std::time::SystemTime::now()
@@ -311,6 +492,8 @@ impl MetaMemeRuntime {
             output_emoji,
             reduction_steps: trace.step_count,
             is_perfect_quine,
+            termination: trace.termination,
+            verdict,
             created_at: now,
         };
         
@@ -329,36 +512,55 @@ impl MetaMemeRuntime {
     /// Analyze an emoji sequence
     pub fn analyze_emoji(&mut self, request: AnalysisRequest) -> Result<AnalysisResult> {
         debug!("🔍 Analyzing emoji sequence: {}", request.emoji_sequence);
+        require_emoji_sequence(&self.emoji_engine, &request.emoji_sequence)?;
         
         let _session = self.get_or_create_session(request.session_id)?;
         
         let (expr, resonance) = self.emoji_engine.interpret_emoji_poem(&request.emoji_sequence)?;
-        let trace = self.lambda_engine.normalize(expr.clone())?;
+        let strategy = request.strategy.unwrap_or(self.lambda_engine.strategy);
+        let mut engine = self.lambda_engine.clone().with_strategy(strategy);
+        let trace = engine.normalize(expr.clone())?;
         let output_emoji = self.emoji_engine.expr_to_emoji(&trace.final_form);
+        let quine = quine::classify(&expr, &trace);
+        let typed = engine.infer_type(&expr);
         
-        let trace_strings = if request.include_trace {
-            Some(trace.steps.iter().map(|step| format!("{}", step)).collect())
+        let trace_steps = if request.include_trace {
+            Some(trace.steps())
         } else {
             None
         };
+        let value = if trace.is_normal_form { engine.decode(&trace.final_form) } else { None };
+        if let Some(value) = &value {
+            debug!("🔢 {} evaluates to {}", request.emoji_sequence, value.describe());
+        }
         
         Ok(AnalysisResult {
             input: request.emoji_sequence.clone(),
-            emoji_count: request.emoji_sequence.chars().count(),
+            emoji_count: self.emoji_engine.emoji_count(&request.emoji_sequence),
             resonance_score: resonance,
             lambda_expression: format!("{}", expr),
             reduced_expression: format!("{}", trace.final_form),
             reduction_steps: trace.step_count,
             is_normal_form: trace.is_normal_form,
-            output_emoji: output_emoji.clone(),
-            is_quine: output_emoji == request.emoji_sequence,
-            trace: trace_strings,
+            termination: trace.termination,
+            output_emoji,
+            canonical_emoji: trace.final_form.to_emoji_code(),
+            is_quine: quine.is_quine(),
+            quine,
+            trace: trace_steps,
+            strategy: trace.strategy,
+            value,
+            typeable: typed.is_ok(),
+            principal_type: typed.as_ref().ok().map(ToString::to_string),
+            type_error: typed.err().map(|error| error.to_string()),
+            metrics: trace.metrics,
         })
     }
     
     /// Generate NFT metadata
     pub fn generate_nft(&mut self, request: NFTRequest) -> Result<NFTMetadata> {
         debug!("🎨 Generating NFT for: {}", request.emoji_sequence);
+        require_emoji_sequence(&self.emoji_engine, &request.emoji_sequence)?;
         
         let session = self.get_or_create_session(request.session_id)?;
         
@@ -368,10 +570,17 @@ impl MetaMemeRuntime {
             (nft_cache.len() as u32) + 1
         };
         
-        let mut metadata = self.emoji_engine.generate_nft_metadata(&request.emoji_sequence, token_id)?;
+        let mut metadata = match request.seed {
+            Some(seed) => self
+                .emoji_engine
+                .with_seeded_rng(seed, |engine| engine.generate_nft_metadata(&request.emoji_sequence, token_id))?,
+            None => self.emoji_engine.generate_nft_metadata(&request.emoji_sequence, token_id)?,
+        };
         
-        // Add custom attributes if provided
+        // Add custom attributes if provided, in key order so output is stable
         if let Some(custom_attrs) = request.custom_attributes {
+            let mut custom_attrs: Vec<(String, String)> = custom_attrs.into_iter().collect();
+            custom_attrs.sort();
             for (key, value) in custom_attrs {
                 metadata.attributes.push(emoji_semantics::NFTAttribute {
                     trait_type: key,
@@ -423,15 +632,15 @@ impl MetaMemeRuntime {
         };
         
         // Find most popular emoji
-        let mut emoji_counts: HashMap<char, usize> = HashMap::new();
+        let mut emoji_counts: HashMap<String, usize> = HashMap::new();
         for poem in poems.values() {
-            for emoji in poem.input_emoji.chars() {
-                *emoji_counts.entry(emoji).or_insert(0) += 1;
+            for token in self.emoji_engine.tokenize(&poem.input_emoji) {
+                *emoji_counts.entry(token.text).or_insert(0) += 1;
             }
         }
         let most_popular_emoji = emoji_counts.iter()
             .max_by_key(|(_, count)| *count)
-            .map(|(emoji, _)| emoji.to_string())
+            .map(|(emoji, _)| emoji.clone())
             .unwrap_or_else(|| "🌀".to_string());
         
         // Calculate total quines
@@ -456,7 +665,7 @@ impl MetaMemeRuntime {
     }
     
     /// Generate poetic text from expression
-    fn generate_poetic_text(&self, expr: &Expr, resonance: f64) -> String {
+    fn generate_poetic_text(rng: &mut StdRng, expr: &Expr, resonance: f64) -> String {
         let base_verses = vec![
             "In the metaprotocol's dance, where lambda meets the light,\nThrough recursive dreams and combinatorial flight,",
             "Digital muses stir in silicon dreams,\nWhere poetry flows in data streams,",
@@ -465,8 +674,6 @@ impl MetaMemeRuntime {
             "Where S-combinators weave their spell,\nAnd K-combinators guard truth well,",
         ];
         
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
         let base = base_verses[rng.gen_range(0..base_verses.len())];
         
         let resonance_line = match resonance {
@@ -547,6 +754,7 @@ mod tests {
             emoji_sequence: "🌀🎭".to_string(),
             session_id: None,
             max_reduction_steps: Some(10),
+            seed: None,
         };
         
         let poem = runtime.generate_poem(request).unwrap();
@@ -572,6 +780,33 @@ mod tests {
         assert!(quine.reduction_steps >= 0);
     }
     
+    #[test]
+    fn test_analysis_verifies_quines_over_terms() {
+        let mut runtime = MetaMemeRuntime::new();
+        let mut analyze = |poem: &str| {
+            runtime.analyze_emoji(AnalysisRequest {
+                emoji_sequence: poem.to_string(),
+                include_trace: false,
+                session_id: None,
+                strategy: None,
+            }).unwrap()
+        };
+        
+        // Ω written with the poem grammar reduces to itself in one step
+        let omega = analyze("🌛🐑🐱🐱🐱🌜🌛🐑🐱🐱🐱🌜");
+        assert!(omega.is_quine);
+        assert!(matches!(omega.quine, QuineVerdict::Exact { .. }));
+        assert!(!omega.typeable);
+        assert!(omega.type_error.unwrap().contains("infinite type"));
+        
+        // A normal form renders back to the same emojis but does not reproduce itself
+        let identity = analyze("💫");
+        assert_eq!(identity.output_emoji, "💫");
+        assert!(!identity.is_quine);
+        assert!(identity.typeable);
+        assert_eq!(identity.principal_type.as_deref(), Some("a → a"));
+    }
+    
     #[test]
     fn test_emoji_analysis() {
         let mut runtime = MetaMemeRuntime::new();
@@ -579,6 +814,7 @@ mod tests {
             emoji_sequence: "🌀🎭🧬".to_string(),
             include_trace: true,
             session_id: None,
+            strategy: None,
         };
         
         let analysis = runtime.analyze_emoji(request).unwrap();
@@ -586,7 +822,104 @@ mod tests {
         assert_eq!(analysis.input, "🌀🎭🧬");
         assert_eq!(analysis.emoji_count, 3);
         assert!(analysis.resonance_score > 0.0);
-        assert!(analysis.trace.is_some());
+        assert_eq!(analysis.trace.map(|steps| steps.len()), Some(analysis.reduction_steps));
+        assert_eq!(analysis.strategy, Strategy::NormalOrder);
+        
+        let analysis = runtime.analyze_emoji(AnalysisRequest {
+            emoji_sequence: "⚛️👍🏽".to_string(),
+            include_trace: false,
+            session_id: None,
+            strategy: None,
+        }).unwrap();
+        assert_eq!(analysis.emoji_count, 2);
+        // K applied to an unknown emoji is stuck, and the canonical code keeps it exactly
+        assert!(!analysis.is_quine);
+        let reduced = Expr::from_emoji_code(&analysis.canonical_emoji).unwrap();
+        assert_eq!(reduced.to_string(), analysis.reduced_expression);
+    }
+    
+    #[test]
+    fn test_request_budget_rejects_oversized_terms() {
+        let mut runtime = MetaMemeRuntime::new()
+            .with_request_budget(EvalBudget::unlimited().with_max_term_size(1));
+        let request = AnalysisRequest {
+            emoji_sequence: "🌀🎭🧬".to_string(),
+            include_trace: false,
+            session_id: None,
+            strategy: None,
+        };
+        
+        let err = runtime.analyze_emoji(request).unwrap_err();
+        assert!(matches!(
+            err,
+            RuntimeError::Eval(EvalError::BudgetExceeded { kind: BudgetKind::TermSize, .. })
+        ));
+        assert_eq!(err.status_code(), 422);
+    }
+    
+    #[test]
+    fn test_errors_map_to_status_codes() {
+        let mut runtime = MetaMemeRuntime::new();
+        let analyze = |runtime: &mut MetaMemeRuntime, poem: &str| runtime.analyze_emoji(AnalysisRequest {
+            emoji_sequence: poem.to_string(),
+            include_trace: false,
+            session_id: None,
+            strategy: None,
+        });
+        
+        let empty = analyze(&mut runtime, "  ").unwrap_err();
+        assert!(matches!(empty, RuntimeError::InvalidRequest(_)));
+        assert_eq!(empty.status_code(), 400);
+        
+        let long = analyze(&mut runtime, &"🐱".repeat(30_000)).unwrap_err();
+        assert!(matches!(long, RuntimeError::InvalidRequest(_)));
+        assert_eq!(long.status_code(), 400);
+        
+        let many = analyze(&mut runtime, &"🐱".repeat(MAX_POEM_EMOJIS + 1)).unwrap_err();
+        assert!(matches!(many, RuntimeError::InvalidRequest(_)));
+        
+        let unbalanced = analyze(&mut runtime, "🌀🌜").unwrap_err();
+        assert!(matches!(unbalanced, RuntimeError::Poem(_)));
+        assert_eq!(unbalanced.status_code(), 422);
+        
+        let missing = runtime.get_session("no-such-session").unwrap_err();
+        assert!(matches!(missing, RuntimeError::SessionNotFound(_)));
+        assert_eq!(missing.status_code(), 404);
+        assert_eq!(runtime.get_nft(404).unwrap_err().status_code(), 404);
+        
+        let mut slow = MetaMemeRuntime::new().with_request_budget(EvalBudget::unlimited().with_deadline(Duration::ZERO));
+        assert_eq!(analyze(&mut slow, "🌀🎭🧬").unwrap_err().status_code(), 503);
+    }
+    
+    #[test]
+    fn test_emoji_analysis_with_strategy() {
+        let mut runtime = MetaMemeRuntime::new();
+        let request: AnalysisRequest = serde_json::from_str(
+            r#"{"emoji_sequence": "🌀🎭🧬", "include_trace": false, "session_id": null, "strategy": "weak-head"}"#,
+        ).unwrap();
+        
+        let analysis = runtime.analyze_emoji(request).unwrap();
+        
+        assert_eq!(analysis.strategy, Strategy::WeakHead);
+        assert_eq!(runtime.lambda_engine.strategy, Strategy::NormalOrder);
+    }
+    
+    #[test]
+    fn test_emoji_analysis_decodes_church_values() {
+        let mut runtime = MetaMemeRuntime::new();
+        let mut analyze = |emoji: &str| {
+            runtime.analyze_emoji(AnalysisRequest {
+                emoji_sequence: emoji.to_string(),
+                include_trace: false,
+                session_id: None,
+                strategy: None,
+            }).unwrap().value
+        };
+        
+        // 🔮💫 is K I, the numeral zero; 🌀🔮🔮 is S K K, the identity, η-equal to one
+        assert_eq!(analyze("🔮💫"), Some(ChurchValue::Number(0)));
+        assert_eq!(analyze("🌀🔮🔮").map(|value| value.describe()), Some("the number 1".to_string()));
+        assert_eq!(analyze("🎭"), None);
     }
     
     #[test]
@@ -600,6 +933,7 @@ mod tests {
                 attrs.insert("Creator".to_string(), "Test".to_string());
                 attrs
             }),
+            seed: None,
         };
         
         let nft = runtime.generate_nft(request).unwrap();
@@ -612,6 +946,54 @@ mod tests {
         assert!(nft.attributes.iter().any(|attr| attr.trait_type == "Creator"));
     }
     
+    #[test]
+    fn test_seeded_requests_are_reproducible() {
+        let generate = |seed| {
+            let mut runtime = MetaMemeRuntime::new();
+            let poem = runtime.generate_poem(PoemRequest {
+                emoji_sequence: "🌀🎭".to_string(),
+                session_id: None,
+                max_reduction_steps: Some(10),
+                seed: Some(seed),
+            }).unwrap();
+            let nft = runtime.generate_nft(NFTRequest {
+                emoji_sequence: "🌀🎭🧬".to_string(),
+                session_id: None,
+                custom_attributes: None,
+                seed: Some(seed),
+            }).unwrap();
+            (poem.poetic_text, serde_json::to_string(&nft).unwrap())
+        };
+        
+        assert_eq!(generate(9901), generate(9901));
+    }
+    
+    #[test]
+    fn test_seeded_requests_leave_the_runtime_streams_alone() {
+        let unseeded = |runtime: &mut MetaMemeRuntime| {
+            runtime.generate_nft(NFTRequest {
+                emoji_sequence: "🌀🎭🧬".to_string(),
+                session_id: None,
+                custom_attributes: None,
+                seed: None,
+            }).unwrap().description
+        };
+        let mut undisturbed = MetaMemeRuntime::new_with_seed(7);
+        let mut disturbed = MetaMemeRuntime::new_with_seed(7);
+        for seed in 0..8 {
+            disturbed.generate_nft(NFTRequest {
+                emoji_sequence: "🌀🎭".to_string(),
+                session_id: None,
+                custom_attributes: None,
+                seed: Some(seed),
+            }).unwrap();
+        }
+        
+        for _ in 0..4 {
+            assert_eq!(unseeded(&mut undisturbed), unseeded(&mut disturbed));
+        }
+    }
+    
     #[test]
     fn test_stats_generation() {
         let mut runtime = MetaMemeRuntime::new();
@@ -621,6 +1003,7 @@ mod tests {
             emoji_sequence: "🌀🎭".to_string(),
             session_id: None,
             max_reduction_steps: Some(10),
+            seed: None,
         };
         runtime.generate_poem(poem_request).unwrap();
         
@@ -5,13 +5,48 @@
 //! and create the foundation for our 9,901 NFT collection.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use serde::{Serialize, Deserialize};
-use anyhow::{Result, anyhow};
+use thiserror::Error;
 use log::{debug, info};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
-use lambda_calculus_core::LambdaEngine;
-use emoji_semantics::{EmojiSemantics, RarityTier};
+use lambda_calculus_core::genetic::Resonance;
+use lambda_calculus_core::{EvalError, Expr, Fitness, LambdaEngine, ParseError};
+use emoji_semantics::{EmojiSemantics, PoemError, RarityTier};
+
+pub mod lineage;
+pub mod storage;
+
+pub use lineage::{LineageGraph, LineageRecord, MutationOp};
+
+use storage::Journal;
+
+/// ❌ Why a universe operation failed
+#[derive(Debug, Error)]
+pub enum StanzaError {
+    #[error("stanza #{0} not found")]
+    UnknownStanza(u32),
+    /// A stanza's emoji sequence does not parse cleanly
+    #[error(transparent)]
+    Poem(#[from] PoemError),
+    #[error(transparent)]
+    Eval(#[from] EvalError),
+    /// A stored lambda expression does not parse
+    #[error("stanza #{id} has an unreadable expression: {source}")]
+    Expression { id: u32, source: ParseError },
+    #[error("cannot access {path}: {source}")]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("invalid universe {path}: {source}")]
+    InvalidSnapshot { path: PathBuf, source: serde_json::Error },
+    #[error("corrupt journal {path} at line {line}: {source}")]
+    CorruptJournal { path: PathBuf, line: usize, source: serde_json::Error },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+type Result<T, E = StanzaError> = std::result::Result<T, E>;
 
 /// 🎭 A single stanza in our poetic universe
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +69,19 @@ pub struct Stanza {
     pub recursion_depth: u32,
     /// Self-replication capability
     pub is_quine: bool,
+    /// The stanza this one was bred from, if any
+    #[serde(default)]
+    pub parent_id: Option<u32>,
+    /// Breeding generation; 0 for stanzas created directly
+    #[serde(default)]
+    pub generation: u32,
+}
+
+impl Stanza {
+    /// Reload the stored lambda expression as an `Expr`
+    pub fn parse_lambda_expr(&self) -> Result<Expr, ParseError> {
+        Expr::parse(&self.lambda_expr)
+    }
 }
 
 /// 🌌 The complete universe of stanzas
@@ -48,6 +96,12 @@ pub struct StanzaUniverse {
     pub lambda_engine: LambdaEngine,
     /// Next available stanza ID
     pub next_id: u32,
+    /// Parents, generations and mutations of every stanza
+    pub lineage: LineageGraph,
+    /// Append-only record of new stanzas, when one is attached
+    journal: Option<Journal>,
+    /// Source of randomness for evolution
+    rng: StdRng,
 }
 
 impl Default for StanzaUniverse {
@@ -59,16 +113,39 @@ impl Default for StanzaUniverse {
 impl StanzaUniverse {
     /// Create a new stanza universe
     pub fn new() -> Self {
-        let mut universe = Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    
+    /// 🎲 Create a universe whose evolution is reproducible
+    pub fn new_with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+    
+    fn with_rng(rng: StdRng) -> Self {
+        let mut universe = Self::empty(rng);
+        universe.initialize_core_stanzas();
+        universe
+    }
+    
+    /// A universe without any stanzas, for `load` to fill
+    fn empty(mut rng: StdRng) -> Self {
+        Self {
             stanzas: HashMap::new(),
             emoji_to_stanza: HashMap::new(),
-            emoji_engine: EmojiSemantics::new(),
-            lambda_engine: LambdaEngine::new(),
+            emoji_engine: EmojiSemantics::new_with_seed(rng.gen()),
+            lambda_engine: LambdaEngine::new().with_seed(rng.gen()),
             next_id: 1,
-        };
-        
-        universe.initialize_core_stanzas();
-        universe
+            lineage: LineageGraph::new(),
+            journal: None,
+            rng,
+        }
+    }
+    
+    /// Restart every random stream of the universe and its engines from `seed`
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.emoji_engine.reseed(self.rng.gen());
+        self.lambda_engine.reseed(self.rng.gen());
     }
     
     /// Initialize the core foundational stanzas
@@ -114,14 +191,40 @@ impl StanzaUniverse {
         is_quine: bool,
         recursion_depth: u32,
     ) -> Result<u32> {
-        let id = self.next_id;
-        self.next_id += 1;
-        
+        let id = self.insert_stanza(text, emoji_sequence, resonance, is_quine, recursion_depth)?;
+        self.journal_stanza(id)?;
+        Ok(id)
+    }
+    
+    /// Add a stanza without journaling it, so callers can finish it first
+    fn insert_stanza(
+        &mut self,
+        text: &str,
+        emoji_sequence: &str,
+        resonance: f64,
+        is_quine: bool,
+        recursion_depth: u32,
+    ) -> Result<u32> {
         // Interpret the emoji sequence as a lambda expression
         let (lambda_expr, _) = self.emoji_engine.interpret_emoji_poem(emoji_sequence)?;
+        Ok(self.insert_expr_stanza(text, emoji_sequence, &lambda_expr, resonance, is_quine, recursion_depth))
+    }
+    
+    /// Add a stanza whose expression is already known, without journaling it
+    fn insert_expr_stanza(
+        &mut self,
+        text: &str,
+        emoji_sequence: &str,
+        lambda_expr: &Expr,
+        resonance: f64,
+        is_quine: bool,
+        recursion_depth: u32,
+    ) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
         
         // Calculate rarity based on resonance and complexity
-        let rarity = self.calculate_stanza_rarity(resonance, emoji_sequence.chars().count(), recursion_depth);
+        let rarity = self.calculate_stanza_rarity(resonance, self.emoji_engine.emoji_count(emoji_sequence), recursion_depth);
         
         let stanza = Stanza {
             id,
@@ -133,13 +236,23 @@ impl StanzaUniverse {
             program_id: None, // Will be set when deployed to Solana
             recursion_depth,
             is_quine,
+            parent_id: None,
+            generation: 0,
         };
         
+        self.lineage.insert(LineageRecord {
+            stanza_id: id,
+            emoji_sequence: stanza.emoji_sequence.clone(),
+            parents: Vec::new(),
+            generation: 0,
+            mutations: Vec::new(),
+            fitness_delta: 0.0,
+        });
         self.stanzas.insert(id, stanza);
         self.emoji_to_stanza.insert(emoji_sequence.to_string(), id);
         
         debug!("📜 Created stanza #{} with resonance {:.3}", id, resonance);
-        Ok(id)
+        id
     }
     
     /// Calculate rarity tier for a stanza
@@ -169,41 +282,91 @@ impl StanzaUniverse {
         }
     }
     
-    /// Generate a new stanza through evolution
+    /// Generate a new stanza through evolution, recording its change in resonance fitness
     pub fn evolve_stanza(&mut self, parent_id: u32, mutation_rate: f64) -> Result<u32> {
         let parent = self.get_stanza(parent_id)
-            .ok_or_else(|| anyhow!("Parent stanza {} not found", parent_id))?
-            .clone();
+            .ok_or(StanzaError::UnknownStanza(parent_id))?;
         
-        // Re-interpret the parent's emoji sequence to get the lambda expression
-        let (parent_expr, _) = self.emoji_engine.interpret_emoji_poem(&parent.emoji_sequence)?;
+        // Bred stanzas carry canonical emoji codes, so read the stored expression
+        let parent_expr = parent
+            .parse_lambda_expr()
+            .map_err(|source| StanzaError::Expression { id: parent_id, source })?;
         
         // Evolve the lambda expression
         let evolved_expr = self.lambda_engine.evolve(&parent_expr, mutation_rate)?;
+        let fitness_delta = Resonance.score(&evolved_expr, &self.lambda_engine) - Resonance.score(&parent_expr, &self.lambda_engine);
+        
+        self.breed_stanza(parent_id, None, &evolved_expr, mutation_rate, fitness_delta)
+    }
+    
+    /// 🐣 Add a stanza for an expression bred from `parent_id`, crossed over
+    /// with `partner_id` if given
+    ///
+    /// The new stanza stores `offspring` exactly, with its canonical emoji
+    /// code as the emoji sequence. It inherits the parent's text (mutated at
+    /// `mutation_rate`), resonance, quine flag and one more level of
+    /// recursion depth, and its lineage is recorded with `fitness_delta`, the
+    /// offspring's fitness minus the parent's under the fitness that bred it.
+    pub fn breed_stanza(
+        &mut self,
+        parent_id: u32,
+        partner_id: Option<u32>,
+        offspring: &Expr,
+        mutation_rate: f64,
+        fitness_delta: f64,
+    ) -> Result<u32> {
+        let parent = self.get_stanza(parent_id)
+            .ok_or(StanzaError::UnknownStanza(parent_id))?
+            .clone();
         
-        // Convert back to emoji
-        let new_emoji = self.emoji_engine.expr_to_emoji(&evolved_expr);
+        // The lossless code, so the emoji sequence decodes back to the offspring
+        let new_emoji = offspring.to_emoji_code();
         
         // Generate evolved poetic text
         let evolved_text = self.evolve_poetic_text(&parent.text, mutation_rate);
         
         // Create the evolved stanza
-        let new_resonance = (parent.resonance + rand::thread_rng().gen_range(-0.05..0.05)).clamp(0.0, 1.0);
+        let new_resonance = (parent.resonance + self.rng.gen_range(-0.05..0.05)).clamp(0.0, 1.0);
         
-        self.create_stanza(
+        let id = self.insert_expr_stanza(
             &evolved_text,
             &new_emoji,
+            offspring,
             new_resonance,
             parent.is_quine,
             parent.recursion_depth + 1,
-        )
+        );
+        
+        let generation = parent.generation + 1;
+        let stanza = self.stanzas.get_mut(&id).expect("stanza was just created");
+        stanza.parent_id = Some(parent_id);
+        stanza.generation = generation;
+        
+        let mut mutations: Vec<MutationOp> = partner_id.map(|partner| MutationOp::Crossover { partner }).into_iter().collect();
+        if stanza.lambda_expr != parent.lambda_expr {
+            mutations.push(MutationOp::Expression { rate: mutation_rate });
+        }
+        if evolved_text != parent.text {
+            mutations.push(MutationOp::Text);
+        }
+        self.lineage.insert(LineageRecord {
+            stanza_id: id,
+            emoji_sequence: new_emoji,
+            parents: std::iter::once(parent_id).chain(partner_id).collect(),
+            generation,
+            mutations,
+            fitness_delta,
+        });
+        
+        self.journal_stanza(id)?;
+        
+        debug!("🌳 Stanza #{} is generation {} of #{}", id, generation, parent_id);
+        Ok(id)
     }
     
     /// Evolve poetic text through linguistic mutation
-    fn evolve_poetic_text(&self, original: &str, mutation_rate: f64) -> String {
-        let mut rng = rand::thread_rng();
-        
-        if rng.gen::<f64>() < mutation_rate {
+    fn evolve_poetic_text(&mut self, original: &str, mutation_rate: f64) -> String {
+        if self.rng.gen::<f64>() < mutation_rate {
             let variations = vec![
                 original.replace("Lambda", "Combinator"),
                 original.replace("dreams", "visions"),
@@ -213,9 +376,44 @@ impl StanzaUniverse {
                 format!("{}\nEvolved through digital mutation,\nA new form of computation.", original),
             ];
             
-            variations[rng.gen_range(0..variations.len())].clone()
+            variations[self.rng.gen_range(0..variations.len())].clone()
         } else {
             original.to_string()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bred_stanza_stores_the_offspring_exactly() {
+        let mut universe = StanzaUniverse::new_with_seed(18);
+        // Free variables, binders and muses are all lost by `expr_to_emoji`
+        let offspring = Expr::lambda("x", Expr::app(Expr::app(Expr::var("x"), Expr::var("free")), Expr::muse("Sol", 0.5)));
+        let id = universe.breed_stanza(1, Some(2), &offspring, 0.0, 0.0).unwrap();
+
+        let stanza = universe.get_stanza(id).unwrap();
+        assert_eq!(stanza.parse_lambda_expr().unwrap(), offspring);
+        assert_eq!(Expr::from_emoji_code(&stanza.emoji_sequence).unwrap(), offspring);
+        assert_eq!(universe.get_stanza_by_emoji(&stanza.emoji_sequence).map(|stanza| stanza.id), Some(id));
+
+        // Breeding again starts from the stored expression
+        let grandchild = universe.evolve_stanza(id, 0.0).unwrap();
+        assert_eq!(universe.get_stanza(grandchild).unwrap().parse_lambda_expr().unwrap(), offspring);
+    }
+
+    #[test]
+    fn test_lineage_records_the_fitness_change() {
+        let mut universe = StanzaUniverse::new_with_seed(19);
+        let id = universe.breed_stanza(1, None, &Expr::muse("Sol", 0.25), 0.0, -0.5).unwrap();
+        assert_eq!(universe.lineage.get(id).unwrap().fitness_delta, -0.5);
+
+        // Evolution nudges the muse's resonance, which is its resonance fitness
+        let evolved = universe.evolve_stanza(id, 1.0).unwrap();
+        let parent = Resonance.score(&universe.get_stanza(id).unwrap().parse_lambda_expr().unwrap(), &universe.lambda_engine);
+        let child = Resonance.score(&universe.get_stanza(evolved).unwrap().parse_lambda_expr().unwrap(), &universe.lambda_engine);
+        assert_eq!(universe.lineage.get(evolved).unwrap().fitness_delta, child - parent);
+    }
+}
@@ -0,0 +1,239 @@
+//! # 🌳 Lineage: The Family Tree of Evolved Stanzas
+//!
+//! Every stanza the universe creates gets a [`LineageRecord`]: the stanzas it
+//! was bred from, its generation, the mutations that produced it and how its
+//! fitness changed against its parent. The [`LineageGraph`] answers
+//! ancestor and descendant queries and exports a stanza's family tree as
+//! Graphviz DOT or JSON for its NFT metadata page.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+/// 🧪 One change applied while breeding a stanza
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MutationOp {
+    /// Subterms were exchanged with another stanza's expression
+    Crossover { partner: u32 },
+    /// The lambda expression differs from the parent's
+    Expression { rate: f64 },
+    /// The poetic text was rewritten
+    Text,
+}
+
+impl MutationOp {
+    fn label(&self) -> String {
+        match self {
+            MutationOp::Crossover { partner } => format!("crossover with #{}", partner),
+            MutationOp::Expression { rate } => format!("mutation @{:.2}", rate),
+            MutationOp::Text => "text".to_string(),
+        }
+    }
+}
+
+/// 🧾 Where a stanza came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineageRecord {
+    pub stanza_id: u32,
+    /// The emoji sequence of the stanza, so exported trees stand on their own
+    pub emoji_sequence: String,
+    /// The primary parent first, then any crossover partner; empty for stanzas created directly
+    pub parents: Vec<u32>,
+    /// 0 for stanzas created directly, otherwise one more than the primary parent's
+    pub generation: u32,
+    pub mutations: Vec<MutationOp>,
+    /// Fitness minus the primary parent's fitness, under the fitness function
+    /// that bred the stanza; 0 for stanzas created directly
+    pub fitness_delta: f64,
+}
+
+/// 🌳 Lineage records of a universe, indexed by stanza ID
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Records")]
+pub struct LineageGraph {
+    records: BTreeMap<u32, LineageRecord>,
+    /// Parent to children, kept in step with `records`
+    #[serde(skip)]
+    children: BTreeMap<u32, BTreeSet<u32>>,
+}
+
+/// The serialized form of a `LineageGraph`, without its index
+#[derive(Deserialize)]
+struct Records {
+    records: BTreeMap<u32, LineageRecord>,
+}
+
+impl From<Records> for LineageGraph {
+    fn from(records: Records) -> Self {
+        records.records.into_values().collect()
+    }
+}
+
+impl FromIterator<LineageRecord> for LineageGraph {
+    fn from_iter<T: IntoIterator<Item = LineageRecord>>(records: T) -> Self {
+        let mut graph = Self::new();
+        for record in records {
+            graph.insert(record);
+        }
+        graph
+    }
+}
+
+impl LineageGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace the record of `record.stanza_id`
+    pub fn insert(&mut self, record: LineageRecord) {
+        let id = record.stanza_id;
+        for parent in &record.parents {
+            self.children.entry(*parent).or_default().insert(id);
+        }
+        if let Some(old) = self.records.insert(id, record) {
+            let parents = &self.records[&id].parents;
+            for parent in old.parents.iter().filter(|parent| !parents.contains(parent)) {
+                if let Some(children) = self.children.get_mut(parent) {
+                    children.remove(&id);
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, id: u32) -> Option<&LineageRecord> {
+        self.records.get(&id)
+    }
+
+    /// Every record, by stanza ID
+    pub fn records(&self) -> &BTreeMap<u32, LineageRecord> {
+        &self.records
+    }
+
+    /// Direct parents of a stanza
+    pub fn parents(&self, id: u32) -> &[u32] {
+        self.records.get(&id).map_or(&[], |record| record.parents.as_slice())
+    }
+
+    /// Stanzas bred directly from `id`, as primary parent or crossover partner
+    pub fn children(&self, id: u32) -> Vec<u32> {
+        self.children.get(&id).map_or_else(Vec::new, |children| children.iter().copied().collect())
+    }
+
+    /// Every stanza `id` descends from, in ascending ID order
+    pub fn ancestors(&self, id: u32) -> Vec<u32> {
+        self.reachable(id, |graph, id| graph.parents(id).to_vec())
+    }
+
+    /// Every stanza descending from `id`, in ascending ID order
+    pub fn descendants(&self, id: u32) -> Vec<u32> {
+        self.reachable(id, |graph, id| graph.children(id))
+    }
+
+    /// True when `ancestor` is a (possibly distant) parent of `id`
+    pub fn is_ancestor(&self, ancestor: u32, id: u32) -> bool {
+        self.ancestors(id).contains(&ancestor)
+    }
+
+    fn reachable(&self, start: u32, next: impl Fn(&Self, u32) -> Vec<u32>) -> Vec<u32> {
+        let mut seen = BTreeSet::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(id) = queue.pop_front() {
+            for neighbour in next(self, id) {
+                if neighbour != start && seen.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        seen.into_iter().collect()
+    }
+
+    /// 👪 The subgraph of `id`, its ancestors and its descendants
+    pub fn family_tree(&self, id: u32) -> LineageGraph {
+        let members = self.ancestors(id).into_iter().chain([id]).chain(self.descendants(id));
+        members.filter_map(|member| self.records.get(&member).cloned()).collect()
+    }
+
+    /// 🗺️ Render as a Graphviz digraph, parents pointing at children
+    ///
+    /// Crossover partners are drawn dashed; edges from the primary parent are
+    /// labelled with the mutations and fitness change.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph lineage {\n    rankdir=TB;\n    node [shape=box];\n");
+        for record in self.records.values() {
+            let _ = writeln!(
+                dot,
+                "    s{} [label=\"#{} {}\\ngeneration {}\"];",
+                record.stanza_id,
+                record.stanza_id,
+                escape(&record.emoji_sequence),
+                record.generation
+            );
+        }
+        for record in self.records.values() {
+            let Some((parent, partners)) = record.parents.split_first() else {
+                continue;
+            };
+            if !self.records.contains_key(parent) {
+                continue;
+            }
+            let mut label: Vec<String> = record.mutations.iter().map(MutationOp::label).collect();
+            label.push(format!("Δ{:+.3}", record.fitness_delta));
+            let _ = writeln!(dot, "    s{} -> s{} [label=\"{}\"];", parent, record.stanza_id, escape(&label.join(", ")));
+            for partner in partners.iter().filter(|partner| self.records.contains_key(partner)) {
+                let _ = writeln!(dot, "    s{} -> s{} [style=dashed];", partner, record.stanza_id);
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// 📦 Render as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(stanza_id: u32, parents: &[u32]) -> LineageRecord {
+        LineageRecord {
+            stanza_id,
+            emoji_sequence: String::new(),
+            parents: parents.to_vec(),
+            generation: parents.len() as u32,
+            mutations: Vec::new(),
+            fitness_delta: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_children_follow_replaced_records() {
+        let mut graph: LineageGraph = [record(1, &[]), record(2, &[]), record(3, &[1, 2]), record(4, &[3])].into_iter().collect();
+        assert_eq!(graph.children(1), vec![3]);
+        assert_eq!(graph.children(2), vec![3]);
+        assert_eq!(graph.descendants(1), vec![3, 4]);
+
+        graph.insert(record(3, &[2]));
+        assert!(graph.children(1).is_empty());
+        assert_eq!(graph.descendants(2), vec![3, 4]);
+        assert_eq!(graph.ancestors(4), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_children_survive_a_json_round_trip() {
+        let graph: LineageGraph = [record(1, &[]), record(2, &[1]), record(3, &[2]), record(4, &[1])].into_iter().collect();
+        let restored: LineageGraph = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+
+        assert_eq!(restored, graph);
+        assert_eq!(restored.descendants(1), vec![2, 3, 4]);
+        assert_eq!(restored.family_tree(2).descendants(1), vec![2, 3]);
+    }
+}
@@ -0,0 +1,280 @@
+//! # 💾 Storage: Saving, Loading and Journaling a Universe
+//!
+//! A universe is saved as one JSON snapshot holding its stanzas, lineage
+//! and next ID; loading it rebuilds the emoji index, so stanzas created in
+//! one run can be evolved in the next. Snapshots written before lineage
+//! existed (a bare map of stanzas by ID) load too.
+//!
+//! Long evolutions can also attach an append-only journal: every stanza is
+//! written to it as one JSON line the moment it is created. Attaching the
+//! same journal after a crash replays those lines on top of the last
+//! snapshot, and a checkpoint folds them into a new snapshot.
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use log::{info, warn};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::{LineageGraph, LineageRecord, Result, Stanza, StanzaError, StanzaUniverse};
+
+/// 📸 Everything needed to restore a universe
+#[derive(Serialize, Deserialize)]
+struct UniverseSnapshot {
+    next_id: u32,
+    stanzas: BTreeMap<u32, Stanza>,
+    #[serde(default)]
+    lineage: LineageGraph,
+}
+
+/// 📓 One journal line: a stanza as it was created
+#[derive(Serialize, Deserialize)]
+struct JournalEntry {
+    stanza: Stanza,
+    lineage: LineageRecord,
+}
+
+/// An open append-only journal
+pub(crate) struct Journal {
+    path: PathBuf,
+    file: File,
+}
+
+impl StanzaUniverse {
+    /// 📂 Load a universe saved with `save`, or a bare stanza map
+    ///
+    /// The emoji index is rebuilt and `next_id` continues after the highest
+    /// stanza ID. The loaded universe draws fresh randomness; call `reseed`
+    /// for reproducible evolution.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(io_error(path))?;
+        let snapshot = match serde_json::from_str::<UniverseSnapshot>(&json) {
+            Ok(snapshot) => snapshot,
+            Err(error) => match serde_json::from_str::<BTreeMap<u32, Stanza>>(&json) {
+                // Older versions wrote only the stanzas, keyed by ID
+                Ok(stanzas) => UniverseSnapshot { next_id: 1, stanzas, lineage: LineageGraph::new() },
+                Err(_) => return Err(StanzaError::InvalidSnapshot { path: path.to_path_buf(), source: error }),
+            },
+        };
+
+        let mut universe = Self::empty(StdRng::from_entropy());
+        universe.next_id = snapshot.next_id;
+        universe.lineage = snapshot.lineage;
+        for stanza in snapshot.stanzas.into_values() {
+            let lineage = universe.lineage.get(stanza.id).cloned().unwrap_or_else(|| LineageRecord {
+                stanza_id: stanza.id,
+                emoji_sequence: stanza.emoji_sequence.clone(),
+                parents: stanza.parent_id.into_iter().collect(),
+                generation: stanza.generation,
+                mutations: Vec::new(),
+                fitness_delta: 0.0,
+            });
+            universe.restore(stanza, lineage);
+        }
+
+        info!("📂 Loaded {} stanzas from {}", universe.stanzas.len(), path.display());
+        Ok(universe)
+    }
+
+    /// 💾 Write a snapshot of every stanza, the lineage and the next ID
+    ///
+    /// The snapshot is written to a temporary file, synced to disk and
+    /// renamed into place, and the directory is synced after the rename, so
+    /// a crash never leaves a half-written universe behind.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let snapshot = UniverseSnapshot {
+            next_id: self.next_id,
+            stanzas: self.stanzas.iter().map(|(&id, stanza)| (id, stanza.clone())).collect(),
+            lineage: self.lineage.clone(),
+        };
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        let temporary = Path::new(&temporary);
+        let json = serde_json::to_string_pretty(&snapshot)?;
+        let mut file = File::create(temporary).map_err(io_error(temporary))?;
+        file.write_all(json.as_bytes()).map_err(io_error(temporary))?;
+        // The contents must reach the disk before the rename can expose them
+        file.sync_all().map_err(io_error(temporary))?;
+        drop(file);
+        std::fs::rename(temporary, path).map_err(io_error(path))?;
+        sync_directory(path)?;
+
+        info!("💾 Saved {} stanzas to {}", self.stanzas.len(), path.display());
+        Ok(())
+    }
+
+    /// 📓 Replay `path` onto this universe, then append every new stanza to it
+    ///
+    /// Returns how many journaled stanzas were replayed. A torn last line,
+    /// left by a crash in the middle of a write, is skipped and cut off so
+    /// the next entry starts on a line of its own.
+    pub fn attach_journal(&mut self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let mut replayed = 0;
+        let mut length = 0;
+        // Length of the prefix holding whole entries
+        let mut intact = 0;
+        let mut unterminated = false;
+        if path.exists() {
+            // Bytes rather than a string: a torn write can split an emoji
+            let contents = std::fs::read(path).map_err(io_error(path))?;
+            length = contents.len();
+            let lines: Vec<&[u8]> = contents.split(|&byte| byte == b'\n').collect();
+            let last = lines.len() - 1;
+            for (number, line) in lines.iter().enumerate() {
+                if !line.trim_ascii().is_empty() {
+                    match serde_json::from_slice::<JournalEntry>(line) {
+                        Ok(entry) => {
+                            self.restore(entry.stanza, entry.lineage);
+                            replayed += 1;
+                        }
+                        Err(e) if number == last => {
+                            warn!("📓 Skipping torn journal entry at line {}: {}", number + 1, e);
+                            break;
+                        }
+                        Err(source) => {
+                            return Err(StanzaError::CorruptJournal { path: path.to_path_buf(), line: number + 1, source });
+                        }
+                    }
+                }
+                intact += line.len() + usize::from(number < last);
+            }
+            // A whole last entry whose newline never made it to disk
+            unterminated = intact == length && contents.last().is_some_and(|&byte| byte != b'\n');
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path).map_err(io_error(path))?;
+        if intact < length {
+            file.set_len(intact as u64).map_err(io_error(path))?;
+        } else if unterminated {
+            file.write_all(b"\n").map_err(io_error(path))?;
+        }
+        self.journal = Some(Journal { path: path.to_path_buf(), file });
+        info!("📓 Journaling to {} after replaying {} stanzas", path.display(), replayed);
+        Ok(replayed)
+    }
+
+    /// 📸 Save a snapshot to `path` and empty the attached journal, whose
+    /// entries the snapshot now contains
+    pub fn checkpoint(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        self.save(path)?;
+        if let Some(journal) = &mut self.journal {
+            // The file is in append mode, so later entries start from the empty file
+            journal.file.set_len(0).map_err(io_error(&journal.path))?;
+        }
+        Ok(())
+    }
+
+    /// Append the stanza and its lineage to the journal, if one is attached
+    pub(crate) fn journal_stanza(&mut self, id: u32) -> Result<()> {
+        let Some(journal) = &mut self.journal else {
+            return Ok(());
+        };
+        let entry = JournalEntry {
+            stanza: self.stanzas[&id].clone(),
+            lineage: self.lineage.get(id).cloned().expect("every stanza has a lineage record"),
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        journal.file.write_all(line.as_bytes()).and_then(|()| journal.file.flush()).map_err(io_error(&journal.path))?;
+        Ok(())
+    }
+
+    /// Put a stored stanza back, keeping the emoji index and `next_id` consistent
+    fn restore(&mut self, stanza: Stanza, lineage: LineageRecord) {
+        let id = stanza.id;
+        self.next_id = self.next_id.max(id + 1);
+        self.emoji_to_stanza.insert(stanza.emoji_sequence.clone(), id);
+        self.lineage.insert(lineage);
+        self.stanzas.insert(id, stanza);
+    }
+}
+
+/// Attach the offending path to an I/O error
+/// Sync the directory holding `path`, so a rename into it survives a crash
+#[cfg(unix)]
+fn sync_directory(path: &Path) -> Result<()> {
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    File::open(directory).and_then(|directory| directory.sync_all()).map_err(io_error(directory))
+}
+
+/// Directories cannot be opened for syncing here; the rename is left to the OS
+#[cfg(not(unix))]
+fn sync_directory(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> StanzaError + '_ {
+    move |source| StanzaError::Io { path: path.to_path_buf(), source }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("stanza-universe-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_torn_journal_is_cut_before_appending() {
+        let dir = scratch_dir("torn");
+        let (snapshot, journal) = (dir.join("universe.json"), dir.join("universe.journal"));
+
+        let mut universe = StanzaUniverse::new_with_seed(20);
+        universe.save(&snapshot).unwrap();
+        universe.attach_journal(&journal).unwrap();
+        let child = universe.evolve_stanza(1, 0.9).unwrap();
+
+        // Crash halfway through writing the next entry
+        let whole = std::fs::read(&journal).unwrap();
+        let mut torn = OpenOptions::new().append(true).open(&journal).unwrap();
+        torn.write_all(&whole[..whole.len() / 2]).unwrap();
+        drop(torn);
+
+        let mut resumed = StanzaUniverse::load(&snapshot).unwrap();
+        assert_eq!(resumed.attach_journal(&journal).unwrap(), 1);
+        assert_eq!(std::fs::read(&journal).unwrap(), whole);
+        let grandchild = resumed.evolve_stanza(child, 0.9).unwrap();
+
+        // Both entries replay cleanly after the second resume
+        let mut again = StanzaUniverse::load(&snapshot).unwrap();
+        assert_eq!(again.attach_journal(&journal).unwrap(), 2);
+        assert_eq!(again.lineage.ancestors(grandchild), vec![1, child]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unterminated_last_entry_gets_its_newline() {
+        let dir = scratch_dir("unterminated");
+        let (snapshot, journal) = (dir.join("universe.json"), dir.join("universe.journal"));
+
+        let mut universe = StanzaUniverse::new_with_seed(21);
+        universe.save(&snapshot).unwrap();
+        universe.attach_journal(&journal).unwrap();
+        let child = universe.evolve_stanza(1, 0.9).unwrap();
+        let whole = std::fs::read(&journal).unwrap();
+        std::fs::write(&journal, &whole[..whole.len() - 1]).unwrap();
+
+        let mut resumed = StanzaUniverse::load(&snapshot).unwrap();
+        assert_eq!(resumed.attach_journal(&journal).unwrap(), 1);
+        resumed.evolve_stanza(child, 0.9).unwrap();
+
+        let mut again = StanzaUniverse::load(&snapshot).unwrap();
+        assert_eq!(again.attach_journal(&journal).unwrap(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -7,7 +7,7 @@ use rand::Rng;
 use solfunmeme_metameme::MetaMemeEngine;
 use ragit_memory_monitor::MemoryMonitor;
 
-use lambda_calculus_core::{Expr, LambdaEngine};
+use lambda_calculus_core::{Backend, Expr, LambdaEngine, Strategy};
 use emoji_semantics::EmojiSemantics;
 use stanza_universe::StanzaUniverse;
 
@@ -16,6 +16,7 @@ use crate::commands;
 use crate::commands::instrumented_run::instrumented_run;
 use crate::commands::generate_stanza::generate_stanza;
 use crate::commands::create_quine::create_quine;
+use crate::commands::search_quine::search_quine;
 use crate::commands::evolve_stanza::evolve_stanza;
 use crate::commands::generate_nft_collection::generate_nft_collection;
 use crate::commands::analyze_emoji::analyze_emoji;
@@ -50,6 +51,10 @@ enum Commands {
         /// Output file for the generated stanza
         #[arg(short, long)]
         output: Option<PathBuf>,
+        
+        /// Random seed; the same seed reproduces the same output byte for byte
+        #[arg(long)]
+        seed: Option<u64>,
     },
     
     /// 🌀 Create a self-replicating quine expression
@@ -61,6 +66,14 @@ enum Commands {
         /// Maximum reduction steps
         #[arg(short, long, default_value = "100")]
         max_steps: usize,
+        
+        /// Search for the smallest quine carrying the seed instead of building one
+        #[arg(long)]
+        search: bool,
+        
+        /// Largest candidate term to try when searching
+        #[arg(long, default_value = "21")]
+        max_size: usize,
     },
     
     /// 🧬 Evolve an existing stanza
@@ -76,6 +89,22 @@ enum Commands {
         /// Number of generations to evolve
         #[arg(short, long, default_value = "1")]
         generations: u32,
+        
+        /// Random seed; the same seed reproduces the same output byte for byte
+        #[arg(long)]
+        seed: Option<u64>,
+        
+        /// Write the family tree of the last evolved stanza here (DOT for .dot/.gv, JSON otherwise)
+        #[arg(long)]
+        lineage: Option<PathBuf>,
+        
+        /// Universe file to evolve in; loaded if it exists and saved afterwards
+        #[arg(short, long)]
+        universe: Option<PathBuf>,
+        
+        /// Append-only journal of new stanzas; an existing journal is replayed first
+        #[arg(long)]
+        journal: Option<PathBuf>,
     },
     
     /// 🎨 Generate NFT metadata for a collection
@@ -91,6 +120,14 @@ enum Commands {
         /// Minimum resonance score
         #[arg(short, long, default_value = "0.85")]
         min_resonance: f64,
+        
+        /// Reduction backend (substitution, machine)
+        #[arg(short, long, default_value = "substitution")]
+        backend: Backend,
+        
+        /// Random seed; the same seed reproduces the same output byte for byte
+        #[arg(long)]
+        seed: Option<u64>,
     },
     
     /// 🔍 Analyze an emoji sequence
@@ -102,6 +139,11 @@ enum Commands {
         /// Show detailed reduction trace
         #[arg(short, long)]
         trace: bool,
+        
+        /// Evaluation strategy (normal-order, applicative-order, call-by-name,
+        /// call-by-need, head-normal, weak-head)
+        #[arg(short, long, default_value = "normal-order")]
+        strategy: Strategy,
     },
     
     /// 🌌 Initialize the complete stanza universe
@@ -113,13 +155,21 @@ enum Commands {
         /// Output file for the universe
         #[arg(short, long, default_value = "stanza-universe.json")]
         output: PathBuf,
+        
+        /// Random seed; the same seed reproduces the same output byte for byte
+        #[arg(long)]
+        seed: Option<u64>,
     },
     
     /// 🚀 Launch interactive SOLFUNMEME REPL
     Repl,
     
     /// 📊 Show statistics about the current universe
-    Stats,
+    Stats {
+        /// Universe file to read instead of the core stanzas
+        #[arg(short, long)]
+        universe: Option<PathBuf>,
+    },
 
     /// 🧪 Run instrumented methods and monitor memory
     InstrumentedRun,
@@ -138,37 +188,41 @@ pub async fn main() -> Result<()> {
     info!("🚀 SOLFUNMEME MetaMeme Engine Starting...");
     
     match cli.command {
-        Commands::Generate { emoji, output } => {
-            let mut engine = MetaMemeEngine::new();
+        Commands::Generate { emoji, output, seed } => {
+            let mut engine = seed.map_or_else(MetaMemeEngine::new, MetaMemeEngine::new_with_seed);
             generate_stanza(&mut engine, &emoji, output.as_deref()).await?;
         }
         
-        Commands::Quine { seed, max_steps } => {
-            create_quine(&seed, max_steps).await?;
+        Commands::Quine { seed, max_steps, search, max_size } => {
+            if search {
+                search_quine(&seed, max_size, max_steps).await?;
+            } else {
+                create_quine(&seed, max_steps).await?;
+            }
         }
         
-        Commands::Evolve { parent_id, mutation_rate, generations } => {
-            evolve_stanza(parent_id, mutation_rate, generations).await?;
+        Commands::Evolve { parent_id, mutation_rate, generations, seed, lineage, universe, journal } => {
+            evolve_stanza(parent_id, mutation_rate, generations, seed, lineage.as_deref(), universe.as_deref(), journal.as_deref()).await?;
         }
         
-        Commands::Nft { count, output_dir, min_resonance } => {
-            generate_nft_collection(count, &output_dir, min_resonance).await?;
+        Commands::Nft { count, output_dir, min_resonance, backend, seed } => {
+            generate_nft_collection(count, &output_dir, min_resonance, backend, seed).await?;
         }
         
-        Commands::Analyze { emoji, trace } => {
-            analyze_emoji(&emoji, trace).await?;
+        Commands::Analyze { emoji, trace, strategy } => {
+            analyze_emoji(&emoji, trace, strategy).await?;
         }
         
-        Commands::Universe { count, output } => {
-            create_universe(count, &output).await?;
+        Commands::Universe { count, output, seed } => {
+            create_universe(count, &output, seed).await?;
         }
         
         Commands::Repl => {
             launch_repl().await?;
         }
         
-        Commands::Stats => {
-            show_stats().await?;
+        Commands::Stats { universe } => {
+            show_stats(universe.as_deref()).await?;
         }
 
         Commands::InstrumentedRun => {
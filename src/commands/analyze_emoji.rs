@@ -1,35 +1,58 @@
 use anyhow::Result;
 use log::info;
-use lambda_calculus_core::LambdaEngine;
+use lambda_calculus_core::{quine, LambdaEngine, Strategy};
 use emoji_semantics::EmojiSemantics;
 
-pub async fn analyze_emoji(emoji: &str, show_trace: bool) -> Result<()> {
+pub async fn analyze_emoji(emoji: &str, show_trace: bool, strategy: Strategy) -> Result<()> {
     info!("🔍 Analyzing emoji sequence: {}", emoji);
     
-    let mut emoji_engine = EmojiSemantics::new();
-    let (expr, resonance) = emoji_engine.interpret_emoji_poem(emoji)?;
+    let emoji_engine = EmojiSemantics::new();
+    let parsed = emoji_engine.parse_poem(emoji);
+    for diagnostic in &parsed.diagnostics {
+        eprintln!("⚠️  {}", diagnostic);
+    }
+    let (expr, resonance) = parsed.into_result()?;
     
-    let mut lambda_engine = LambdaEngine::new();
+    let mut lambda_engine = LambdaEngine::new().with_strategy(strategy);
     let trace = lambda_engine.normalize(expr.clone())?;
     
     println!("🔍 SOLFUNMEME Emoji Analysis 🔍");
     println!("==============================");
     println!();
     println!("Input: {}", emoji);
-    println!("Emoji Count: {}", emoji.chars().count());
+    println!("Emoji Count: {}", emoji_engine.emoji_count(emoji));
     println!("Resonance Score: {:.3}", resonance);
     println!();
     println!("Lambda Expression: {}", expr);
+    println!("Strategy: {}", trace.strategy);
     println!("Reduced Form: {}", trace.final_form);
     println!("Reduction Steps: {}", trace.step_count);
     println!("Normal Form: {}", trace.is_normal_form);
+    println!("Termination: {}", trace.termination);
+    if trace.metrics.per_step {
+        println!(
+            "Peak Size: {} (depth {}, {} redexes at most, {} duplications)",
+            trace.metrics.peak_size, trace.metrics.peak_depth, trace.metrics.peak_redexes, trace.metrics.duplications
+        );
+    }
+    match lambda_engine.infer_type(&expr) {
+        Ok(principal_type) => println!("Type: {}", principal_type),
+        Err(error) => println!("Type: untypeable ({})", error),
+    }
+    if trace.is_normal_form {
+        if let Some(value) = lambda_engine.decode(&trace.final_form) {
+            println!("Evaluates To: {}", value.describe());
+        }
+    }
     println!();
     
-    if show_trace && !trace.steps.is_empty() {
+    let steps = if show_trace { trace.steps() } else { Vec::new() };
+    if !steps.is_empty() {
         println!("🔄 Reduction Trace:");
         println!("------------------");
-        for (i, step) in trace.steps.iter().enumerate() {
-            println!("Step {}: {}", i, step);
+        println!("Step 0: {}", expr);
+        for (i, step) in steps.iter().enumerate() {
+            println!("Step {}: {}", i + 1, step);
         }
         println!();
     }
@@ -37,9 +60,11 @@ pub async fn analyze_emoji(emoji: &str, show_trace: bool) -> Result<()> {
     // Convert back to emoji
     let output_emoji = emoji_engine.expr_to_emoji(&trace.final_form);
     println!("Output Emoji: {}", output_emoji);
+    println!("Canonical Code: {}", trace.final_form.to_emoji_code());
     
-    if output_emoji == emoji {
-        println!("🌀 QUINE DETECTED: This sequence is self-replicating!");
+    let verdict = quine::classify(&expr, &trace);
+    if verdict.is_quine() {
+        println!("🌀 QUINE DETECTED: This sequence is self-replicating ({})!", verdict);
     }
     
     Ok(())
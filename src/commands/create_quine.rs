@@ -1,6 +1,6 @@
 use anyhow::Result;
 use log::info;
-use lambda_calculus_core::LambdaEngine;
+use lambda_calculus_core::{quine, LambdaEngine, QuineVerdict};
 use emoji_semantics::EmojiSemantics;
 
 pub async fn create_quine(seed: &str, max_steps: usize) -> Result<()> {
@@ -10,6 +10,7 @@ pub async fn create_quine(seed: &str, max_steps: usize) -> Result<()> {
     let quine = lambda_engine.create_quine(seed);
     
     let trace = lambda_engine.normalize(quine.clone())?;
+    let verdict = quine::classify(&quine, &trace);
     
     let emoji_engine = EmojiSemantics::new();
     let emoji_output = emoji_engine.expr_to_emoji(&trace.final_form);
@@ -22,14 +23,22 @@ pub async fn create_quine(seed: &str, max_steps: usize) -> Result<()> {
     println!("Final Form: {}", trace.final_form);
     println!("Emoji Output: {}", emoji_output);
     println!("Reduction Steps: {}", trace.step_count);
+    println!("Termination: {}", trace.termination);
+    println!("Verdict: {}", verdict);
     println!();
     
-    if emoji_output.contains(seed) {
-        println!("✅ PERFECT QUINE: The output contains the original seed!");
-        println!("🧬 This expression has achieved self-replication!");
-    } else {
-        println!("🔄 PARTIAL QUINE: The expression shows self-referential properties.");
-        println!("🌱 Further evolution may achieve perfect self-replication.");
+    match verdict {
+        QuineVerdict::Exact { .. } => {
+            println!("✅ PERFECT QUINE: The expression reduces to itself in one step!");
+            println!("🧬 This expression has achieved self-replication!");
+        }
+        QuineVerdict::Cyclic { period, .. } => {
+            println!("🔁 CYCLIC QUINE: The reduction returns to itself every {} steps!", period);
+        }
+        QuineVerdict::NotQuine { .. } => {
+            println!("🔄 PARTIAL QUINE: The expression shows self-referential properties.");
+            println!("🌱 Further evolution may achieve perfect self-replication.");
+        }
     }
     
     Ok(())
@@ -1,11 +1,36 @@
 use anyhow::Result;
 use log::{info, error};
+use std::path::Path;
 use stanza_universe::StanzaUniverse;
 
-pub async fn evolve_stanza(parent_id: u32, mutation_rate: f64, generations: u32) -> Result<()> {
+pub async fn evolve_stanza(
+    parent_id: u32,
+    mutation_rate: f64,
+    generations: u32,
+    seed: Option<u64>,
+    lineage: Option<&Path>,
+    universe_path: Option<&Path>,
+    journal: Option<&Path>,
+) -> Result<()> {
     info!("🧬 Evolving stanza {} for {} generations", parent_id, generations);
     
-    let mut universe = StanzaUniverse::new();
+    // Continue a saved universe if there is one, otherwise start from the core stanzas
+    let mut universe = match universe_path.filter(|path| path.exists()) {
+        Some(path) => {
+            let mut universe = StanzaUniverse::load(path)?;
+            if let Some(seed) = seed {
+                universe.reseed(seed);
+            }
+            universe
+        }
+        None => seed.map_or_else(StanzaUniverse::new, StanzaUniverse::new_with_seed),
+    };
+    if let Some(journal) = journal {
+        let replayed = universe.attach_journal(journal)?;
+        if replayed > 0 {
+            println!("📓 Resumed {} stanzas from {}", replayed, journal.display());
+        }
+    }
     let mut current_id = parent_id;
     
     for generation in 1..=generations {
@@ -26,5 +51,20 @@ pub async fn evolve_stanza(parent_id: u32, mutation_rate: f64, generations: u32)
         }
     }
     
+    if let Some(path) = universe_path {
+        universe.checkpoint(path)?;
+    }
+    
+    if let Some(path) = lineage {
+        // The family tree of the last stanza bred, as DOT for .dot/.gv files and JSON otherwise
+        let tree = universe.lineage.family_tree(current_id);
+        let rendered = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("dot" | "gv") => tree.to_dot(),
+            _ => tree.to_json()?,
+        };
+        std::fs::write(path, rendered)?;
+        info!("🌳 Lineage of stanza #{} written to {}", current_id, path.display());
+    }
+    
     Ok(())
 }
\ No newline at end of file
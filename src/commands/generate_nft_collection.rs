@@ -2,19 +2,23 @@ use anyhow::Result;
 use log::info;
 use std::path::Path;
 use emoji_semantics::EmojiSemantics;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use emoji_semantics::NFTMetadata;
+use lambda_calculus_core::Backend;
 
-pub async fn generate_nft_collection(count: u32, output_dir: &Path, min_resonance: f64) -> Result<()> {
-    info!("🎨 Generating {} NFTs with min resonance {:.3}", count, min_resonance);
+pub async fn generate_nft_collection(count: u32, output_dir: &Path, min_resonance: f64, backend: Backend, seed: Option<u64>) -> Result<()> {
+    info!("🎨 Generating {} NFTs with min resonance {:.3} ({} backend)", count, min_resonance, backend);
     
     std::fs::create_dir_all(output_dir)?;
     
-    let mut emoji_engine = EmojiSemantics::new();
+    let mut rng = seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+    let mut emoji_engine = EmojiSemantics::new_with_seed(rng.gen());
+    emoji_engine.lambda_engine.backend = backend;
     
     for token_id in 1..=count {
         // Generate random high-resonance emoji sequence
-        let emoji_length = rand::thread_rng().gen_range(3..=8);
+        let emoji_length = rng.gen_range(3..=8);
         let emoji_sequence = emoji_engine.generate_random_poem(emoji_length, min_resonance);
         
         let metadata = emoji_engine.generate_nft_metadata(&emoji_sequence, token_id)?;
@@ -1,17 +1,20 @@
 use anyhow::Result;
 use std::io::{self, Write};
-use lambda_calculus_core::LambdaEngine;
+use lambda_calculus_core::{quine, Breakpoint, LambdaEngine, RunOutcome, Stepper};
 use emoji_semantics::EmojiSemantics;
 
 pub async fn launch_repl() -> Result<()> {
     println!("🚀 SOLFUNMEME Interactive REPL");
     println!("==============================");
     println!("Enter emoji sequences to see their lambda calculus interpretations!");
-    println!("Commands: :quit, :help, :stats");
+    println!("Commands: :quit, :help, :stats, :step, :back, :run, :break");
     println!();
     
     let mut emoji_engine = EmojiSemantics::new();
     let mut lambda_engine = LambdaEngine::new();
+    // 🐞 The last interpreted poem, ready to step through
+    let mut stepper: Option<Stepper> = None;
+    let mut breakpoints: Vec<Breakpoint> = Vec::new();
     
     loop {
         print!("🌀 > ");
@@ -35,6 +38,10 @@ pub async fn launch_repl() -> Result<()> {
                 println!("  Enter emoji sequences to interpret them");
                 println!("  :quit or :q - Exit the REPL");
                 println!("  :help or :h - Show this help");
+                println!("  :step [n] - Contract the next n redexes of the last poem");
+                println!("  :back [n] - Rewind n steps");
+                println!("  :run - Step until a breakpoint or the normal form");
+                println!("  :break [rule|emoji|🎭muse|clear] - Add, list or clear breakpoints");
                 continue;
             }
             ":stats" | ":s" => {
@@ -43,9 +50,18 @@ pub async fn launch_repl() -> Result<()> {
                 println!("  Lambda engine max steps: {}", lambda_engine.max_steps);
                 continue;
             }
+            _ if input.starts_with(':') => {
+                debug_command(input, &mut stepper, &mut breakpoints);
+            }
             _ => {
                 match emoji_engine.interpret_emoji_poem(input) {
                     Ok((expr, resonance)) => {
+                        let mut debugger = lambda_engine.stepper(expr.clone());
+                        for breakpoint in &breakpoints {
+                            debugger.add_breakpoint(breakpoint.clone());
+                        }
+                        stepper = Some(debugger);
+                        
                         match lambda_engine.normalize(expr.clone()) {
                             Ok(trace) => {
                                 println!("  Expression: {}", expr);
@@ -56,8 +72,9 @@ pub async fn launch_repl() -> Result<()> {
                                 let output_emoji = emoji_engine.expr_to_emoji(&trace.final_form);
                                 println!("  Output: {}", output_emoji);
                                 
-                                if output_emoji == input {
-                                    println!("  🌀 QUINE!");
+                                let verdict = quine::classify(&expr, &trace);
+                                if verdict.is_quine() {
+                                    println!("  🌀 QUINE: {}", verdict);
                                 }
                             }
                             Err(e) => println!("  ❌ Reduction error: {}", e),
@@ -71,4 +88,101 @@ pub async fn launch_repl() -> Result<()> {
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 🐞 Handle `:step`, `:back`, `:run` and `:break` against the last poem
+fn debug_command(input: &str, stepper: &mut Option<Stepper>, breakpoints: &mut Vec<Breakpoint>) {
+    let mut words = input.split_whitespace();
+    let command = words.next().unwrap_or_default();
+    let argument = words.next();
+    
+    if command == ":break" {
+        match argument {
+            None if breakpoints.is_empty() => println!("  No breakpoints set"),
+            None => {
+                for (index, breakpoint) in breakpoints.iter().enumerate() {
+                    println!("  {}. {}", index + 1, breakpoint);
+                }
+            }
+            Some("clear") => {
+                breakpoints.clear();
+                if let Some(stepper) = stepper {
+                    stepper.clear_breakpoints();
+                }
+                println!("  🧹 Breakpoints cleared");
+            }
+            Some(text) => match text.parse::<Breakpoint>() {
+                Ok(breakpoint) => {
+                    println!("  🔴 Break when {}", breakpoint);
+                    if let Some(stepper) = stepper {
+                        stepper.add_breakpoint(breakpoint.clone());
+                    }
+                    if !breakpoints.contains(&breakpoint) {
+                        breakpoints.push(breakpoint);
+                    }
+                }
+                Err(e) => println!("  ❌ {}", e),
+            },
+        }
+        return;
+    }
+    
+    let Some(stepper) = stepper else {
+        println!("  ❌ Enter an emoji poem before stepping through it");
+        return;
+    };
+    let count = match argument.map(str::parse::<usize>) {
+        None => 1,
+        Some(Ok(count)) => count,
+        Some(Err(_)) => {
+            println!("  ❌ Expected a number of steps, got {}", argument.unwrap_or_default());
+            return;
+        }
+    };
+    
+    match command {
+        ":step" => {
+            for _ in 0..count {
+                match stepper.step().map(|step| step.cloned()) {
+                    Ok(Some(step)) => println!("  ⏭️ {}: {}", stepper.position(), step),
+                    Ok(None) => {
+                        println!("  ✅ Normal form");
+                        break;
+                    }
+                    Err(kind) => {
+                        println!("  ⏳ {} budget exceeded", kind);
+                        break;
+                    }
+                }
+            }
+        }
+        ":back" => {
+            for _ in 0..count {
+                match stepper.step_back() {
+                    Some(step) => println!("  ⏮️ Undid {} at step {}", step.rule, stepper.position() + 1),
+                    None if stepper.position() == 0 => {
+                        println!("  ⏮️ At the original term");
+                        break;
+                    }
+                    None => {
+                        println!("  ⏮️ At the oldest remembered term, step {}", stepper.position());
+                        break;
+                    }
+                }
+            }
+            println!("  Expression: {}", stepper.current());
+        }
+        ":run" => {
+            let start = stepper.position();
+            let outcome = stepper.run();
+            println!("  ▶️ {} after {} steps", outcome, stepper.position() - start);
+            if let RunOutcome::Breakpoint(_) = outcome {
+                if let Some(step) = stepper.steps().last() {
+                    println!("  {}", step);
+                }
+            }
+            println!("  Expression: {}", stepper.current());
+        }
+        _ => println!("  ❌ Unknown command {} (try :help)", command),
+    }
+}
@@ -1,6 +1,7 @@
 pub mod instrumented_run;
 pub mod generate_stanza;
 pub mod create_quine;
+pub mod search_quine;
 pub mod evolve_stanza;
 pub mod generate_nft_collection;
 pub mod analyze_emoji;
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use log::info;
+use lambda_calculus_core::SynthesisBudget;
+use crate::MetaMemeEngine;
+
+pub async fn search_quine(seed: &str, max_size: usize, max_steps: usize) -> Result<()> {
+    info!("🔭 Searching for a quine with seed: {}", seed);
+    
+    let mut engine = MetaMemeEngine::new();
+    let budget = SynthesisBudget::default().with_max_size(max_size).with_max_steps(max_steps);
+    let search = engine.synthesize_quine(seed, budget).await?;
+    
+    println!("🔭 SOLFUNMEME Quine Search 🔭");
+    println!("============================");
+    println!();
+    println!("Seed: {}", seed);
+    println!("Maximum Size: {}", max_size);
+    println!("Candidates Checked: {}", search.candidates_checked);
+    println!();
+    
+    match &search.quine {
+        Some(report) => {
+            let quine = &report.terms[0];
+            println!("✅ Found: {}", quine);
+            println!("Size: {}", quine.size());
+            println!("Verdict: {}", report.verdict);
+            println!("Emoji Code: {}", quine.to_emoji_code());
+            println!();
+            println!("🪞 Proof:");
+            println!("Step 0: {}", quine);
+            for (i, step) in report.steps.iter().enumerate() {
+                println!("Step {}: {}", i + 1, step);
+            }
+        }
+        None if search.budget_exhausted => {
+            println!("⏳ Candidate budget exhausted before any quine was found.");
+        }
+        None => {
+            println!("🌱 No quine containing {} up to size {}; try a larger --max-size.", seed, max_size);
+        }
+    }
+    
+    Ok(())
+}
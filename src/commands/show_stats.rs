@@ -1,12 +1,16 @@
 use anyhow::Result;
+use std::path::Path;
 use stanza_universe::StanzaUniverse;
 use emoji_semantics::EmojiSemantics;
 
-pub async fn show_stats() -> Result<()> {
+pub async fn show_stats(universe_path: Option<&Path>) -> Result<()> {
     println!("📊 SOLFUNMEME MetaMeme Statistics");
     println!("=================================");
     
-    let universe = StanzaUniverse::new();
+    let universe = match universe_path {
+        Some(path) => StanzaUniverse::load(path)?,
+        None => StanzaUniverse::new(),
+    };
     let emoji_engine = EmojiSemantics::new();
     
     println!("🌌 Universe Statistics:");
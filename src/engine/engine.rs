@@ -2,6 +2,7 @@ use lambda_calculus_core::LambdaEngine;
 use emoji_semantics::EmojiSemantics;
 use stanza_universe::StanzaUniverse;
 use ragit_memory_monitor::MemoryMonitor;
+use rand::rngs::StdRng;
 
 /// 🌟 The main MetaMeme engine that orchestrates all components
 pub struct MetaMemeEngine {
@@ -13,4 +14,6 @@ pub struct MetaMemeEngine {
     pub stanza_universe: StanzaUniverse,
     /// Memory monitor for tracking memory usage
     pub memory_monitor: MemoryMonitor,
+    /// Source of randomness for the engine's own choices
+    pub(crate) rng: StdRng,
 }
\ No newline at end of file
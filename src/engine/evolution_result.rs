@@ -1,3 +1,4 @@
+use lambda_calculus_core::GenerationStats;
 use serde::{Serialize, Deserialize};
 
 /// 🧬 Result of universe evolution
@@ -8,4 +9,10 @@ pub struct EvolutionResult {
     pub new_stanzas_created: usize,
     pub generations_completed: u32,
     pub evolved_stanza_ids: Vec<u32>,
-}
\ No newline at end of file
+    /// Name of the fitness function that drove selection
+    #[serde(default)]
+    pub fitness: String,
+    /// Fitness statistics of the stanza population at the start of each generation
+    #[serde(default)]
+    pub generation_stats: Vec<GenerationStats>,
+}
@@ -1,5 +1,6 @@
 use crate::{engine::MetaMemeEngine, engine::QuineResult};
 use anyhow::Result;
+use lambda_calculus_core::quine;
 use log::debug;
 use ragit_instrumentation_macros::instrument_function;
 
@@ -12,7 +13,7 @@ impl MetaMemeEngine {
         let trace = self.lambda_engine.normalize(quine_expr.clone())?;
         let output_emoji = self.emoji_engine.expr_to_emoji(&trace.final_form);
         
-        let is_perfect_quine = output_emoji.contains(seed);
+        let verdict = quine::classify(&quine_expr, &trace);
         
         Ok(QuineResult {
             seed: seed.to_string(),
@@ -20,7 +21,9 @@ impl MetaMemeEngine {
             final_expression: format!("{}", trace.final_form),
             output_emoji,
             reduction_steps: trace.step_count,
-            is_perfect_quine,
+            is_perfect_quine: verdict.is_quine(),
+            termination: trace.termination,
+            verdict,
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,92 @@
+use crate::{engine::MetaMemeEngine, engine::EvolutionResult};
+use anyhow::Result;
+use lambda_calculus_core::{Fitness, GenerationStats, GeneticConfig, LambdaEngine, Scored};
+use log::{info, debug};
+use ragit_instrumentation_macros::instrument_function;
+use stanza_universe::StanzaUniverse;
+
+impl MetaMemeEngine {
+    /// 🧬 Evolve the universe with genetic programming
+    ///
+    /// The first generation breeds from every stanza. Each generation scores
+    /// its pool with `fitness`, breeds `mutation_rate` times as many new
+    /// stanzas as the pool holds by the config's selection, crossover and
+    /// mutation, and passes its `elitism` fittest stanzas and all of its
+    /// offspring on as the next pool. Stanzas are never removed from the
+    /// universe itself.
+    #[instrument_function]
+    pub async fn evolve_universe_with(&mut self, generations: u32, config: &GeneticConfig, fitness: &dyn Fitness) -> Result<EvolutionResult> {
+        info!("🧬 Evolving universe for {} generations by {}", generations, fitness.name());
+
+        let initial_count = self.stanza_universe.stanzas.len();
+        let mut evolved_stanzas = Vec::new();
+        let mut generation_stats = Vec::new();
+
+        // Sorted by ID so seeded runs breed the same parents
+        let mut ids: Vec<u32> = self.stanza_universe.stanzas.keys().cloned().collect();
+        ids.sort_unstable();
+        let mut pool = score_stanzas(&self.stanza_universe, &self.lambda_engine, &ids, fitness);
+
+        for generation in 1..=generations {
+            let population: Vec<Scored> = pool.iter().map(|(_, scored)| scored.clone()).collect();
+            let Some(stats) = GenerationStats::from_population(generation as usize, &population) else {
+                break;
+            };
+            debug!("🧬 Generation {}: best {:.3}, mean {:.3}", generation, stats.best_fitness, stats.mean_fitness);
+            generation_stats.push(stats);
+
+            let evolution_count = (population.len() as f64 * config.mutation_rate) as usize;
+            let mut bred = Vec::new();
+            for offspring in self.lambda_engine.breed(&population, evolution_count, config)? {
+                let parent_id = pool[offspring.parent].0;
+                let partner_id = offspring.partner.map(|partner| pool[partner].0);
+                let score = fitness.score(&offspring.expr, &self.lambda_engine);
+                let fitness_delta = score - population[offspring.parent].fitness;
+
+                match self.stanza_universe.breed_stanza(parent_id, partner_id, &offspring.expr, config.mutation_rate, fitness_delta) {
+                    Ok(new_id) => {
+                        evolved_stanzas.push(new_id);
+                        bred.push((new_id, Scored { expr: offspring.expr, fitness: score }));
+                        debug!("🧬 Generation {}: Bred stanza #{} from #{}", generation, new_id, parent_id);
+                    }
+                    Err(e) => {
+                        debug!("⚠️ Evolution failed for stanza {}: {}", parent_id, e);
+                    }
+                }
+            }
+
+            // The fittest carry over unchanged; a stable sort keeps ties in ID order
+            pool.sort_by(|(_, a), (_, b)| b.fitness.total_cmp(&a.fitness));
+            pool.truncate(config.elitism);
+            pool.extend(bred);
+        }
+
+        let final_count = self.stanza_universe.stanzas.len();
+
+        Ok(EvolutionResult {
+            initial_stanza_count: initial_count,
+            final_stanza_count: final_count,
+            new_stanzas_created: evolved_stanzas.len(),
+            generations_completed: generations,
+            evolved_stanza_ids: evolved_stanzas,
+            fitness: fitness.name().to_string(),
+            generation_stats,
+        })
+    }
+}
+
+/// Score the expressions of `ids`, skipping stanzas whose expression does not parse
+fn score_stanzas(universe: &StanzaUniverse, engine: &LambdaEngine, ids: &[u32], fitness: &dyn Fitness) -> Vec<(u32, Scored)> {
+    ids.iter()
+        .filter_map(|&id| match universe.stanzas[&id].parse_lambda_expr() {
+            Ok(expr) => {
+                let score = fitness.score(&expr, engine);
+                Some((id, Scored { expr, fitness: score }))
+            }
+            Err(e) => {
+                debug!("⚠️ Stanza {} has no readable expression: {}", id, e);
+                None
+            }
+        })
+        .collect()
+}
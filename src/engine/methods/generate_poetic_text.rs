@@ -5,7 +5,7 @@ use ragit_instrumentation_macros::instrument_function;
 
 impl MetaMemeEngine {
     #[instrument_function]
-    pub fn generate_poetic_text(&self, expr: &Expr, resonance: f64) -> String {
+    pub fn generate_poetic_text(&mut self, expr: &Expr, resonance: f64) -> String {
         let base_verses = vec![
             "In the metaprotocol's dance, where lambda meets the light,\nThrough recursive dreams and combinatorial flight:",
             "Digital muses stir in silicon dreams,\nWhere poetry flows in data streams,",
@@ -14,8 +14,7 @@ impl MetaMemeEngine {
             "Where S-combinators weave their spell,\nAnd K-combinators guard truth well,",
         ];
         
-        let mut rng = rand::thread_rng();
-        let base = base_verses[rng.gen_range(0..base_verses.len())];
+        let base = base_verses[self.rng.gen_range(0..base_verses.len())];
         
         let resonance_line = match resonance {
             r if r >= 0.95 => "With resonance that shakes the stars,",
@@ -4,9 +4,7 @@ use ragit_instrumentation_macros::instrument_function;
 
 impl MetaMemeEngine {
     #[instrument_function]
-    pub(super) fn generate_rarity_based_emoji(&self, token_id: u32, total_count: u32) -> String {
-        let mut rng = rand::thread_rng();
-        
+    pub(super) fn generate_rarity_based_emoji(&mut self, token_id: u32, total_count: u32) -> String {
         // Calculate rarity based on token position
         let rarity_percentile = (token_id as f64) / (total_count as f64);
         
@@ -15,7 +13,7 @@ impl MetaMemeEngine {
             p if p >= 0.96 => (7, 0.93), // Epic: 4%
             p if p >= 0.90 => (6, 0.90), // Rare: 10%
             p if p >= 0.75 => (5, 0.85), // Uncommon: 25%
-            _ => (rng.gen_range(3..=4), 0.80), // Common: 60%
+            _ => (self.rng.gen_range(3..=4), 0.80), // Common: 60%
         };
         
         self.emoji_engine.generate_random_poem(emoji_length, min_resonance)
@@ -1,7 +1,9 @@
 pub mod new;
 pub mod generate_poem;
 pub mod create_quine;
+pub mod synthesize_quine;
 pub mod generate_nft_collection;
 pub mod generate_rarity_based_emoji;
 pub mod generate_poetic_text;
-pub mod evolve_universe;
\ No newline at end of file
+pub mod evolve_universe;
+pub mod evolve_universe_with;
\ No newline at end of file
@@ -1,17 +1,30 @@
 use crate::engine::MetaMemeEngine;
 use log::info;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use ragit_memory_monitor::MemoryMonitor;
 
 impl MetaMemeEngine {
     /// Create a new MetaMeme engine with all components initialized
     pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+    
+    /// 🎲 Create an engine where the same seed produces byte-identical
+    /// poems, NFT collections and universes
+    pub fn new_with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+    
+    fn with_rng(mut rng: StdRng) -> Self {
         info!("🚀 Initializing SOLFUNMEME MetaMeme Engine...");
         
         Self {
-            lambda_engine: lambda_calculus_core::LambdaEngine::new(),
-            emoji_engine: emoji_semantics::EmojiSemantics::new(),
-            stanza_universe: stanza_universe::StanzaUniverse::new(),
+            lambda_engine: lambda_calculus_core::LambdaEngine::new().with_seed(rng.gen()),
+            emoji_engine: emoji_semantics::EmojiSemantics::new_with_seed(rng.gen()),
+            stanza_universe: stanza_universe::StanzaUniverse::new_with_seed(rng.gen()),
             memory_monitor: MemoryMonitor::new(true, None, None),
+            rng,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+use crate::engine::MetaMemeEngine;
+use anyhow::Result;
+use lambda_calculus_core::{QuineSearch, SynthesisBudget};
+use log::debug;
+use ragit_instrumentation_macros::instrument_function;
+
+impl MetaMemeEngine {
+    /// 🔭 Search for the smallest self-replicating term that carries `seed`
+    #[instrument_function]
+    pub async fn synthesize_quine(&mut self, seed: &str, budget: SynthesisBudget) -> Result<QuineSearch> {
+        debug!("🔭 Synthesizing quine for seed: {}", seed);
+        
+        Ok(self.lambda_engine.synthesize_quine(seed, budget)?)
+    }
+}
@@ -12,7 +12,9 @@ pub use evolution_result::EvolutionResult;
 pub use methods::new::*;
 pub use methods::generate_poem::*;
 pub use methods::create_quine::*;
+pub use methods::synthesize_quine::*;
 pub use methods::generate_nft_collection::*;
 pub use methods::generate_rarity_based_emoji::*;
 pub use methods::generate_poetic_text::*;
-pub use methods::evolve_universe::*;
\ No newline at end of file
+pub use methods::evolve_universe::*;
+pub use methods::evolve_universe_with::*;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use serde::{Serialize, Deserialize};
+use lambda_calculus_core::{QuineVerdict, Termination};
 
 /// 🌀 Result of quine creation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,4 +10,7 @@ pub struct QuineResult {
     pub output_emoji: String,
     pub reduction_steps: usize,
     pub is_perfect_quine: bool,
+    pub termination: Termination,
+    /// Exact, cyclic or not a quine, from the fixed-point check
+    pub verdict: QuineVerdict,
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use solfunmeme_metameme::*;
+use lambda_calculus_core::SynthesisBudget;
 
 #[tokio::test]
 async fn test_metameme_engine_creation() {
@@ -27,6 +28,71 @@ async fn test_quine_creation() {
     assert!(quine.reduction_steps >= 0);
 }
 
+#[tokio::test]
+async fn test_quine_synthesis_finds_seeded_quine() {
+    let mut engine = MetaMemeEngine::new();
+    let search = engine.synthesize_quine("🌀", SynthesisBudget::default()).await.unwrap();
+    
+    let quine = search.expr().expect("a quine within the default budget");
+    assert!(quine.to_string().contains("🌀"));
+    assert!(engine.lambda_engine.verify_quine(quine).unwrap().verdict.is_quine());
+}
+
+#[tokio::test]
+async fn test_universe_evolution_reports_generation_stats() {
+    let mut engine = MetaMemeEngine::new_with_seed(18);
+    let result = engine.evolve_universe(3, 0.5).await.unwrap();
+    
+    assert_eq!(result.fitness, "resonance");
+    assert_eq!(result.generation_stats.len(), 3);
+    assert!(result.new_stanzas_created > 0);
+    assert!(result.generation_stats.iter().all(|stats| stats.best_fitness >= stats.mean_fitness));
+}
+
+#[tokio::test]
+async fn test_evolved_stanzas_record_their_lineage() {
+    let mut engine = MetaMemeEngine::new_with_seed(18);
+    let result = engine.evolve_universe(3, 0.5).await.unwrap();
+    let lineage = &engine.stanza_universe.lineage;
+    
+    for &id in &result.evolved_stanza_ids {
+        let stanza = engine.stanza_universe.get_stanza(id).unwrap();
+        let parent_id = stanza.parent_id.expect("evolved stanzas have a parent");
+        assert_eq!(lineage.parents(id)[0], parent_id);
+        assert!(lineage.descendants(parent_id).contains(&id));
+        assert!(stanza.generation >= 1);
+    }
+    
+    let last = *result.evolved_stanza_ids.last().unwrap();
+    let dot = lineage.family_tree(last).to_dot();
+    assert!(dot.starts_with("digraph lineage {"));
+    assert!(dot.contains(&format!("s{} [label=", last)));
+}
+
+#[tokio::test]
+async fn test_universe_survives_save_load_and_journal_replay() {
+    let dir = std::env::temp_dir().join(format!("solfunmeme-universe-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (snapshot, journal) = (dir.join("universe.json"), dir.join("universe.journal"));
+    
+    let mut universe = StanzaUniverse::new_with_seed(20);
+    let child = universe.evolve_stanza(1, 0.9).unwrap();
+    universe.save(&snapshot).unwrap();
+    universe.attach_journal(&journal).unwrap();
+    let grandchild = universe.evolve_stanza(child, 0.9).unwrap();
+    
+    // A fresh process: the snapshot plus the journal restore everything
+    let mut restored = StanzaUniverse::load(&snapshot).unwrap();
+    assert_eq!(restored.attach_journal(&journal).unwrap(), 1);
+    assert_eq!(restored.stanzas.len(), universe.stanzas.len());
+    assert_eq!(restored.next_id, universe.next_id);
+    assert_eq!(restored.emoji_to_stanza, universe.emoji_to_stanza);
+    assert_eq!(restored.lineage.ancestors(grandchild), vec![1, child]);
+    assert!(restored.evolve_stanza(grandchild, 0.5).is_ok());
+    
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
 #[tokio::test]
 async fn test_nft_collection_generation() {
     let mut engine = MetaMemeEngine::new();
@@ -34,4 +100,17 @@ async fn test_nft_collection_generation() {
     
     assert_eq!(nfts.len(), 10);
     assert!(nfts.iter().all(|nft| !nft.emoji_sequence.is_empty()));
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_seeded_nft_collections_are_identical() {
+    let mut first = MetaMemeEngine::new_with_seed(9901);
+    let mut second = MetaMemeEngine::new_with_seed(9901);
+    // Some poems grow exponentially under S; keep their reductions short
+    first.emoji_engine.lambda_engine.max_steps = 25;
+    second.emoji_engine.lambda_engine.max_steps = 25;
+    
+    let first = serde_json::to_string(&first.generate_nft_collection(10).await.unwrap()).unwrap();
+    let second = serde_json::to_string(&second.generate_nft_collection(10).await.unwrap()).unwrap();
+    
+    assert_eq!(first, second);
+}